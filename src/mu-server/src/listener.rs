@@ -5,9 +5,12 @@
 use mu::{Condition, Env, Mu};
 
 pub fn _listener(env: &Env, _config: &str) {
-    let eval_string = Mu::eval_str(env, &"(mu:open :string :output \"\")".to_string()).unwrap();
+    let eval_string = Mu::eval_str(env, "(mu:open :string :output \"\")").unwrap();
 
-    let eof_value = Mu::eval_str(env, &"(env:symbol \"eof\")".to_string()).unwrap();
+    let eof_value = Mu::eval_str(env, "(env:symbol \"eof\")").unwrap();
+
+    // held across evaluations, keep them from being collected or moved
+    let pins = Mu::pin(env, &[eval_string, eof_value]);
 
     loop {
         match Mu::read(env, Mu::std_in(), true, eof_value) {
@@ -23,19 +26,14 @@ pub fn _listener(env: &Env, _config: &str) {
                             Mu::write(env, eval, true, eval_string).unwrap();
                             println!(
                                 "{}",
-                                Mu::eval_str(env, &"(mu:get-string eval-string)".to_string())
-                                    .unwrap()
+                                Mu::eval_str(env, "(mu:get-string eval-string)").unwrap()
                             )
                         }
                         Err(e) => {
                             Mu::write(env, e.object, true, eval_string).unwrap();
-                            let object =
-                                Mu::eval_str(env, &"(mu:get-string eval-string)".to_string())
-                                    .unwrap();
+                            let object = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
                             Mu::write(env, e.source, true, eval_string).unwrap();
-                            let source =
-                                Mu::eval_str(env, &"(mu:get-string eval-string)".to_string())
-                                    .unwrap();
+                            let source = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
 
                             eprintln!(
                                 "eval exception raised by {}, {:?} condition on {}",
@@ -45,11 +43,9 @@ pub fn _listener(env: &Env, _config: &str) {
                     },
                     Err(e) => {
                         Mu::write(env, e.object, true, eval_string).unwrap();
-                        let object =
-                            Mu::eval_str(env, &"(mu:get-string eval-string)".to_string()).unwrap();
+                        let object = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
                         Mu::write(env, e.source, true, eval_string).unwrap();
-                        let source =
-                            Mu::eval_str(env, &"(mu:get-string eval-string)".to_string()).unwrap();
+                        let source = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
 
                         eprintln!(
                             "eval exception raised by {}, {:?} condition on {}",
@@ -63,11 +59,9 @@ pub fn _listener(env: &Env, _config: &str) {
                     std::process::exit(0);
                 } else {
                     Mu::write(env, e.object, true, eval_string).unwrap();
-                    let object =
-                        Mu::eval_str(env, &"(mu:get-string eval-string)".to_string()).unwrap();
+                    let object = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
                     Mu::write(env, e.source, true, eval_string).unwrap();
-                    let source =
-                        Mu::eval_str(env, &"(mu:get-string eval-string)".to_string()).unwrap();
+                    let source = Mu::eval_str(env, "(mu:get-string eval-string)").unwrap();
 
                     eprintln!(
                         "eval exception raised by {}, {:?} condition on {}",
@@ -77,4 +71,6 @@ pub fn _listener(env: &Env, _config: &str) {
             }
         }
     }
+
+    Mu::unpin(env, pins);
 }
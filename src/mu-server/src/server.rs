@@ -124,7 +124,7 @@ pub async fn delay(dur: Duration) {
 //
 thread_local! {
     static CURRENT: RefCell<Option<channel::Sender<Arc<Task>>>> =
-        RefCell::new(None);
+        const { RefCell::new(None) };
 }
 
 struct Task {
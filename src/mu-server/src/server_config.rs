@@ -118,7 +118,13 @@ impl ServerConfig {
                     }
                 }
 
-                let env = Mu::env(&Mu::config(Some(config)));
+                let env = match Mu::try_env(&Mu::config(Some(config))) {
+                    Ok(env) => env,
+                    Err(e) => {
+                        eprintln!("runtime: {e}");
+                        std::process::exit(-1)
+                    }
+                };
 
                 for opt in opts {
                     match opt.0 {
@@ -85,7 +85,13 @@ pub fn main() {
         }
     }
 
-    let env = Mu::env(&Mu::config(_config));
+    let env = match Mu::try_env(&Mu::config(_config)) {
+        Ok(env) => env,
+        Err(e) => {
+            eprintln!("runtime: {e}");
+            std::process::exit(-1)
+        }
+    };
 
     match options(std::env::args().collect()) {
         Some(opts) => {
@@ -11,6 +11,7 @@ use crate::{
         tag::Tag,
         type_::Type,
     },
+    gc::gc_::{Gc as _, GcContext},
     types::{
         cons::Cons,
         fixnum::Fixnum,
@@ -24,6 +25,7 @@ pub trait Apply {
     fn apply(&self, _: Tag, _: Tag) -> exception::Result<Tag>;
     fn apply_(&self, _: Tag, _: Vec<Tag>) -> exception::Result<Tag>;
     fn eval(&self, _: Tag) -> exception::Result<Tag>;
+    fn eval_args(&self, _: Tag) -> exception::Result<Vec<Tag>>;
 }
 
 impl Apply for Env {
@@ -43,6 +45,18 @@ impl Apply for Env {
                     }
                     _ => Err(Exception::err(self, fp_arg, Condition::Type, source))?,
                 },
+                Type::Flonum => match fp_arg_type {
+                    Type::Float | Type::Double => (),
+                    _ => Err(Exception::err(self, fp_arg, Condition::Type, source))?,
+                },
+                Type::Number => match fp_arg_type {
+                    Type::Fixnum | Type::Bignum | Type::Float | Type::Double => (),
+                    _ => Err(Exception::err(self, fp_arg, Condition::Type, source))?,
+                },
+                Type::Integer => match fp_arg_type {
+                    Type::Fixnum | Type::Bignum => (),
+                    _ => Err(Exception::err(self, fp_arg, Condition::Type, source))?,
+                },
                 Type::List => match fp_arg_type {
                     Type::Cons | Type::Null => (),
                     _ => Err(Exception::err(self, fp_arg, Condition::Type, source))?,
@@ -76,11 +90,23 @@ impl Apply for Env {
     }
 
     fn apply(&self, func: Tag, args: Tag) -> exception::Result<Tag> {
+        self.apply_(func, self.eval_args(args)?)
+    }
+
+    // evaluated arguments are protected until the frame is built
+    fn eval_args(&self, args: Tag) -> exception::Result<Vec<Tag>> {
+        let roots = GcContext::push_roots(self, &[]);
         let eval_results: exception::Result<Vec<Tag>> = Cons::list_iter(self, args)
-            .map(|expr| self.eval(expr))
+            .map(|expr| {
+                let value = self.eval(expr)?;
+
+                GcContext::push_roots(self, &[value]);
+                Ok(value)
+            })
             .collect();
 
-        self.apply_(func, eval_results?)
+        GcContext::pop_roots(self, roots);
+        eval_results
     }
 
     fn eval(&self, expr: Tag) -> exception::Result<Tag> {
@@ -98,14 +124,14 @@ impl Apply for Env {
                             let fn_ = Symbol::destruct(self, func).2;
 
                             match fn_.type_of() {
-                                Type::Function => self.apply(fn_, args),
+                                Type::Async | Type::Function => self.apply(fn_, args),
                                 _ => Err(Exception::err(self, func, Condition::Type, "mu:eval"))?,
                             }
                         } else {
                             Err(Exception::err(self, func, Condition::Unbound, "mu:eval"))?
                         }
                     }
-                    Type::Function => self.apply(func, args),
+                    Type::Async | Type::Function => self.apply(func, args),
                     _ => Err(Exception::err(self, func, Condition::Type, "mu:eval"))?,
                 }
             }
@@ -135,11 +161,15 @@ impl CoreFn for Env {
     }
 
     fn mu_apply(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:apply", &[Type::Function, Type::List], fp)?;
+        env.argv_check("mu:apply", &[Type::T, Type::List], fp)?;
 
         let func = fp.argv[0];
         let args = fp.argv[1];
 
+        if !matches!(func.type_of(), Type::Async | Type::Function) {
+            Err(Exception::err(env, func, Condition::Type, "mu:apply"))?;
+        }
+
         fp.value = Frame {
             func,
             argv: Cons::cons_iter(env, args)
@@ -175,7 +205,5 @@ impl CoreFn for Env {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn mu_functions() {
-        assert!(true)
-    }
+    fn mu_functions() {}
 }
@@ -14,9 +14,11 @@ use {
             frame::Frame,
             tag::Tag,
             type_::Type,
+            vm::Vm,
         },
         namespaces::namespace::Namespace,
         types::{
+            async_::Async,
             cons::Cons,
             fixnum::Fixnum,
             function::Function,
@@ -40,7 +42,8 @@ static COMPILER: LazyLock<Compiler> = LazyLock::new(|| Compiler {
     ],
 });
 
-type LexEnv = Vec<(Tag, Vec<Tag>)>;
+// function, frame symbols, captures
+type LexEnv = Vec<(Tag, Vec<Tag>, bool)>;
 type CompilerSpecFn = fn(&Env, Tag, &mut LexEnv) -> exception::Result<Tag>;
 
 pub struct Compiler {
@@ -52,46 +55,96 @@ pub struct Compiler {
 impl Compiler {
     // special forms
     fn compile_lambda(env: &Env, form: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
+        Self::lambda_function(env, form, lex_env, true)
+    }
+
+    // a lambda that may outlive the frames it refers to captures the
+    // values of their symbols, and compiles to the application that
+    // makes a closure of them, see Frame::apply_body. a lambda applied
+    // where it is compiled, or an :if arm, captures nothing.
+    fn lambda_function(
+        env: &Env,
+        form: Tag,
+        lex_env: &mut LexEnv,
+        captures: bool,
+    ) -> exception::Result<Tag> {
         let (lambda, body, symbols) = Self::lambda(env, form)?;
+        let nargs = Cons::length(env, lambda).unwrap();
 
-        let function = Function::new(
-            Fixnum::with_usize(env, Cons::length(env, lambda).unwrap()).unwrap(),
-            Tag::nil(),
-        );
+        let function = Function::new(Fixnum::with_usize(env, nargs).unwrap(), Tag::nil());
 
-        let func = function.with_heap(env);
+        let func = function.with_heap(env)?;
         let mut function = Function::to_image(env, func);
 
-        lex_env.push((func, symbols));
+        lex_env.push((func, symbols, captures));
 
-        function.form = Self::list(env, body, lex_env)?;
+        // bodies that don't assemble are interpreted, see Vm
+        let body = Self::list(env, body, lex_env)?;
+        let frame_len = lex_env.last().unwrap().1.len();
+
+        function.form = Vm::assemble(env, func, frame_len, body).unwrap_or(body);
         Function::update(env, &function, func);
 
-        lex_env.pop();
+        let (_, symbols, _) = lex_env.pop().unwrap();
 
-        Ok(func)
+        Self::closure(env, func, &symbols[nargs..], lex_env)
     }
 
-    // needs implementation
+    // applying an async function returns a future, see Future. its
+    // task runs after the frames it refers to may be gone, so it always
+    // captures.
     fn compile_alambda(env: &Env, form: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
         let (lambda, body, symbols) = Self::lambda(env, form)?;
+        let nargs = Cons::length(env, lambda).unwrap();
 
-        let function = Function::new(
-            Fixnum::with_usize(env, Cons::length(env, lambda).unwrap()).unwrap(),
-            Tag::nil(),
-        );
+        let async_ = Async::new(Fixnum::with_usize(env, nargs).unwrap(), Tag::nil());
 
-        let func = function.with_heap(env);
-        let mut function = Function::to_image(env, func);
+        let func = async_.with_heap(env)?;
+        let mut async_ = Async::to_image(env, func);
 
-        lex_env.push((func, symbols));
+        lex_env.push((func, symbols, true));
 
-        function.form = Self::list(env, body, lex_env)?;
-        Function::update(env, &function, func);
+        async_.form = Self::list(env, body, lex_env)?;
+        Async::update(env, &async_, func);
+
+        let (_, symbols, _) = lex_env.pop().unwrap();
+
+        Self::closure(env, func, &symbols[nargs..], lex_env)
+    }
+
+    // the application that makes a closure of the captured values
+    fn closure(
+        env: &Env,
+        func: Tag,
+        captured: &[Tag],
+        lex_env: &mut LexEnv,
+    ) -> exception::Result<Tag> {
+        if captured.is_empty() {
+            return Ok(func);
+        }
 
-        lex_env.pop();
+        let values = captured
+            .iter()
+            .map(|symbol| Self::symbol(env, *symbol, lex_env))
+            .collect::<exception::Result<Vec<Tag>>>()?;
+
+        Ok(Cons::list(
+            env,
+            &[
+                Self::mu_function(env, "%closure"),
+                func,
+                Self::list_form(env, &values),
+            ],
+        ))
+    }
+
+    // the application that makes a list of the values of forms
+    fn list_form(env: &Env, forms: &[Tag]) -> Tag {
+        let cons = Self::mu_function(env, "cons");
 
-        Ok(func)
+        forms.iter().rev().fold(Tag::nil(), |list, form| {
+            Cons::list(env, &[cons, *form, list])
+        })
     }
 
     fn compile_if(env: &Env, args: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
@@ -100,7 +153,7 @@ impl Compiler {
         }
 
         let if_vec = vec![
-            Namespace::intern(env, env.mu_ns, "%if".into(), Tag::nil()).unwrap(),
+            Namespace::find_symbol(env, env.mu_ns, "%if").unwrap(),
             Cons::nth(env, 0, args).unwrap(),
             Cons::list(
                 env,
@@ -203,46 +256,56 @@ impl Compiler {
         Ok(Cons::list(env, &compile_results?))
     }
 
+    fn mu_function(env: &Env, name: &str) -> Tag {
+        Symbol::destruct(env, Namespace::find_symbol(env, env.mu_ns, name).unwrap()).2
+    }
+
+    // a lexical symbol is captured by each lambda between its binding
+    // and the reference that captures values
     fn symbol(env: &Env, symbol: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
         let ns = Symbol::destruct(env, symbol).0;
 
         if ns.eq_(&UNBOUND) {
-            let lex_sym = lex_env.iter().rev().find_map(|frame| {
-                frame
-                    .1
-                    .iter()
-                    .position(|lex| symbol.eq_(lex))
-                    .map(|nth| (frame.0, nth))
-            });
-
-            match lex_sym {
-                Some(_) => {
-                    let sym_ref = match lex_sym {
-                        Some((tag, nth)) => {
-                            let frame_ref = Symbol::destruct(
-                                env,
-                                Namespace::intern(env, env.mu_ns, "%frame-ref".into(), Tag::nil())
-                                    .unwrap(),
-                            )
-                            .2;
-
-                            Cons::list(
-                                env,
-                                &[frame_ref, tag, Fixnum::with_usize(env, nth).unwrap()],
-                            )
-                        }
-                        None => symbol,
-                    };
-
-                    Ok(sym_ref)
-                }
-                None => Err(Exception::err(env, symbol, Condition::Type, "mu:compile"))?,
+            let Some(depth) = lex_env
+                .iter()
+                .rposition(|frame| frame.1.iter().any(|lex| symbol.eq_(lex)))
+            else {
+                Err(Exception::err(env, symbol, Condition::Type, "mu:compile"))?
+            };
+
+            for frame in lex_env[depth + 1..].iter_mut().filter(|frame| frame.2) {
+                frame.1.push(symbol);
             }
+
+            let frame = lex_env[depth + 1..]
+                .iter()
+                .rfind(|frame| frame.2)
+                .unwrap_or(&lex_env[depth]);
+            let nth = frame.1.iter().position(|lex| symbol.eq_(lex)).unwrap();
+
+            Ok(Cons::list(
+                env,
+                &[
+                    Self::mu_function(env, "%frame-ref"),
+                    frame.0,
+                    Fixnum::with_usize(env, nth).unwrap(),
+                ],
+            ))
         } else {
             Ok(symbol)
         }
     }
 
+    // compile a form applied where it is compiled
+    fn applied(env: &Env, expr: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
+        match expr.type_of() {
+            Type::Cons if Cons::destruct(env, expr).0.eq_(&COMPILER.lambda) => {
+                Self::lambda_function(env, Cons::destruct(env, expr).1, lex_env, false)
+            }
+            _ => Self::compile(env, expr, lex_env),
+        }
+    }
+
     pub fn compile(env: &Env, expr: Tag, lex_env: &mut LexEnv) -> exception::Result<Tag> {
         match expr.type_of() {
             Type::Symbol => Self::symbol(env, expr, lex_env),
@@ -252,26 +315,51 @@ impl Compiler {
                 match func.type_of() {
                     Type::Keyword => Ok(Self::special_form(env, func, args, lex_env)?),
                     Type::Symbol => {
-                        let args = Self::list(env, args, lex_env)?;
+                        // %if applies its arms
+                        let args =
+                            if func.eq_(&Namespace::find_symbol(env, env.mu_ns, "%if").unwrap()) {
+                                let args = Cons::list_iter(env, args)
+                                    .map(|expr| Self::applied(env, expr, lex_env))
+                                    .collect::<exception::Result<Vec<Tag>>>()?;
+
+                                Cons::list(env, &args)
+                            } else {
+                                Self::list(env, args, lex_env)?
+                            };
 
                         if Symbol::is_bound(env, func) {
                             let fn_ = Symbol::destruct(env, func).2;
 
                             match fn_.type_of() {
-                                Type::Function => Ok(Cons::cons(env, fn_, args)),
+                                Type::Async | Type::Function => Ok(Cons::cons(env, fn_, args)),
                                 _ => Err(Exception::err(env, func, Condition::Type, "mu:compile")),
                             }
                         } else {
                             Ok(Cons::cons(env, func, args))
                         }
                     }
-                    Type::Function => Ok(Cons::cons(env, func, Self::list(env, args, lex_env)?)),
+                    Type::Async | Type::Function => {
+                        Ok(Cons::cons(env, func, Self::list(env, args, lex_env)?))
+                    }
                     Type::Cons => {
                         let arglist = Self::list(env, args, lex_env)?;
-                        let fn_ = Self::compile(env, func, lex_env)?;
+                        let fn_ = Self::applied(env, func, lex_env)?;
 
                         match fn_.type_of() {
-                            Type::Function => Ok(Cons::cons(env, fn_, arglist)),
+                            Type::Async | Type::Function => Ok(Cons::cons(env, fn_, arglist)),
+                            // an async closure is made where it is applied
+                            Type::Cons => {
+                                let arglist = Cons::list_iter(env, arglist).collect::<Vec<Tag>>();
+
+                                Ok(Cons::list(
+                                    env,
+                                    &[
+                                        Self::mu_function(env, "apply"),
+                                        fn_,
+                                        Self::list_form(env, &arglist),
+                                    ],
+                                ))
+                            }
                             _ => Err(Exception::err(env, func, Condition::Type, "mu:compile")),
                         }
                     }
@@ -284,6 +372,7 @@ impl Compiler {
 }
 
 pub trait CoreFn {
+    fn mu_closure(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_compile(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_if(_: &Env, _: &mut Frame) -> exception::Result<()>;
 }
@@ -304,6 +393,30 @@ impl CoreFn for Compiler {
         Ok(())
     }
 
+    // a function of the same arity whose application appends the
+    // captured values to its arguments
+    fn mu_closure(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:%closure", &[Type::T, Type::List], fp)?;
+
+        let func = fp.argv[0];
+        let captured = Cons::list_iter(env, fp.argv[1]).collect::<Vec<Tag>>();
+
+        fp.value = match func.type_of() {
+            Type::Function
+                if matches!(func, Tag::Indirect(_))
+                    && Function::closure_destruct(env, func).is_none() =>
+            {
+                Function::closure(env, func, captured)?
+            }
+            Type::Async if Async::closure_destruct(env, func).is_none() => {
+                Async::closure(env, func, captured)?
+            }
+            _ => Err(Exception::err(env, func, Condition::Type, "mu:%closure"))?,
+        };
+
+        Ok(())
+    }
+
     fn mu_compile(env: &Env, fp: &mut Frame) -> exception::Result<()> {
         fp.value = Self::compile(env, fp.argv[0], &mut vec![])?;
 
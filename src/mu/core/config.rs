@@ -4,7 +4,7 @@
 /// Config struct
 use {
     crate::{
-        core::{env::Env, tag::Tag},
+        core::{env::Env, exception, tag::Tag},
         types::{cons::Cons, fixnum::Fixnum, vector::Vector},
     },
     lite_json::{json::JsonValue, json_parser},
@@ -13,6 +13,9 @@ use {
 #[derive(Debug, Clone)]
 pub struct Config {
     pub gc_mode: GcMode,
+    pub gc_threshold: usize,
+    pub heap_file: Option<String>,
+    pub max_pages: usize,
     pub npages: usize,
 }
 
@@ -20,7 +23,10 @@ impl Default for Config {
     fn default() -> Config {
         Config {
             npages: 1024,
+            max_pages: 16384,
             gc_mode: GcMode::None,
+            gc_threshold: 1 << 20,
+            heap_file: None,
         }
     }
 }
@@ -35,6 +41,9 @@ pub enum GcMode {
 pub struct ConfigBuilder {
     pub json: JsonValue,
     pub gc_mode: Option<GcMode>,
+    pub gc_threshold: Option<usize>,
+    pub heap_file: Option<String>,
+    pub max_pages: Option<usize>,
     pub npages: Option<usize>,
 }
 
@@ -45,6 +54,9 @@ impl ConfigBuilder {
         Self {
             json,
             gc_mode: None,
+            gc_threshold: None,
+            heap_file: None,
+            max_pages: None,
             npages: None,
         }
     }
@@ -78,23 +90,52 @@ impl ConfigBuilder {
         self
     }
 
-    fn npages(&mut self) -> &mut Self {
-        let npages = Self::map_json("pages", &self.json);
-
-        self.npages = match npages.unwrap() {
-            #[allow(clippy::cast_possible_truncation)]
-            JsonValue::Number(n) => Some(n.integer as usize),
-            JsonValue::String(nstr) => Some(
-                (*(nstr.iter().collect::<String>()))
-                    .parse::<usize>()
-                    .unwrap(),
-            ),
-            _ => panic!("pages: config string format"),
+    fn heap_file(&mut self) -> &mut Self {
+        let path = Self::map_json("heap-file", &self.json);
+
+        self.heap_file = match path {
+            Some(JsonValue::String(path)) => Some(path.iter().collect::<String>()),
+            Some(_) => panic!("heap-file: config string format"),
+            None => None,
         };
 
         self
     }
 
+    fn usize(term: &str, json: &JsonValue) -> Option<usize> {
+        match Self::map_json(term, json) {
+            None => None,
+            Some(value) => match value {
+                #[allow(clippy::cast_possible_truncation)]
+                JsonValue::Number(n) => Some(n.integer as usize),
+                JsonValue::String(nstr) => Some(
+                    (*(nstr.iter().collect::<String>()))
+                        .parse::<usize>()
+                        .unwrap(),
+                ),
+                _ => panic!("{term}: config string format"),
+            },
+        }
+    }
+
+    fn npages(&mut self) -> &mut Self {
+        self.npages = Self::usize("pages", &self.json);
+
+        self
+    }
+
+    fn gc_threshold(&mut self) -> &mut Self {
+        self.gc_threshold = Self::usize("gc-threshold", &self.json);
+
+        self
+    }
+
+    fn max_pages(&mut self) -> &mut Self {
+        self.max_pages = Self::usize("max-pages", &self.json);
+
+        self
+    }
+
     fn build(&self) -> Config {
         let mut config: Config = Config::default();
 
@@ -102,10 +143,22 @@ impl ConfigBuilder {
             config.npages = npages;
         }
 
+        if let Some(max_pages) = self.max_pages {
+            config.max_pages = max_pages;
+        }
+
         if let Some(gc_mode) = self.gc_mode {
             config.gc_mode = gc_mode;
         }
 
+        if let Some(gc_threshold) = self.gc_threshold {
+            config.gc_threshold = gc_threshold;
+        }
+
+        if let Some(path) = &self.heap_file {
+            config.heap_file = Some(path.clone());
+        }
+
         config
     }
 }
@@ -114,38 +167,60 @@ impl Config {
     pub fn new(conf_option: Option<String>) -> Self {
         match conf_option {
             None => Config::default(),
-            Some(conf) => ConfigBuilder::new(&conf).gc_mode().npages().build(),
+            Some(conf) => ConfigBuilder::new(&conf)
+                .gc_mode()
+                .gc_threshold()
+                .heap_file()
+                .npages()
+                .max_pages()
+                .build(),
         }
     }
 
-    pub fn as_list(&self, env: &Env) -> Tag {
+    pub fn as_list(&self, env: &Env) -> exception::Result<Tag> {
         let gc_mode = match self.gc_mode {
             GcMode::None => "none",
             GcMode::Auto => "auto",
         };
 
-        Cons::list(
+        Ok(Cons::list(
             env,
             &[
                 Cons::new(
-                    Vector::from("gc-mode").with_heap(env),
-                    Vector::from(gc_mode).with_heap(env),
+                    Vector::from("gc-mode").with_heap(env)?,
+                    Vector::from(gc_mode).with_heap(env)?,
+                )
+                .with_heap(env),
+                Cons::new(
+                    Vector::from("gc-threshold").with_heap(env)?,
+                    Fixnum::with_usize(env, env.config.gc_threshold)?,
+                )
+                .with_heap(env),
+                Cons::new(
+                    Vector::from("npages").with_heap(env)?,
+                    Fixnum::with_usize(env, env.config.npages)?,
                 )
                 .with_heap(env),
                 Cons::new(
-                    Vector::from("npages").with_heap(env),
-                    Fixnum::with_usize(env, env.config.npages).unwrap(),
+                    Vector::from("max-pages").with_heap(env)?,
+                    Fixnum::with_usize(env, env.config.max_pages)?,
+                )
+                .with_heap(env),
+                Cons::new(
+                    Vector::from("heap-file").with_heap(env)?,
+                    match &self.heap_file {
+                        Some(path) => Vector::from(path.as_str()).with_heap(env)?,
+                        None => Tag::nil(),
+                    },
                 )
                 .with_heap(env),
             ],
-        )
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test() {
-        assert!(true);
-    }
+    fn test() {}
 }
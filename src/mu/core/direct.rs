@@ -285,7 +285,5 @@ impl DirectTag {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn direct_test() {
-        assert!(true);
-    }
+    fn direct_test() {}
 }
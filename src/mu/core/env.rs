@@ -8,19 +8,27 @@ use {
         core::{
             config::Config,
             core_::{CORE, CORE_FUNCTIONS},
-            frame::Frame,
+            frame::FrameStack,
             tag::Tag,
         },
         namespaces::{
             cache::Cache,
             heap::Heap,
             namespace::{Namespace, StaticSymbols},
+            persist::Persist,
+            system::SystemHeap,
         },
         features::feature::FEATURES,
+        types::future::Task,
         vectors::cache::VecCacheMap,
     },
+    futures_lite::future::block_on,
     futures_locks::RwLock,
-    std::collections::HashMap,
+    smol::Executor,
+    std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        sync::Arc,
+    },
 };
 
 #[cfg(feature = "instrument")]
@@ -34,11 +42,21 @@ pub struct Env {
     // heaps
     pub heap: RwLock<Heap>,
     pub vector_cache: RwLock<VecCacheMap>,
-    pub lexical: RwLock<HashMap<u64, Vec<Frame>>>,
+    pub lexical: RwLock<FrameStack>,
     pub cache: RwLock<Cache>,
+    pub system: Option<Arc<SystemHeap>>,
+
+    // gc roots held by the runtime
+    pub gc_roots: RwLock<Vec<Tag>>,
 
-    // dynamic state
-    pub dynamic: RwLock<Vec<(u64, usize)>>,
+    // streams opened in this environment, released when unreachable
+    pub streams: RwLock<HashSet<u64>>,
+
+    // async tasks by future id, released when unreachable, and the
+    // executor they run on
+    pub futures: RwLock<BTreeMap<u64, Task>>,
+    pub future_id: RwLock<u64>,
+    pub executor: Executor<'static>,
 
     // namespaces
     pub ns_map: RwLock<HashMap<String, (Tag, Namespace)>>,
@@ -55,15 +73,25 @@ pub struct Env {
 
 impl Env {
     pub fn new(config: &Config) -> Self {
+        Self::open(config).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    // an environment on a heap file that can't be restored is an error
+    pub fn open(config: &Config) -> std::io::Result<Self> {
         let mut env = Env {
             cache: RwLock::new(Cache::new()),
             config: config.clone(),
-            dynamic: RwLock::new(Vec::new()),
-            heap: RwLock::new(Heap::new(config)),
+            executor: Executor::new(),
+            future_id: RwLock::new(0),
+            futures: RwLock::new(BTreeMap::new()),
+            gc_roots: RwLock::new(Vec::new()),
+            heap: RwLock::new(Heap::open(config)?),
             keyword_ns: Tag::nil(),
-            lexical: RwLock::new(HashMap::new()),
+            lexical: RwLock::new(FrameStack::default()),
             mu_ns: Tag::nil(),
             ns_map: RwLock::new(HashMap::new()),
+            streams: RwLock::new(HashSet::new()),
+            system: None,
             vector_cache: RwLock::new(HashMap::new()),
             #[cfg(feature = "instrument")]
             prof: RwLock::new(Vec::new()),
@@ -71,9 +99,24 @@ impl Env {
             prof_on: RwLock::new(false),
         };
 
+        // reopen a persistent heap
+        let state = block_on(env.heap.write()).read_state();
+
+        if let Some(state) = state {
+            if let Err(e) = env.restore_state(&state) {
+                // don't let drop overwrite the heap file
+                env.config.heap_file = None;
+
+                return Err(e);
+            }
+
+            block_on(env.heap.write()).promote();
+
+            return Ok(env);
+        }
+
         // establish runtime namespaces
-        env.keyword_ns =
-            Namespace::with_static(&env, "keyword", StaticSymbols(None, None)).unwrap();
+        env.keyword_ns = Namespace::with(&env, "keyword").unwrap();
 
         env.mu_ns = Namespace::with_mu_static(
             &env,
@@ -85,7 +128,8 @@ impl Env {
                 ]),
                 Some(CORE_FUNCTIONS.to_vec()),
             ),
-        );
+        )
+        .unwrap();
 
         // install feature namespaces
         for feature in &FEATURES.features {
@@ -101,12 +145,53 @@ impl Env {
             .unwrap();
         }
 
+        // the runtime namespaces start out in the old generation
+        block_on(env.heap.write()).promote();
+
         /*
         #[cfg(feature = "instrument")]
         Instrument::eprintln(&env, "env: new, mu ns", true, env.mu_ns);
          */
 
-        env
+        Ok(env)
+    }
+
+    // an environment on a shared system heap, the system namespaces
+    // are copied and everything allocated goes on a private heap
+    pub fn with_system(config: &Config, system: &Arc<SystemHeap>) -> Self {
+        let config = Config {
+            heap_file: None,
+            ..config.clone()
+        };
+
+        Env {
+            cache: RwLock::new(system.cache.clone()),
+            config: config.clone(),
+            executor: Executor::new(),
+            future_id: RwLock::new(0),
+            futures: RwLock::new(BTreeMap::new()),
+            gc_roots: RwLock::new(Vec::new()),
+            heap: RwLock::new(Heap::new(&config)),
+            keyword_ns: system.keyword_ns,
+            lexical: RwLock::new(FrameStack::default()),
+            mu_ns: system.mu_ns,
+            ns_map: RwLock::new(system.namespaces()),
+            streams: RwLock::new(HashSet::new()),
+            system: Some(Arc::clone(system)),
+            vector_cache: RwLock::new(HashMap::new()),
+            #[cfg(feature = "instrument")]
+            prof: RwLock::new(Vec::new()),
+            #[cfg(feature = "instrument")]
+            prof_on: RwLock::new(false),
+        }
+    }
+}
+
+impl Drop for Env {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            let _ = self.heap_sync();
+        }
     }
 }
 
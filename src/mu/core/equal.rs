@@ -0,0 +1,206 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// structural equality
+//
+// equal compares conses, vectors, structs, and heap numbers by their
+// contents, and sxhash hashes objects so that equal objects hash the
+// same. both walk their arguments with an explicit stack, long lists
+// and deeply nested data do not recurse on the rust stack.
+//
+// sxhash does not depend on where an object lives in the heap.
+// symbols hash by name, and functions, futures, and hash tables, which
+// are only equal when eq, hash by type.
+use {
+    crate::{
+        core::{env::Env, exception, frame::Frame, tag::Tag, type_::Type},
+        types::{
+            bignum::Bignum,
+            cons::Cons,
+            double::Double,
+            fixnum::Fixnum,
+            struct_::Struct,
+            symbol::Symbol,
+            vector::{Vector, VectorType},
+        },
+    },
+    std::hash::{DefaultHasher, Hash as _, Hasher},
+};
+
+impl Tag {
+    pub fn equal(env: &Env, tag: Tag, other: Tag) -> bool {
+        let mut stack = vec![(tag, other)];
+
+        while let Some((tag, other)) = stack.pop() {
+            if tag.eq_(&other) {
+                continue;
+            }
+
+            if tag.type_of() != other.type_of() {
+                return false;
+            }
+
+            let equal = match tag.type_of() {
+                Type::Bignum => Bignum::to_image(env, tag) == Bignum::to_image(env, other),
+                Type::Double => {
+                    Double::as_f64(env, tag).to_bits() == Double::as_f64(env, other).to_bits()
+                }
+                Type::Cons => {
+                    let cons = Cons::destruct(env, tag);
+                    let other_cons = Cons::destruct(env, other);
+
+                    stack.push((cons.1, other_cons.1));
+                    stack.push((cons.0, other_cons.0));
+                    true
+                }
+                Type::Struct => {
+                    let (stype, vector) = Struct::destruct(env, tag);
+                    let (other_stype, other_vector) = Struct::destruct(env, other);
+
+                    stack.push((vector, other_vector));
+                    stack.push((stype, other_stype));
+                    true
+                }
+                Type::Vector => Self::vector_equal(env, tag, other, &mut stack),
+                _ => false,
+            };
+
+            if !equal {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    // :t vector elements are pushed on the stack, the others compare here
+    fn vector_equal(env: &Env, vector: Tag, other: Tag, stack: &mut Vec<(Tag, Tag)>) -> bool {
+        let vtype = Vector::type_of(env, vector);
+        let length = Vector::length(env, vector);
+
+        if vtype != Vector::type_of(env, other) || length != Vector::length(env, other) {
+            return false;
+        }
+
+        match vtype {
+            VectorType::Char => Vector::as_string(env, vector) == Vector::as_string(env, other),
+            VectorType::Double => (0..length).all(|index| {
+                Vector::double_ref(env, vector, index).to_bits()
+                    == Vector::double_ref(env, other, index).to_bits()
+            }),
+            VectorType::T => {
+                stack.extend(Vector::iter(env, vector).zip(Vector::iter(env, other)));
+                true
+            }
+            VectorType::Bit | VectorType::Byte | VectorType::Fixnum | VectorType::Float => {
+                Vector::iter(env, vector)
+                    .zip(Vector::iter(env, other))
+                    .all(|(el, other_el)| el.eq_(&other_el))
+            }
+        }
+    }
+
+    pub fn sxhash(env: &Env, tag: Tag) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        let mut stack = vec![tag];
+
+        while let Some(tag) = stack.pop() {
+            let type_ = tag.type_of();
+
+            type_.map_typeid().hash(&mut hasher);
+            match type_ {
+                Type::Async | Type::Function | Type::Hash => (),
+                Type::Bignum => {
+                    let bignum = Bignum::to_image(env, tag);
+
+                    bignum.negative.hash(&mut hasher);
+                    bignum.digits.hash(&mut hasher);
+                }
+                Type::Double => Double::as_f64(env, tag).to_bits().hash(&mut hasher),
+                Type::Cons => {
+                    let (car, cdr) = Cons::destruct(env, tag);
+
+                    stack.push(cdr);
+                    stack.push(car);
+                }
+                Type::Struct => {
+                    let (stype, vector) = Struct::destruct(env, tag);
+
+                    stack.push(vector);
+                    stack.push(stype);
+                }
+                Type::Keyword | Type::Symbol => {
+                    let (_, name, _) = Symbol::destruct(env, tag);
+
+                    Vector::as_string(env, name).hash(&mut hasher);
+                }
+                Type::Vector => {
+                    let vtype = Vector::type_of(env, tag);
+                    let length = Vector::length(env, tag);
+
+                    vtype.map_type().map_typeid().hash(&mut hasher);
+                    length.hash(&mut hasher);
+                    match vtype {
+                        VectorType::Char => Vector::as_string(env, tag).hash(&mut hasher),
+                        VectorType::Double => {
+                            for index in 0..length {
+                                Vector::double_ref(env, tag, index)
+                                    .to_bits()
+                                    .hash(&mut hasher);
+                            }
+                        }
+                        VectorType::T => {
+                            let mut elements = Vector::iter(env, tag).collect::<Vec<Tag>>();
+
+                            elements.reverse();
+                            stack.extend(elements);
+                        }
+                        VectorType::Bit
+                        | VectorType::Byte
+                        | VectorType::Fixnum
+                        | VectorType::Float => {
+                            for el in Vector::iter(env, tag) {
+                                el.as_u64().hash(&mut hasher);
+                            }
+                        }
+                    }
+                }
+                _ => tag.as_u64().hash(&mut hasher),
+            }
+        }
+
+        hasher.finish()
+    }
+}
+
+pub trait CoreFn {
+    fn mu_equal(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_sxhash(_: &Env, _: &mut Frame) -> exception::Result<()>;
+}
+
+impl CoreFn for Tag {
+    fn mu_equal(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = if Self::equal(env, fp.argv[0], fp.argv[1]) {
+            Symbol::keyword("t")
+        } else {
+            Tag::nil()
+        };
+
+        Ok(())
+    }
+
+    // a non-negative fixnum
+    fn mu_sxhash(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Fixnum::with_usize_or_panic(
+            usize::try_from(Self::sxhash(env, fp.argv[0]) >> 9).unwrap(),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn equal_test() {}
+}
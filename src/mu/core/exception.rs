@@ -28,6 +28,7 @@ pub enum Condition {
     Except,
     Exit,
     Future,
+    Heap,
     Namespace,
     Open,
     Over,
@@ -55,6 +56,7 @@ static CONDMAP: LazyLock<Vec<(Tag, Condition)>> = LazyLock::new(|| {
         (Symbol::keyword("except"), Condition::Except),
         (Symbol::keyword("exit"), Condition::Exit),
         (Symbol::keyword("future"), Condition::Future),
+        (Symbol::keyword("heap"), Condition::Heap),
         (Symbol::keyword("ns"), Condition::Namespace),
         (Symbol::keyword("open"), Condition::Open),
         (Symbol::keyword("over"), Condition::Over),
@@ -94,7 +96,10 @@ impl Exception {
     }
 
     pub fn err(env: &Env, object: Tag, condition: Condition, referrer: &str) -> Self {
-        let source = Vector::from(referrer).with_heap(env);
+        // an exhausted heap leaves no room for the source name
+        let source = Vector::from(referrer)
+            .with_heap(env)
+            .unwrap_or_else(|_| Tag::nil());
 
         Exception {
             object,
@@ -142,19 +147,16 @@ impl CoreFn for Exception {
         let handler = fp.argv[0];
         let thunk = fp.argv[1];
 
-        let dynamic_ref = block_on(env.dynamic.read());
-        let frame_stack_len = dynamic_ref.len();
-
-        drop(dynamic_ref);
+        let frame_stack_len = block_on(env.lexical.read()).dynamic.len();
 
         fp.value = match env.apply(thunk, Tag::nil()) {
             Ok(value) => value,
             Err(e) => {
                 let args = vec![e.object, Self::map_condkey(&e.condition), e.source];
                 let value = env.apply_(handler, args)?;
-                let mut dynamic_ref = block_on(env.dynamic.write());
+                let mut lexical_ref = block_on(env.lexical.write());
 
-                dynamic_ref.resize(frame_stack_len, (0, 0));
+                lexical_ref.dynamic.resize(frame_stack_len, (0, 0));
 
                 value
             }
@@ -167,7 +169,5 @@ impl CoreFn for Exception {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn exception() {
-        assert!(true)
-    }
+    fn exception() {}
 }
@@ -5,37 +5,121 @@
 use {
     crate::{
         core::{
-            apply::Apply as _,
-            core_::Core,
-            dynamic::Dynamic,
+            apply::{Apply as _, CoreFn as _},
+            compiler::{Compiler, CoreFn as _},
+            core_::{self, Core},
             env::Env,
             exception::{self, Condition, Exception},
             tag::Tag,
             type_::Type,
+            vm::Vm,
         },
+        gc::gc_::{Gc as _, GcContext},
+        namespaces::heap::Heap,
         types::{
-            async_::Async, cons::Cons, fixnum::Fixnum, function::Function, struct_::Struct,
-            symbol::Symbol, vector::Vector,
+            async_::Async, cons::Cons, fixnum::Fixnum, function::Function, future::Future,
+            struct_::Struct, symbol::Symbol, vector::Vector,
         },
     },
     futures_lite::future::block_on,
+    std::collections::HashMap,
 };
 
 #[cfg(feature = "instrument")]
 use crate::features::{feature::Feature, instrument::Instrument};
 
+// the result of evaluating a body, a value or an application in tail
+// position
+pub enum Tail {
+    Call(Tag, Vec<Tag>),
+    Value(Tag),
+}
+
 pub struct Frame {
     pub argv: Vec<Tag>,
     pub func: Tag,
     pub value: Tag,
 }
 
+// the frame stack, frames of active applications and %frame-push,
+// innermost last. a function's arguments are found in its latest
+// frame, each frame links to the frame before it of the same function
+// so the latest is found without a search. applications are listed
+// outermost first with the depth of their frame.
+#[derive(Default)]
+pub struct FrameStack {
+    pub frames: Vec<Frame>,
+    pub dynamic: Vec<(u64, usize)>,
+    links: Vec<Option<usize>>,
+    latest: HashMap<u64, usize>,
+}
+
+impl FrameStack {
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+        self.dynamic.clear();
+        self.links.clear();
+        self.latest.clear();
+    }
+
+    fn push(&mut self, frame: Frame) -> usize {
+        let depth = self.frames.len();
+
+        self.links
+            .push(self.latest.insert(frame.func.as_u64(), depth));
+        self.frames.push(frame);
+
+        depth
+    }
+
+    fn pop(&mut self) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+
+        match self.links.pop().flatten() {
+            Some(depth) => self.latest.insert(frame.func.as_u64(), depth),
+            None => self.latest.remove(&frame.func.as_u64()),
+        };
+    }
+
+    // the depth of a function's latest frame
+    fn depth(&self, func: Tag) -> Option<usize> {
+        match self.frames.last() {
+            Some(frame) if frame.func.eq_(&func) => Some(self.frames.len() - 1),
+            _ => self.latest.get(&func.as_u64()).copied(),
+        }
+    }
+
+    // the frames above a removed frame move down
+    fn remove(&mut self, depth: usize) {
+        self.frames.remove(depth);
+        self.links.clear();
+        self.latest.clear();
+
+        for (depth, frame) in self.frames.iter().enumerate() {
+            self.links
+                .push(self.latest.insert(frame.func.as_u64(), depth));
+        }
+
+        for (_, frame_depth) in &mut self.dynamic {
+            if *frame_depth > depth {
+                *frame_depth -= 1;
+            }
+        }
+    }
+}
+
 impl Frame {
     #[allow(dead_code)]
-    fn to_tag(&self, env: &Env) -> Tag {
+    fn to_tag(&self, env: &Env) -> exception::Result<Tag> {
         let vec = self.argv.clone();
 
-        Struct::new(env, "frame", vec).with_heap(env)
+        Struct::new(env, "frame", vec)?.with_heap(env)
     }
 
     #[allow(dead_code)]
@@ -55,68 +139,105 @@ impl Frame {
         }
     }
 
-    // frame stacks
-    fn frame_stack_push(self, env: &Env) {
-        let id = self.func.as_u64();
-        let mut lexical_ref = block_on(env.lexical.write());
+    fn frame_stack_push(self, env: &Env) -> usize {
+        block_on(env.lexical.write()).push(self)
+    }
 
-        if let std::collections::hash_map::Entry::Vacant(e) = lexical_ref.entry(id) {
-            e.insert(vec![self]);
-        } else {
-            let vec_ref = lexical_ref.get_mut(&id);
+    // an application's frame and its dynamic entry
+    fn application_push(self, env: &Env) {
+        let mut lexical_ref = block_on(env.lexical.write());
+        let func = self.func.as_u64();
+        let depth = lexical_ref.push(self);
 
-            vec_ref.expect("").push(self);
-        }
+        lexical_ref.dynamic.push((func, depth));
     }
 
-    fn frame_stack_pop(env: &Env, id: Tag) {
+    fn application_pop(env: &Env) {
         let mut lexical_ref = block_on(env.lexical.write());
 
-        lexical_ref.get_mut(&id.as_u64()).expect("").pop();
+        lexical_ref.pop();
+        lexical_ref.dynamic.pop();
     }
 
-    fn frame_stack_len(env: &Env, id: Tag) -> Option<usize> {
-        let lexical_ref = block_on(env.lexical.read());
+    fn frame_stack_replace(env: &Env, id: Tag, argv: Vec<Tag>) {
+        let mut lexical_ref = block_on(env.lexical.write());
+        let depth = lexical_ref.depth(id).expect("");
 
-        if lexical_ref.contains_key(&id.as_u64()) {
-            Some(lexical_ref[&id.as_u64()].len())
-        } else {
-            None
-        }
+        lexical_ref.frames[depth].argv = argv;
     }
 
-    pub fn frame_stack_ref(env: &Env, id: Tag, offset: usize, argv: &mut Vec<u64>) {
+    // frame reference, a function with no active frame has nothing to
+    // refer to
+    pub fn frame_ref(env: &Env, id: u64, offset: usize) -> exception::Result<Tag> {
+        let func: Tag = (&id.to_le_bytes()).into();
         let lexical_ref = block_on(env.lexical.read());
 
-        argv.extend(
-            lexical_ref[&id.as_u64()][offset]
-                .argv
-                .iter()
-                .map(Tag::as_u64),
-        );
+        let value = lexical_ref
+            .depth(func)
+            .and_then(|depth| lexical_ref.frames[depth].argv.get(offset).copied());
+
+        drop(lexical_ref);
+        value.ok_or_else(|| Exception::err(env, func, Condition::Range, "mu:%frame-ref"))
     }
 
-    // frame reference
-    fn frame_ref(env: &Env, id: u64, offset: usize) -> Tag {
-        let lexical_ref = block_on(env.lexical.read());
-        let frame = lexical_ref[&id].last().unwrap();
+    // run an async application, see Future
+    pub fn run_async(mut self, env: &Env, func: Tag) -> exception::Result<Tag> {
+        let mut form = Async::destruct(env, func).1;
 
-        frame.argv[offset]
+        // a closure runs its function, the captured values follow the
+        // arguments
+        if form.type_of() == Type::Struct {
+            let (closure_func, captured) = Async::closure_destruct(env, func).unwrap();
+
+            form = Async::destruct(env, closure_func).1;
+            self.func = closure_func;
+            self.argv.extend(captured);
+        }
+
+        match form.type_of() {
+            Type::Null => Ok(Tag::nil()),
+            Type::Cons => {
+                self.application_push(env);
+
+                let value: exception::Result<Tag> =
+                    Cons::list_iter(env, form).try_fold(Tag::nil(), |_, expr| env.eval(expr));
+
+                Self::application_pop(env);
+
+                value
+            }
+            _ => panic!(),
+        }
     }
 
-    // apply
-    pub fn apply(self, env: &Env, func: Tag) -> exception::Result<Tag> {
+    // checks made on every application, tail calls included
+    fn apply_check(env: &Env, func: Tag, argv: &[Tag]) -> exception::Result<()> {
         #[cfg(feature = "instrument")]
         <Feature as Instrument>::instrument_event(env, func).unwrap();
 
-        let (arity, form) = Function::destruct(env, func);
-        let nreqs = usize::try_from(Fixnum::as_i64(arity)).unwrap();
-        let nargs = self.argv.len();
+        if Heap::is_exhausted(env) && GcContext::heap_exhausted(env, func, argv)? {
+            Err(Exception::err(env, func, Condition::Heap, "mu:apply"))?;
+        }
 
-        if nargs != nreqs {
+        GcContext::gc_safepoint(env, func, argv)?;
+
+        let arity = match func.type_of() {
+            Type::Async => Async::destruct(env, func).0,
+            Type::Function => Function::destruct(env, func).0,
+            _ => return Ok(()),
+        };
+
+        if argv.len() != usize::try_from(Fixnum::as_i64(arity)).unwrap() {
             Err(Exception::err(env, func, Condition::Arity, "mu:apply"))?;
         }
 
+        Ok(())
+    }
+
+    // apply
+    pub fn apply(self, env: &Env, func: Tag) -> exception::Result<Tag> {
+        Self::apply_check(env, func, &self.argv)?;
+
         match func.type_of() {
             Type::Symbol => {
                 if Symbol::is_bound(env, func) {
@@ -127,32 +248,8 @@ impl Frame {
                     Err(Exception::err(env, func, Condition::Unbound, "mu:apply"))?
                 }
             }
-            Type::Async => {
-                let form = Async::destruct(env, func).1;
-                let offset = Cons::destruct(env, form).1;
-
-                match form.type_of() {
-                    Type::Null => Ok(Tag::nil()),
-                    Type::Cons => match offset.type_of() {
-                        Type::Null | Type::Cons => {
-                            let offset = Self::frame_stack_len(env, self.func).unwrap_or(0);
-
-                            Dynamic::dynamic_push(env, self.func, offset);
-                            self.frame_stack_push(env);
-
-                            let value: exception::Result<Tag> = Cons::list_iter(env, form)
-                                .try_fold(Tag::nil(), |_, expr| env.eval(expr));
-
-                            Self::frame_stack_pop(env, func);
-                            Dynamic::dynamic_pop(env);
-
-                            value
-                        }
-                        _ => panic!(),
-                    },
-                    _ => panic!(),
-                }
-            }
+            // the application is a task on the executor, see Future
+            Type::Async => Future::spawn(env, func, self.argv),
             Type::Function => {
                 if let Tag::Direct(_) = func {
                     let mut fp = Frame {
@@ -161,31 +258,166 @@ impl Frame {
                         value: self.value,
                     };
 
-                    Core::map_core_function(func).2(env, &mut fp)?;
+                    // core functions may apply, protect the arguments
+                    let roots = GcContext::push_roots(env, &fp.argv);
+                    let value = Core::map_core_function(func).2(env, &mut fp);
+
+                    GcContext::pop_roots(env, roots);
+                    value?;
 
                     return Ok(fp.value);
                 }
 
-                match form.type_of() {
-                    Type::Null => Ok(Tag::nil()),
-                    Type::Cons => {
-                        let offset = Self::frame_stack_len(env, self.func).unwrap_or(0);
+                self.apply_body(env, func)
+            }
+            _ => Err(Exception::err(env, func, Condition::Type, "mu:apply"))?,
+        }
+    }
+
+    // a call in tail position continues this application rather than
+    // recursing. a function already framed here has its frame's
+    // arguments replaced, frame references resolve to the latest frame
+    // of a function either way.
+    fn apply_body(mut self, env: &Env, mut func: Tag) -> exception::Result<Tag> {
+        let mut framed: Vec<Tag> = Vec::new();
+
+        let value = loop {
+            let mut form = Function::destruct(env, func).1;
+
+            // a closure applies its function, the captured values follow
+            // the arguments
+            if form.type_of() == Type::Struct {
+                let (closure_func, captured) = Function::closure_destruct(env, func).unwrap();
+
+                func = closure_func;
+                form = Function::destruct(env, func).1;
+                self.func = func;
+                self.argv.extend(captured);
+            }
+
+            // bytecode reads its arguments from the frame
+            let argv = match form.type_of() {
+                Type::Vector => self.argv.clone(),
+                _ => Vec::new(),
+            };
+
+            if framed.iter().any(|framed| framed.eq_(&func)) {
+                Self::frame_stack_replace(env, func, self.argv);
+            } else {
+                self.application_push(env);
+                framed.push(func);
+            }
+
+            match Self::eval_body(env, form, &argv) {
+                Ok(Tail::Call(tail_func, argv)) => {
+                    if let Err(e) = Self::apply_check(env, tail_func, &argv) {
+                        break Err(e);
+                    }
+
+                    func = tail_func;
+                    self = Frame {
+                        argv,
+                        func,
+                        value: Tag::nil(),
+                    };
+                }
+                Ok(Tail::Value(value)) => break Ok(value),
+                Err(e) => break Err(e),
+            }
+        };
+
+        for _ in framed {
+            Self::application_pop(env);
+        }
+
+        value
+    }
+
+    // evaluate all but the last form of a body
+    fn eval_body(env: &Env, form: Tag, argv: &[Tag]) -> exception::Result<Tail> {
+        match form.type_of() {
+            Type::Null => Ok(Tail::Value(Tag::nil())),
+            Type::Vector => Vm::run(env, form, argv),
+            Type::Cons => {
+                let body = Cons::list_iter(env, form).collect::<Vec<Tag>>();
+                let (tail, body) = body.split_last().unwrap();
+
+                for expr in body {
+                    env.eval(*expr)?;
+                }
+
+                Self::eval_tail(env, *tail)
+            }
+            _ => panic!(),
+        }
+    }
+
+    // a form in tail position, an application of a compiled function,
+    // of the function %if selects, or by mu:apply, is returned to the
+    // caller
+    fn eval_tail(env: &Env, expr: Tag) -> exception::Result<Tail> {
+        if expr.type_of() != Type::Cons || Compiler::is_quoted(env, &expr) {
+            return Ok(Tail::Value(env.eval(expr)?));
+        }
+
+        let (func, arglist) = Cons::destruct(env, expr);
+        let func = match func.type_of() {
+            Type::Symbol if Symbol::is_bound(env, func) => Symbol::destruct(env, func).2,
+            _ => func,
+        };
+
+        // eval reports unbound and non-function applications
+        if func.type_of() != Type::Function {
+            return Ok(Tail::Value(env.eval(expr)?));
+        }
 
-                        Dynamic::dynamic_push(env, self.func, offset);
-                        self.frame_stack_push(env);
+        let argv = env.eval_args(arglist)?;
 
-                        let value: exception::Result<Tag> = Cons::list_iter(env, form)
-                            .try_fold(Tag::nil(), |_, expr| env.eval(expr));
+        Self::tail_apply(env, func, argv)
+    }
 
-                        Self::frame_stack_pop(env, func);
-                        Dynamic::dynamic_pop(env);
+    // an application in tail position
+    pub fn tail_apply(env: &Env, func: Tag, argv: Vec<Tag>) -> exception::Result<Tail> {
+        if func.type_of() != Type::Function {
+            return Ok(Tail::Value(env.apply_(func, argv)?));
+        }
 
-                        value
+        match func {
+            Tag::Direct(_) => {
+                let core_fn = Core::map_core_function(func).2;
+                let is_if = std::ptr::fn_addr_eq(core_fn, Compiler::mu_if as core_::CoreFn);
+                let is_apply = std::ptr::fn_addr_eq(core_fn, Env::mu_apply as core_::CoreFn);
+
+                match argv[..] {
+                    [test, true_fn, false_fn]
+                        if is_if
+                            && true_fn.type_of() == Type::Function
+                            && false_fn.type_of() == Type::Function =>
+                    {
+                        match if test.null_() { false_fn } else { true_fn } {
+                            thunk @ Tag::Indirect(_) => Ok(Tail::Call(thunk, Vec::new())),
+                            thunk @ Tag::Direct(_) => {
+                                Ok(Tail::Value(env.apply_(thunk, Vec::new())?))
+                            }
+                        }
+                    }
+                    // mu:apply of a compiled function is that application
+                    [apply_fn @ Tag::Indirect(_), list]
+                        if is_apply
+                            && apply_fn.type_of() == Type::Function
+                            && matches!(list.type_of(), Type::Null | Type::Cons) =>
+                    {
+                        Ok(Tail::Call(
+                            apply_fn,
+                            Cons::cons_iter(env, list)
+                                .map(|cons| Cons::destruct(env, cons).0)
+                                .collect::<Vec<Tag>>(),
+                        ))
                     }
-                    _ => panic!(),
+                    _ => Ok(Tail::Value(env.apply_(func, argv)?)),
                 }
             }
-            _ => Err(Exception::err(env, func, Condition::Type, "mu:apply"))?,
+            Tag::Indirect(_) => Ok(Tail::Call(func, argv)),
         }
     }
 }
@@ -199,25 +431,24 @@ pub trait CoreFn {
 
 impl CoreFn for Frame {
     fn mu_frames(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        let frames_ref = block_on(env.dynamic.read());
-        let mut frames = Vec::new();
-
-        frames.extend(frames_ref.iter().map(|(func, offset)| {
-            let mut argv = vec![];
-
-            Frame::frame_stack_ref(env, (&func.to_le_bytes()).into(), *offset, &mut argv);
-
-            let vec: Vec<Tag> = argv
-                .into_iter()
-                .map(|f| (&f.to_le_bytes()).into())
-                .collect();
-
-            Cons::cons(
-                env,
-                (&func.to_le_bytes()).into(),
-                Vector::from(vec).with_heap(env),
-            )
-        }));
+        let lexical_ref = block_on(env.lexical.read());
+        let frames = lexical_ref
+            .dynamic
+            .iter()
+            .map(|(func, depth)| {
+                (
+                    (&func.to_le_bytes()).into(),
+                    lexical_ref.frames[*depth].argv.clone(),
+                )
+            })
+            .collect::<Vec<(Tag, Vec<Tag>)>>();
+
+        drop(lexical_ref);
+
+        let frames = frames
+            .into_iter()
+            .map(|(func, argv)| Ok(Cons::cons(env, func, Vector::from(argv).with_heap(env)?)))
+            .collect::<exception::Result<Vec<Tag>>>()?;
 
         fp.value = Cons::list(env, &frames);
         Ok(())
@@ -228,7 +459,13 @@ impl CoreFn for Frame {
 
         env.argv_check("mu:frame-pop", &[Type::Function], fp)?;
 
-        Self::frame_stack_pop(env, fp.value);
+        // the latest frame of the function, the frames of any active
+        // applications above it move down
+        let mut lexical_ref = block_on(env.lexical.write());
+
+        if let Some(depth) = lexical_ref.depth(fp.value) {
+            lexical_ref.remove(depth);
+        }
 
         Ok(())
     }
@@ -258,13 +495,22 @@ impl CoreFn for Frame {
         let frame = fp.argv[0];
         let offset = fp.argv[1];
 
-        env.argv_check("mu:%frame-ref", &[Type::Function, Type::Fixnum], fp)?;
+        env.argv_check("mu:%frame-ref", &[Type::T, Type::Fixnum], fp)?;
+
+        if !matches!(frame.type_of(), Type::Function | Type::Async) {
+            Err(Exception::err(env, frame, Condition::Type, "mu:%frame-ref"))?;
+        }
+
+        let Ok(offset) = usize::try_from(Fixnum::as_i64(offset)) else {
+            Err(Exception::err(
+                env,
+                offset,
+                Condition::Range,
+                "mu:%frame-ref",
+            ))?
+        };
 
-        fp.value = Frame::frame_ref(
-            env,
-            frame.as_u64(),
-            usize::try_from(Fixnum::as_i64(offset)).unwrap(),
-        );
+        fp.value = Frame::frame_ref(env, frame.as_u64(), offset)?;
 
         Ok(())
     }
@@ -10,7 +10,7 @@ use {
             tag::{Tag, TagType},
             type_::Type,
         },
-        modular_bitfield::specifiers::{B2, B59},
+        modular_bitfield::specifiers::{B2, B56},
         types::symbol::Symbol,
     },
     std::sync::LazyLock,
@@ -25,7 +25,22 @@ pub struct IndirectTag {
     #[bits = 3]
     pub tag: TagType,
     pub heap_id: B2,
-    pub image_id: B59,
+    pub image_id: B56,
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    #[bits = 3]
+    pub ext: ExtTag,
+}
+
+// the type of a TagType::Ext tag, heap types past the eight tag types.
+// on a TagType::Symbol tag, Keyword marks a heap keyword.
+#[derive(Specifier, Copy, Clone, Debug, PartialEq, Eq)]
+#[bits = 3]
+pub enum ExtTag {
+    None = 0,
+    Bignum = 1,
+    Double = 2,
+    Hash = 3,
+    Keyword = 4,
 }
 
 impl Default for IndirectTag {
@@ -36,8 +51,11 @@ impl Default for IndirectTag {
 
 static TYPEMAP: LazyLock<Vec<(Tag, Type)>> = LazyLock::new(|| {
     vec![
+        (Symbol::keyword("bignum"), Type::Bignum),
         (Symbol::keyword("cons"), Type::Cons),
+        (Symbol::keyword("double"), Type::Double),
         (Symbol::keyword("func"), Type::Function),
+        (Symbol::keyword("hash"), Type::Hash),
         (Symbol::keyword("nil"), Type::Null),
         (Symbol::keyword("stream"), Type::Stream),
         (Symbol::keyword("struct"), Type::Struct),
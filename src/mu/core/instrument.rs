@@ -9,7 +9,6 @@ use {
         core::{
             apply::Apply as _,
             core_::CORE,
-            dynamic::Dynamic,
             env::Env,
             exception::{self, Condition, Exception},
             tag::Tag,
@@ -60,7 +59,5 @@ impl Instrument for Env {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn instrument() {
-        assert!(true);
-    }
+    fn instrument() {}
 }
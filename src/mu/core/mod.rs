@@ -10,11 +10,12 @@ pub mod compiler;
 pub mod config;
 pub mod core_;
 pub mod direct;
-pub mod dynamic;
 pub mod env;
+pub mod equal;
 pub mod exception;
 pub mod frame;
 pub mod indirect;
 // pub mod mu;
 pub mod tag;
 pub mod type_;
+pub mod vm;
@@ -10,22 +10,25 @@ use {
             env::Env,
             exception::{self, Condition, Exception},
             frame::Frame,
-            indirect::IndirectTag,
+            indirect::{ExtTag, IndirectTag},
             type_::{Type, TYPEKEYMAP},
         },
+        namespaces::heap::Heap,
         types::{
+            bignum::Bignum,
+            double::Double,
             char::Char,
             cons::Cons,
             fixnum::Fixnum,
             float::Float,
             function::Function,
+            hash::Hash,
             stream::Stream,
             struct_::Struct,
             symbol::Symbol,
             vector::{Vector, VectorType},
         },
     },
-    futures_lite::future::block_on,
     std::{convert::From, fmt, sync::LazyLock},
 };
 
@@ -42,7 +45,7 @@ pub enum TagType {
     Direct = 0,   // 56 bit direct objects
     Cons = 1,     // cons heap tag
     Function = 2, // function heap tag
-    Stream = 3,   // stream heap tag
+    Ext = 3,      // extended heap tag, see IndirectTag.ext
     Struct = 4,   // struct heap tags
     Symbol = 5,   // symbol heap tag
     Vector = 6,   // vector heap tag
@@ -78,7 +81,7 @@ impl Tag {
         match self {
             Tag::Direct(tag) => tag.data(),
             Tag::Indirect(heap) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, *heap);
                 let info = heap_ref
                     .image_info(usize::try_from(heap.image_id()).unwrap())
                     .unwrap();
@@ -138,9 +141,18 @@ impl Tag {
                     TagType::Cons => Type::Cons,
                     TagType::Function => Type::Function,
                     TagType::Struct => Type::Struct,
-                    TagType::Symbol => Type::Symbol,
+                    TagType::Symbol => match indirect.ext() {
+                        ExtTag::Keyword => Type::Keyword,
+                        _ => Type::Symbol,
+                    },
                     TagType::Vector => Type::Vector,
-                    _ => panic!(),
+                    TagType::Ext => match indirect.ext() {
+                        ExtTag::Bignum => Type::Bignum,
+                        ExtTag::Double => Type::Double,
+                        ExtTag::Hash => Type::Hash,
+                        ExtTag::None | ExtTag::Keyword => panic!(),
+                    },
+                    TagType::Direct => panic!(),
                 },
             }
         }
@@ -172,7 +184,7 @@ impl CoreFn for Tag {
         let arg = fp.argv[0];
         let slice = arg.as_slice().to_vec();
 
-        fp.value = Vector::from(slice).with_heap(env);
+        fp.value = Vector::from(slice).with_heap(env)?;
 
         Ok(())
     }
@@ -219,15 +231,18 @@ impl CoreFn for Tag {
         let tag = fp.argv[0];
 
         fp.value = match tag.type_of() {
-            Type::Char => Char::view(env, tag),
-            Type::Cons => Cons::view(env, tag),
-            Type::Fixnum => Fixnum::view(env, tag),
-            Type::Float => Float::view(env, tag),
-            Type::Function => Function::view(env, tag),
-            Type::Stream => Stream::view(env, tag),
-            Type::Struct => Struct::view(env, tag),
-            Type::Vector => Vector::view(env, tag),
-            Type::Symbol | Type::Null | Type::Keyword => Symbol::view(env, tag),
+            Type::Bignum => Bignum::view(env, tag)?,
+            Type::Double => Double::view(env, tag)?,
+            Type::Char => Char::view(env, tag)?,
+            Type::Cons => Cons::view(env, tag)?,
+            Type::Fixnum => Fixnum::view(env, tag)?,
+            Type::Float => Float::view(env, tag)?,
+            Type::Function => Function::view(env, tag)?,
+            Type::Hash => Hash::view(env, tag)?,
+            Type::Stream => Stream::view(env, tag)?,
+            Type::Struct => Struct::view(env, tag)?,
+            Type::Vector => Vector::view(env, tag)?,
+            Type::Symbol | Type::Null | Type::Keyword => Symbol::view(env, tag)?,
             _ => Err(Exception::err(env, tag, Condition::Type, "mu:view"))?,
         };
 
@@ -238,7 +253,5 @@ impl CoreFn for Tag {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn types() {
-        assert!(true)
-    }
+    fn types() {}
 }
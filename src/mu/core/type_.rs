@@ -17,13 +17,16 @@ use {
 #[repr(u8)]
 pub enum Type {
     Async,
+    Bignum,
     Bit,
     Byte,
     Char,
     Cons,
+    Double,
     Fixnum,
     Float,
     Function,
+    Hash,
     Keyword,
     Null,
     Stream,
@@ -34,18 +37,24 @@ pub enum Type {
     T,
     List,
     String,
+    Integer,
+    Flonum,
+    Number,
 }
 
 pub static TYPEKEYMAP: LazyLock<Vec<(Type, Tag)>> = LazyLock::new(|| {
     vec![
         (Type::Async, Symbol::keyword("async")),
+        (Type::Bignum, Symbol::keyword("bignum")),
         (Type::Bit, Symbol::keyword("bit")),
         (Type::Byte, Symbol::keyword("byte")),
         (Type::Char, Symbol::keyword("char")),
         (Type::Cons, Symbol::keyword("cons")),
+        (Type::Double, Symbol::keyword("double")),
         (Type::Fixnum, Symbol::keyword("fixnum")),
         (Type::Float, Symbol::keyword("float")),
         (Type::Function, Symbol::keyword("func")),
+        (Type::Hash, Symbol::keyword("hash")),
         (Type::Keyword, Symbol::keyword("keyword")),
         (Type::Null, Symbol::keyword("null")),
         (Type::Stream, Symbol::keyword("stream")),
@@ -57,7 +66,7 @@ pub static TYPEKEYMAP: LazyLock<Vec<(Type, Tag)>> = LazyLock::new(|| {
 });
 
 impl Type {
-    pub const NTYPES: usize = 15;
+    pub const NTYPES: usize = 18;
 
     pub fn map_typesym(self) -> Tag {
         TYPEKEYMAP
@@ -76,7 +85,5 @@ impl Type {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn types() {
-        assert!(true)
-    }
+    fn types() {}
 }
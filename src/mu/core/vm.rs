@@ -0,0 +1,391 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// bytecode
+//
+// compiled lambda bodies are assembled into bytecode for a stack
+// machine. the function form becomes a :t vector of the code, the
+// compiled body it was assembled from, and the constants the code
+// refers to by index. references to the function's own arguments
+// index its frame, %if thunks are inlined as branches, and calls in
+// tail position are returned to Frame::apply_body.
+//
+// a body the assembler does not handle keeps its compiled form and
+// is interpreted.
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            compiler::{Compiler, CoreFn as _},
+            core_::{self, Core},
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::{CoreFn as _, Frame, Tail},
+            tag::Tag,
+            type_::Type,
+        },
+        gc::gc_::{Gc as _, GcContext},
+        types::{cons::Cons, fixnum::Fixnum, function::Function, symbol::Symbol, vector::Vector},
+        vectors::image::VectorImageType,
+    },
+    num_enum::TryFromPrimitive,
+};
+
+// operands are little-endian u16s
+#[derive(Copy, Clone, Debug, PartialEq, Eq, TryFromPrimitive)]
+#[repr(u8)]
+enum Op {
+    Const,    // k, push constant k
+    Arg,      // n, push argument n
+    FrameRef, // k n, push argument n of the latest frame of function k
+    Symbol,   // k, push the value of symbol k
+    Resolve,  // k, push the function bound to symbol k
+    Call,     // n, apply the function under n arguments
+    TailCall, // n, the same, in tail position
+    JumpNil,  // a, pop, jump to a if nil
+    Jump,     // a, jump to a
+    Pop,
+    Return,
+}
+
+pub struct Vm<'a> {
+    env: &'a Env,
+    func: Tag,
+    frame_len: usize,
+    code: Vec<u8>,
+    consts: Vec<Tag>,
+}
+
+impl<'a> Vm<'a> {
+    // the bytecode form of a compiled body, if it assembles
+    pub fn assemble(env: &'a Env, func: Tag, frame_len: usize, body: Tag) -> Option<Tag> {
+        let mut vm = Vm {
+            env,
+            func,
+            frame_len,
+            code: Vec::new(),
+            consts: Vec::new(),
+        };
+
+        vm.body(body, true)?;
+        u16::try_from(vm.code.len()).ok()?;
+
+        let mut form = vec![Vector::from(vm.code).with_heap(env).ok()?, body];
+
+        form.extend(vm.consts);
+
+        Vector::from(form).with_heap(env).ok()
+    }
+
+    // the compiled body of a function form
+    pub fn tree(env: &Env, form: Tag) -> Tag {
+        match form.type_of() {
+            Type::Vector => Vector::ref_(env, form, 1).unwrap(),
+            _ => form,
+        }
+    }
+
+    fn emit(&mut self, op: Op, operands: &[u16]) {
+        self.code.push(op as u8);
+
+        for operand in operands {
+            self.code.extend(operand.to_le_bytes());
+        }
+    }
+
+    fn constant(&mut self, tag: Tag) -> Option<u16> {
+        let index = if let Some(index) = self.consts.iter().position(|constant| constant.eq_(&tag))
+        {
+            index
+        } else {
+            self.consts.push(tag);
+            self.consts.len() - 1
+        };
+
+        u16::try_from(index).ok()
+    }
+
+    fn value(&mut self, op: Op, operands: &[u16], tail: bool) {
+        self.emit(op, operands);
+
+        if tail {
+            self.emit(Op::Return, &[]);
+        }
+    }
+
+    // a jump, returns the offset of its operand
+    fn jump(&mut self, op: Op) -> usize {
+        self.emit(op, &[0]);
+
+        self.code.len() - 2
+    }
+
+    fn patch(&mut self, offset: usize) -> Option<()> {
+        let target = u16::try_from(self.code.len()).ok()?;
+
+        self.code[offset..offset + 2].copy_from_slice(&target.to_le_bytes());
+
+        Some(())
+    }
+
+    fn body(&mut self, body: Tag, tail: bool) -> Option<()> {
+        let forms = Cons::list_iter(self.env, body).collect::<Vec<Tag>>();
+
+        match forms.split_last() {
+            None => {
+                let nil = self.constant(Tag::nil())?;
+
+                self.value(Op::Const, &[nil], tail);
+            }
+            Some((last, forms)) => {
+                for form in forms {
+                    self.form(*form, false)?;
+                    self.emit(Op::Pop, &[]);
+                }
+
+                self.form(*last, tail)?;
+            }
+        }
+
+        Some(())
+    }
+
+    fn form(&mut self, form: Tag, tail: bool) -> Option<()> {
+        if Compiler::is_quoted(self.env, &form) {
+            let constant = self.constant(Compiler::unquote(self.env, &form))?;
+
+            self.value(Op::Const, &[constant], tail);
+
+            return Some(());
+        }
+
+        match form.type_of() {
+            Type::Cons => self.application(form, tail),
+            Type::Symbol => {
+                let symbol = self.constant(form)?;
+
+                self.value(Op::Symbol, &[symbol], tail);
+
+                Some(())
+            }
+            _ => {
+                let constant = self.constant(form)?;
+
+                self.value(Op::Const, &[constant], tail);
+
+                Some(())
+            }
+        }
+    }
+
+    fn application(&mut self, form: Tag, tail: bool) -> Option<()> {
+        let (func, args) = Cons::destruct(self.env, form);
+        let args = Cons::list_iter(self.env, args).collect::<Vec<Tag>>();
+
+        match func.type_of() {
+            Type::Function => {
+                if let Tag::Direct(_) = func {
+                    let core_fn = Core::map_core_function(func).2;
+
+                    if std::ptr::fn_addr_eq(core_fn, Frame::mu_frame_ref as core_::CoreFn) {
+                        if let Some(()) = self.frame_ref(&args, tail) {
+                            return Some(());
+                        }
+                    }
+
+                    if std::ptr::fn_addr_eq(core_fn, Compiler::mu_if as core_::CoreFn) {
+                        if let [test, true_fn, false_fn] = args[..] {
+                            if self.is_thunk(true_fn) && self.is_thunk(false_fn) {
+                                return self.if_(test, true_fn, false_fn, tail);
+                            }
+                        }
+                    }
+                }
+
+                let func = self.constant(func)?;
+
+                self.emit(Op::Const, &[func]);
+            }
+            Type::Async => {
+                let func = self.constant(func)?;
+
+                self.emit(Op::Const, &[func]);
+            }
+            Type::Symbol => {
+                let symbol = self.constant(func)?;
+
+                self.emit(Op::Resolve, &[symbol]);
+            }
+            _ => None?,
+        }
+
+        for arg in &args {
+            self.form(*arg, false)?;
+        }
+
+        let nargs = u16::try_from(args.len()).ok()?;
+
+        self.emit(if tail { Op::TailCall } else { Op::Call }, &[nargs]);
+
+        Some(())
+    }
+
+    // a reference to one of this function's arguments or captured
+    // values indexes the frame, other functions' frames are found at
+    // runtime
+    fn frame_ref(&mut self, args: &[Tag], tail: bool) -> Option<()> {
+        let [func, offset] = args[..] else { None? };
+
+        if offset.type_of() != Type::Fixnum {
+            None?;
+        }
+
+        let offset = u16::try_from(Fixnum::as_i64(offset)).ok()?;
+
+        if func.eq_(&self.func) && usize::from(offset) < self.frame_len {
+            self.value(Op::Arg, &[offset], tail);
+        } else if matches!(func.type_of(), Type::Function | Type::Async) {
+            let func = self.constant(func)?;
+
+            self.value(Op::FrameRef, &[func, offset], tail);
+        } else {
+            None?;
+        }
+
+        Some(())
+    }
+
+    fn is_thunk(&self, func: Tag) -> bool {
+        matches!(func, Tag::Indirect(_))
+            && func.type_of() == Type::Function
+            && Fixnum::as_i64(Function::destruct(self.env, func).0) == 0
+            && Function::closure_destruct(self.env, func).is_none()
+    }
+
+    // the thunks are inlined, they have no frame of their own to refer to
+    fn if_(&mut self, test: Tag, true_fn: Tag, false_fn: Tag, tail: bool) -> Option<()> {
+        let true_body = Self::tree(self.env, Function::destruct(self.env, true_fn).1);
+        let false_body = Self::tree(self.env, Function::destruct(self.env, false_fn).1);
+
+        self.form(test, false)?;
+
+        let false_jump = self.jump(Op::JumpNil);
+
+        self.body(true_body, tail)?;
+
+        let end_jump = (!tail).then(|| self.jump(Op::Jump));
+
+        self.patch(false_jump)?;
+        self.body(false_body, tail)?;
+
+        if let Some(end_jump) = end_jump {
+            self.patch(end_jump)?;
+        }
+
+        Some(())
+    }
+
+    // run a bytecode body, argv is its frame's arguments. a form that
+    // isn't assembled bytecode raises :type
+    pub fn run(env: &Env, form: Tag, argv: &[Tag]) -> exception::Result<Tail> {
+        let not_bytecode = || Exception::err(env, form, Condition::Type, "mu:eval");
+
+        let VectorImageType::T(vec) = Vector::to_data(env, form) else {
+            Err(not_bytecode())?
+        };
+        let code = match vec.first() {
+            Some(code) if code.type_of() == Type::Vector => match Vector::to_data(env, *code) {
+                VectorImageType::Byte(code) => code,
+                _ => Err(not_bytecode())?,
+            },
+            _ => Err(not_bytecode())?,
+        };
+
+        let consts = vec.get(2..).ok_or_else(not_bytecode)?;
+        let mut stack = Vec::<Tag>::new();
+        let mut pc = 0;
+
+        let operand = |pc: &mut usize| {
+            let operand = usize::from(u16::from_le_bytes([code[*pc], code[*pc + 1]]));
+
+            *pc += 2;
+            operand
+        };
+
+        loop {
+            let Some(op) = code.get(pc).and_then(|op| Op::try_from(*op).ok()) else {
+                Err(not_bytecode())?
+            };
+
+            pc += 1;
+            match op {
+                Op::Const => stack.push(consts[operand(&mut pc)]),
+                Op::Arg => stack.push(argv[operand(&mut pc)]),
+                Op::FrameRef => {
+                    let frame = consts[operand(&mut pc)];
+
+                    stack.push(Frame::frame_ref(env, frame.as_u64(), operand(&mut pc))?);
+                }
+                Op::Symbol => {
+                    let symbol = consts[operand(&mut pc)];
+
+                    if !Symbol::is_bound(env, symbol) {
+                        Err(Exception::err(env, symbol, Condition::Unbound, "mu:eval"))?;
+                    }
+
+                    stack.push(Symbol::destruct(env, symbol).2);
+                }
+                Op::Resolve => {
+                    let symbol = consts[operand(&mut pc)];
+
+                    if !Symbol::is_bound(env, symbol) {
+                        Err(Exception::err(env, symbol, Condition::Unbound, "mu:eval"))?;
+                    }
+
+                    let func = Symbol::destruct(env, symbol).2;
+
+                    if !matches!(func.type_of(), Type::Async | Type::Function) {
+                        Err(Exception::err(env, symbol, Condition::Type, "mu:eval"))?;
+                    }
+
+                    stack.push(func);
+                }
+                Op::Call => {
+                    let argv = stack.split_off(stack.len() - operand(&mut pc));
+                    let func = stack.pop().unwrap();
+
+                    // the values on the stack are live across the call
+                    let roots = GcContext::push_roots(env, &stack);
+                    let value = env.apply_(func, argv);
+
+                    GcContext::pop_roots(env, roots);
+                    stack.push(value?);
+                }
+                Op::TailCall => {
+                    let argv = stack.split_off(stack.len() - operand(&mut pc));
+                    let func = stack.pop().unwrap();
+
+                    return Frame::tail_apply(env, func, argv);
+                }
+                Op::JumpNil => {
+                    let target = operand(&mut pc);
+
+                    if stack.pop().unwrap().null_() {
+                        pc = target;
+                    }
+                }
+                Op::Jump => pc = operand(&mut pc),
+                Op::Pop => {
+                    stack.pop();
+                }
+                Op::Return => return Ok(Tail::Value(stack.pop().unwrap())),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn vm_test() {}
+}
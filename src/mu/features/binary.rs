@@ -0,0 +1,327 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+//! binary feature
+//!
+//! pack and unpack fixed width integers and floats in byte vectors and
+//! binary streams. types are :u8 :u16 :u32 :u64, :i8 :i16 :i32 :i64,
+//! :f32 and :f64, byte order is :little or :big. integers outside the
+//! fixnum range raise :over.
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            tag::Tag,
+            type_::Type,
+        },
+        features::feature::Feature,
+        streams::{reader::StreamReader, writer::StreamWriter},
+        types::{
+            double::Double,
+            fixnum::Fixnum,
+            symbol::Symbol,
+            vector::{Vector, VectorType},
+        },
+        vectors::image::VectorImageType,
+    },
+};
+
+pub trait Binary {
+    fn feature() -> Feature;
+}
+
+impl Binary for Feature {
+    fn feature() -> Feature {
+        Feature {
+            functions: Some(vec![
+                ("pack", 5, Feature::binary_pack),
+                ("read", 5, Feature::binary_read),
+                ("unpack", 4, Feature::binary_unpack),
+                ("write", 4, Feature::binary_write),
+            ]),
+            symbols: None,
+            namespace: "feature/binary".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Encoding {
+    Unsigned(usize),
+    Signed(usize),
+    F32,
+    F64,
+}
+
+impl Encoding {
+    fn width(self) -> usize {
+        match self {
+            Encoding::Unsigned(width) | Encoding::Signed(width) => width,
+            Encoding::F32 => 4,
+            Encoding::F64 => 8,
+        }
+    }
+}
+
+impl Feature {
+    // type and byte order keywords, big endian is true
+    fn encoding(
+        env: &Env,
+        type_: Tag,
+        order: Tag,
+        source: &str,
+    ) -> exception::Result<(Encoding, bool)> {
+        let encoding = [
+            ("u8", Encoding::Unsigned(1)),
+            ("u16", Encoding::Unsigned(2)),
+            ("u32", Encoding::Unsigned(4)),
+            ("u64", Encoding::Unsigned(8)),
+            ("i8", Encoding::Signed(1)),
+            ("i16", Encoding::Signed(2)),
+            ("i32", Encoding::Signed(4)),
+            ("i64", Encoding::Signed(8)),
+            ("f32", Encoding::F32),
+            ("f64", Encoding::F64),
+        ]
+        .iter()
+        .find(|(name, _)| type_.eq_(&Symbol::keyword(name)))
+        .map(|(_, encoding)| *encoding);
+
+        let Some(encoding) = encoding else {
+            Err(Exception::err(env, type_, Condition::Type, source))?
+        };
+
+        let big = if order.eq_(&Symbol::keyword("big")) {
+            true
+        } else if order.eq_(&Symbol::keyword("little")) {
+            false
+        } else {
+            Err(Exception::err(env, order, Condition::Type, source))?
+        };
+
+        Ok((encoding, big))
+    }
+
+    // bytes in memory order to a value
+    fn decode(
+        env: &Env,
+        encoding: Encoding,
+        big: bool,
+        bytes: &[u8],
+        source: &str,
+    ) -> exception::Result<Tag> {
+        let mut le_bytes = [0_u8; 8];
+
+        le_bytes[..bytes.len()].copy_from_slice(bytes);
+        if big {
+            le_bytes[..bytes.len()].reverse();
+        }
+
+        let raw = u64::from_le_bytes(le_bytes);
+
+        let integer = |int: Option<i64>| match int {
+            Some(int) if Fixnum::is_i56(int) => Ok(Fixnum::with_i64_or_panic(int)),
+            _ => Err(Exception::err(
+                env,
+                Vector::from(bytes.to_vec()).with_heap(env)?,
+                Condition::Over,
+                source,
+            )),
+        };
+
+        match encoding {
+            Encoding::Unsigned(_) => integer(i64::try_from(raw).ok()),
+            Encoding::Signed(width) => {
+                let shift = 64 - 8 * width;
+
+                integer(Some(((raw << shift) as i64) >> shift))
+            }
+            Encoding::F32 => Ok(f32::from_bits(raw as u32).into()),
+            Encoding::F64 => Double::with_f64(env, f64::from_bits(raw)),
+        }
+    }
+
+    // a value to bytes in memory order
+    fn encode(
+        env: &Env,
+        encoding: Encoding,
+        big: bool,
+        value: Tag,
+        source: &str,
+    ) -> exception::Result<Vec<u8>> {
+        let raw = match encoding {
+            Encoding::Unsigned(width) | Encoding::Signed(width) => {
+                if value.type_of() != Type::Fixnum {
+                    Err(Exception::err(env, value, Condition::Type, source))?;
+                }
+
+                let int = i128::from(Fixnum::as_i64(value));
+                let bits = 8 * u32::try_from(width).unwrap();
+                let range = match encoding {
+                    Encoding::Signed(_) => -(1_i128 << (bits - 1))..(1_i128 << (bits - 1)),
+                    _ => 0..(1_i128 << bits),
+                };
+
+                if !range.contains(&int) {
+                    Err(Exception::err(env, value, Condition::Range, source))?;
+                }
+
+                int as u64
+            }
+            Encoding::F32 | Encoding::F64 => {
+                if !matches!(value.type_of(), Type::Float | Type::Double) {
+                    Err(Exception::err(env, value, Condition::Type, source))?;
+                }
+
+                let fl = Double::from_tag(env, value);
+
+                match encoding {
+                    Encoding::F32 => u64::from((fl as f32).to_bits()),
+                    _ => fl.to_bits(),
+                }
+            }
+        };
+
+        let mut bytes = raw.to_le_bytes()[..encoding.width()].to_vec();
+
+        if big {
+            bytes.reverse();
+        }
+
+        Ok(bytes)
+    }
+
+    // a byte vector and an offset with room for width bytes
+    fn byte_vector(
+        env: &Env,
+        fp: &Frame,
+        width: usize,
+        source: &str,
+    ) -> exception::Result<(Vec<u8>, usize)> {
+        let vector = fp.argv[0];
+        let offset = fp.argv[1];
+
+        if Vector::type_of(env, vector) != VectorType::Byte {
+            Err(Exception::err(env, vector, Condition::Type, source))?;
+        }
+
+        let VectorImageType::Byte(vec) = Vector::to_data(env, vector) else {
+            panic!()
+        };
+
+        match usize::try_from(Fixnum::as_i64(offset)) {
+            Ok(start) if start + width <= vec.len() => Ok((vec, start)),
+            _ => Err(Exception::err(env, offset, Condition::Range, source))?,
+        }
+    }
+
+    fn binary_pack(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "binary:pack",
+            &[
+                Type::Vector,
+                Type::Fixnum,
+                Type::Keyword,
+                Type::Keyword,
+                Type::T,
+            ],
+            fp,
+        )?;
+
+        let (encoding, big) = Self::encoding(env, fp.argv[2], fp.argv[3], "binary:pack")?;
+        let (mut vec, start) = Self::byte_vector(env, fp, encoding.width(), "binary:pack")?;
+        let bytes = Self::encode(env, encoding, big, fp.argv[4], "binary:pack")?;
+
+        vec[start..start + bytes.len()].copy_from_slice(&bytes);
+        fp.value = Vector::from(vec).with_heap(env)?;
+
+        Ok(())
+    }
+
+    // end of file before the first byte is eof-error-p/eof-value, a
+    // partial read is an error
+    fn binary_read(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "binary:read",
+            &[Type::Stream, Type::Keyword, Type::Keyword, Type::T, Type::T],
+            fp,
+        )?;
+
+        let stream = fp.argv[0];
+        let eof_error_p = fp.argv[3];
+        let eof_value = fp.argv[4];
+
+        let (encoding, big) = Self::encoding(env, fp.argv[1], fp.argv[2], "binary:read")?;
+        let mut bytes = Vec::with_capacity(encoding.width());
+
+        while bytes.len() < encoding.width() {
+            match StreamReader::read_byte(env, stream)? {
+                Some(byte) => bytes.push(byte),
+                None if bytes.is_empty() && eof_error_p.null_() => {
+                    fp.value = eof_value;
+                    return Ok(());
+                }
+                None => Err(Exception::err(env, stream, Condition::Eof, "binary:read"))?,
+            }
+        }
+
+        fp.value = Self::decode(env, encoding, big, &bytes, "binary:read")?;
+
+        Ok(())
+    }
+
+    fn binary_unpack(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "binary:unpack",
+            &[Type::Vector, Type::Fixnum, Type::Keyword, Type::Keyword],
+            fp,
+        )?;
+
+        let (encoding, big) = Self::encoding(env, fp.argv[2], fp.argv[3], "binary:unpack")?;
+        let (vec, start) = Self::byte_vector(env, fp, encoding.width(), "binary:unpack")?;
+
+        fp.value = Self::decode(
+            env,
+            encoding,
+            big,
+            &vec[start..start + encoding.width()],
+            "binary:unpack",
+        )?;
+
+        Ok(())
+    }
+
+    fn binary_write(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "binary:write",
+            &[Type::Stream, Type::Keyword, Type::Keyword, Type::T],
+            fp,
+        )?;
+
+        let stream = fp.argv[0];
+        let value = fp.argv[3];
+
+        let (encoding, big) = Self::encoding(env, fp.argv[1], fp.argv[2], "binary:write")?;
+
+        for byte in Self::encode(env, encoding, big, value, "binary:write")? {
+            StreamWriter::write_byte(env, stream, byte)?;
+        }
+
+        fp.value = value;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn binary_test() {}
+}
@@ -133,17 +133,17 @@ impl CoreFn for Feature {
         let alist = vec![
             Cons::cons(
                 env,
-                Vector::from("version").with_heap(env),
-                Vector::from(version).with_heap(env),
+                Vector::from("version").with_heap(env)?,
+                Vector::from(version).with_heap(env)?,
             ),
             Cons::cons(
                 env,
-                Vector::from("features").with_heap(env),
-                Core_::features_as_list(env),
+                Vector::from("features").with_heap(env)?,
+                Core_::features_as_list(env)?,
             ),
             Cons::cons(
                 env,
-                Vector::from("streams").with_heap(env),
+                Vector::from("streams").with_heap(env)?,
                 Core_::nstreams(env),
             ),
         ];
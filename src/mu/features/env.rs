@@ -19,13 +19,18 @@ use {
         namespaces::{
             namespace::Namespace,
             cache::Cache,
-            heap::HeapTypeInfo,
+            heap::{Heap, HeapTypeInfo},
+            persist::Persist,
+            verify::Verify,
         },
         types::{
             async_::Async,
+            bignum::Bignum,
+            double::Double,
             cons::Cons,
             fixnum::Fixnum,
             function::Function,
+            hash::Hash,
             struct_::Struct,
             symbol::Symbol,
             vector::Vector,
@@ -37,8 +42,11 @@ use {
 
 static INFOTYPE: LazyLock<Vec<Tag>> = LazyLock::new(|| {
     vec![
+        Symbol::keyword("bignum"),
         Symbol::keyword("cons"),
+        Symbol::keyword("double"),
         Symbol::keyword("func"),
+        Symbol::keyword("hash"),
         Symbol::keyword("stream"),
         Symbol::keyword("struct"),
         Symbol::keyword("symbol"),
@@ -48,11 +56,11 @@ static INFOTYPE: LazyLock<Vec<Tag>> = LazyLock::new(|| {
 
 pub trait Env {
     fn feature() -> Feature;
-    fn heap_room(_: &env::Env) -> Tag;
+    fn heap_room(_: &env::Env) -> exception::Result<Tag>;
     fn image_size(_: &env::Env, tag: Tag) -> usize;
     fn heap_type(_: &env::Env, type_: Type) -> HeapTypeInfo;
-    fn images_room(_: &env::Env) -> Tag;
-    fn ns_map(_: &env::Env) -> Tag;
+    fn images_room(_: &env::Env) -> exception::Result<Tag>;
+    fn ns_map(_: &env::Env) -> exception::Result<Tag>;
 }
 
 impl Env for Feature {
@@ -61,9 +69,12 @@ impl Env for Feature {
             functions: Some(vec![
                 ("cache-room", 0, Feature::env_cache_room),
                 ("env", 0, Feature::env_env),
+                ("heap-graph", 2, Feature::env_hp_graph),
                 ("heap-info", 0, Feature::env_hp_info),
                 ("heap-room", 0, Feature::env_hp_room),
                 ("heap-size", 1, Feature::env_hp_size),
+                ("heap-sync", 0, Feature::env_hp_sync),
+                ("heap-verify", 0, Feature::env_hp_verify),
                 ("namespace", 1, Feature::env_namespace),
             ]),
             symbols: None,
@@ -74,10 +85,13 @@ impl Env for Feature {
     fn image_size(env: &env::Env, tag: Tag) -> usize {
         match tag.type_of() {
             Type::Async => Async::image_size(env, tag),
+            Type::Bignum => Bignum::image_size(env, tag),
+            Type::Double => Double::image_size(env, tag),
             Type::Cons => Cons::image_size(env, tag),
             Type::Function => Function::image_size(env, tag),
+            Type::Hash => Hash::image_size(env, tag),
             Type::Struct => Struct::image_size(env, tag),
-            Type::Symbol => Symbol::image_size(env, tag),
+            Type::Symbol | Type::Keyword => Symbol::image_size(env, tag),
             Type::Vector => Vector::image_size(env, tag),
             _ => std::mem::size_of::<DirectTag>(),
         }
@@ -89,7 +103,7 @@ impl Env for Feature {
         heap_ref.alloc_map[type_ as usize]
     }
 
-    fn heap_room(env: &env::Env) -> Tag {
+    fn heap_room(env: &env::Env) -> exception::Result<Tag> {
         let mut vec = Vec::new();
 
         for htype in INFOTYPE.iter() {
@@ -104,10 +118,20 @@ impl Env for Feature {
             ]);
         }
 
+        // free space by size class, :free class-size blocks bytes
+        for (class, nblocks, bytes) in Heap::heap_free_classes(env) {
+            vec.extend(vec![
+                Symbol::keyword("free"),
+                Fixnum::with_usize(env, class).unwrap(),
+                Fixnum::with_usize(env, nblocks).unwrap(),
+                Fixnum::with_usize(env, bytes).unwrap(),
+            ]);
+        }
+
         Vector::from(vec).with_heap(env)
     }
 
-    fn images_room(env: &env::Env) -> Tag {
+    fn images_room(env: &env::Env) -> exception::Result<Tag> {
         let mut vec = Vec::new();
 
         for htype in INFOTYPE.iter() {
@@ -126,23 +150,26 @@ impl Env for Feature {
         Vector::from(vec).with_heap(env)
     }
 
-    fn ns_map(env: &env::Env) -> Tag {
+    fn ns_map(env: &env::Env) -> exception::Result<Tag> {
         let ns_ref = block_on(env.ns_map.read());
         let vec = ns_ref
             .keys()
             .map(|name| Vector::from((*name).clone()).with_heap(env))
-            .collect::<Vec<Tag>>();
+            .collect::<exception::Result<Vec<Tag>>>()?;
 
-        Cons::list(env, &vec)
+        Ok(Cons::list(env, &vec))
     }
 }
 
 pub trait CoreFn {
     fn env_cache_room(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
     fn env_env(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
+    fn env_hp_graph(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
     fn env_hp_info(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
     fn env_hp_room(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
     fn env_hp_size(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
+    fn env_hp_sync(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
+    fn env_hp_verify(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
     fn env_namespace(_: &env::Env, _: &mut Frame) -> exception::Result<()>;
 }
 
@@ -153,24 +180,29 @@ impl CoreFn for Feature {
         println!("type           :bump");
         println!("page-size      {}", heap_ref.page_size);
         println!("npages         {}", heap_ref.npages);
+        println!("max-pages      {}", heap_ref.max_pages);
         println!("size           {}", heap_ref.size);
         println!("alloc-barrier  {}", heap_ref.alloc_barrier);
         println!("free-space     {}", heap_ref.free_space);
         println!("gc-allocated   {}", heap_ref.gc_allocated);
 
+        if let Some(system) = &env.system {
+            println!("system-size    {}", system.heap.alloc_barrier);
+        }
+
         fp.value = Tag::nil();
 
         Ok(())
     }
 
     fn env_cache_room(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
-        fp.value = Self::images_room(env);
+        fp.value = Self::images_room(env)?;
 
         Ok(())
     }
 
     fn env_hp_room(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
-        fp.value = Self::heap_room(env);
+        fp.value = Self::heap_room(env)?;
 
         Ok(())
     }
@@ -181,24 +213,71 @@ impl CoreFn for Feature {
         Ok(())
     }
 
+    fn env_hp_sync(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = match env.config.heap_file {
+            None => Tag::nil(),
+            Some(_) => match env.heap_sync() {
+                Ok(()) => Symbol::keyword("t"),
+                Err(_) => Err(Exception::err(
+                    env,
+                    Tag::nil(),
+                    Condition::Write,
+                    "feature/env:heap-sync",
+                ))?,
+            },
+        };
+
+        Ok(())
+    }
+
+    fn env_hp_verify(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
+        let errors = env.heap_verify();
+
+        fp.value = Cons::list(
+            env,
+            &errors
+                .iter()
+                .map(|error| Vector::from(error.as_str()).with_heap(env))
+                .collect::<exception::Result<Vec<Tag>>>()?,
+        );
+
+        Ok(())
+    }
+
+    fn env_hp_graph(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("feature/env:heap-graph", &[Type::T, Type::String], fp)?;
+
+        fp.value = match env.heap_graph(fp.argv[0], &Vector::as_string(env, fp.argv[1])) {
+            Ok(nodes) => Fixnum::with_usize(env, nodes)?,
+            Err(_) => Err(Exception::err(
+                env,
+                fp.argv[1],
+                Condition::Open,
+                "feature/env:heap-graph",
+            ))?,
+        };
+
+        Ok(())
+    }
+
     fn env_env(env: &env::Env, fp: &mut Frame) -> exception::Result<()> {
         fp.value = Cons::list(
             env,
             &[
                 Cons::cons(
                     env,
-                    Vector::from("config").with_heap(env),
-                    env.config.as_list(env),
+                    Vector::from("config").with_heap(env)?,
+                    env.config.as_list(env)?,
                 ),
                 Cons::cons(
                     env,
-                    Vector::from("namespaces").with_heap(env),
-                    Self::ns_map(env),
+                    Vector::from("namespaces").with_heap(env)?,
+                    Self::ns_map(env)?,
                 ),
                 Cons::cons(
                     env,
-                    Vector::from("heap-room").with_heap(env),
-                    Self::heap_room(env),
+                    Vector::from("heap-room").with_heap(env)?,
+                    Self::heap_room(env)?,
                 ),
             ],
         );
@@ -11,14 +11,22 @@ use {
     std::sync::LazyLock,
 };
 
+#[cfg(feature = "binary")]
+use crate::features::binary::Binary;
 #[cfg(feature = "core")]
 use crate::features::core::Core;
 #[cfg(feature = "env")]
 use crate::features::env::Env;
 #[cfg(feature = "instrument")]
 use crate::features::instrument::Instrument;
+#[cfg(feature = "math")]
+use crate::features::math::Math;
+#[cfg(feature = "string")]
+use crate::features::string::Strings;
 #[cfg(feature = "system")]
 use crate::features::system::System;
+#[cfg(feature = "vector")]
+use crate::features::vector::Vectors;
 
 pub static FEATURES: LazyLock<Features> = LazyLock::new(Features::new);
 
@@ -42,6 +50,14 @@ impl Features {
             <Feature as Env>::feature(),
             #[cfg(feature = "system")]
             <Feature as System>::feature(),
+            #[cfg(feature = "math")]
+            <Feature as Math>::feature(),
+            #[cfg(feature = "string")]
+            <Feature as Strings>::feature(),
+            #[cfg(feature = "vector")]
+            <Feature as Vectors>::feature(),
+            #[cfg(feature = "binary")]
+            <Feature as Binary>::feature(),
             #[cfg(feature = "instrument")]
             <Feature as Instrument>::feature(),
         ];
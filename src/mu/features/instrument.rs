@@ -91,7 +91,7 @@ impl CoreFn for Feature {
                 })
                 .collect::<Vec<Tag>>();
 
-            fp.value = Vector::from(prof_vec).with_heap(env)
+            fp.value = Vector::from(prof_vec).with_heap(env)?
         } else {
             return Err(Exception::err(
                 env,
@@ -0,0 +1,457 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+//! math feature
+//!
+//! numeric conversions, rounding, remainders and transcendental
+//! functions. mixed arguments promote to the widest class, integer
+//! to float to double, and transcendental functions of integers
+//! are floats.
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_precision_loss)]
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            tag::Tag,
+            type_::Type,
+        },
+        features::feature::Feature,
+        types::{
+            bignum::Bignum,
+            double::Double,
+            fixnum::Fixnum,
+            float::Float,
+            symbol::Symbol,
+        },
+    },
+    std::cmp::Ordering,
+};
+
+pub trait Math {
+    fn feature() -> Feature;
+}
+
+impl Math for Feature {
+    fn feature() -> Feature {
+        Feature {
+            functions: Some(vec![
+                ("abs", 1, Feature::math_abs),
+                ("ceiling", 1, Feature::math_ceiling),
+                ("cos", 1, Feature::math_cos),
+                ("double", 1, Feature::math_double),
+                ("exp", 1, Feature::math_exp),
+                ("fequal", 2, Feature::math_fequal),
+                ("fgreater-than", 2, Feature::math_fgreater_than),
+                ("float", 1, Feature::math_float),
+                ("floor", 1, Feature::math_floor),
+                ("log", 1, Feature::math_log),
+                ("max", 2, Feature::math_max),
+                ("min", 2, Feature::math_min),
+                ("mod", 2, Feature::math_mod),
+                ("rem", 2, Feature::math_rem),
+                ("round", 1, Feature::math_round),
+                ("sin", 1, Feature::math_sin),
+                ("sqrt", 1, Feature::math_sqrt),
+                ("truncate", 1, Feature::math_truncate),
+            ]),
+            symbols: None,
+            namespace: "feature/math".into(),
+        }
+    }
+}
+
+// number classes, ordered by width
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Class {
+    Integer,
+    Float,
+    Double,
+}
+
+impl Feature {
+    fn class(tag: Tag) -> Class {
+        match tag.type_of() {
+            Type::Fixnum | Type::Bignum => Class::Integer,
+            Type::Float => Class::Float,
+            Type::Double => Class::Double,
+            _ => panic!(),
+        }
+    }
+
+    fn as_f64(env: &Env, tag: Tag) -> f64 {
+        match tag.type_of() {
+            Type::Fixnum => Fixnum::as_i64(tag) as f64,
+            Type::Bignum => Bignum::to_image(env, tag).to_f64(),
+            _ => Double::from_tag(env, tag),
+        }
+    }
+
+    // a float or double result, integer results are floats
+    fn flonum(env: &Env, arg: Tag, fl: f64, class: Class, source: &str) -> exception::Result<Tag> {
+        if fl.is_nan() {
+            Err(Exception::err(env, arg, Condition::Range, source))?;
+        }
+
+        if fl.is_infinite() {
+            Err(Exception::err(env, arg, Condition::Over, source))?;
+        }
+
+        match class {
+            Class::Integer | Class::Float => {
+                let fl = fl as f32;
+
+                if fl.is_infinite() {
+                    Err(Exception::err(env, arg, Condition::Over, source))?;
+                }
+
+                Ok(fl.into())
+            }
+            Class::Double => Double::with_f64(env, fl),
+        }
+    }
+
+    // round a float to an integer, integers are unchanged
+    fn integer(
+        env: &Env,
+        fp: &Frame,
+        round: fn(f64) -> f64,
+        source: &str,
+    ) -> exception::Result<Tag> {
+        env.argv_check(source, &[Type::Number], fp)?;
+
+        let arg = fp.argv[0];
+
+        match Self::class(arg) {
+            Class::Integer => Ok(arg),
+            Class::Float | Class::Double => {
+                let fl = round(Double::from_tag(env, arg));
+
+                if !fl.is_finite() {
+                    Err(Exception::err(env, arg, Condition::Range, source))?;
+                }
+
+                Bignum::from_f64(fl).integer(env, source)
+            }
+        }
+    }
+
+    fn transcendental(
+        env: &Env,
+        fp: &Frame,
+        op: fn(f64) -> f64,
+        source: &str,
+    ) -> exception::Result<Tag> {
+        env.argv_check(source, &[Type::Number], fp)?;
+
+        let arg = fp.argv[0];
+
+        Self::flonum(
+            env,
+            arg,
+            op(Self::as_f64(env, arg)),
+            Self::class(arg),
+            source,
+        )
+    }
+
+    // truncating or flooring remainder
+    fn remainder(env: &Env, fp: &Frame, floor: bool, source: &str) -> exception::Result<Tag> {
+        env.argv_check(source, &[Type::Number, Type::Number], fp)?;
+
+        let dividend = fp.argv[0];
+        let divisor = fp.argv[1];
+
+        match Self::class(dividend).max(Self::class(divisor)) {
+            Class::Integer => {
+                if dividend.type_of() == Type::Fixnum && divisor.type_of() == Type::Fixnum {
+                    let divisor_ = Fixnum::as_i64(divisor);
+
+                    if divisor_ == 0 {
+                        Err(Exception::err(env, divisor, Condition::ZeroDivide, source))?;
+                    }
+
+                    let rem = Fixnum::as_i64(dividend) % divisor_;
+
+                    Ok(Fixnum::with_i64_or_panic(
+                        if floor && rem != 0 && (rem < 0) != (divisor_ < 0) {
+                            rem + divisor_
+                        } else {
+                            rem
+                        },
+                    ))
+                } else {
+                    let divisor_ = Bignum::from_tag(env, divisor);
+
+                    if divisor_.is_zero() {
+                        Err(Exception::err(env, divisor, Condition::ZeroDivide, source))?;
+                    }
+
+                    let rem = Bignum::from_tag(env, dividend).rem(&divisor_);
+
+                    if floor && !rem.is_zero() && rem.negative != divisor_.negative {
+                        rem.add(&divisor_).integer(env, source)
+                    } else {
+                        rem.integer(env, source)
+                    }
+                }
+            }
+            class => {
+                let divisor_ = Self::as_f64(env, divisor);
+
+                if divisor_ == 0.0 {
+                    Err(Exception::err(env, divisor, Condition::ZeroDivide, source))?;
+                }
+
+                let rem = Self::as_f64(env, dividend) % divisor_;
+
+                Self::flonum(
+                    env,
+                    dividend,
+                    if floor && rem != 0.0 && (rem < 0.0) != (divisor_ < 0.0) {
+                        rem + divisor_
+                    } else {
+                        rem
+                    },
+                    class,
+                    source,
+                )
+            }
+        }
+    }
+
+    fn compare(env: &Env, number: Tag, other: Tag) -> Ordering {
+        match (Self::class(number), Self::class(other)) {
+            (Class::Integer, Class::Integer) => match (number.type_of(), other.type_of()) {
+                (Type::Fixnum, Type::Fixnum) => Fixnum::as_i64(number).cmp(&Fixnum::as_i64(other)),
+                _ => Bignum::from_tag(env, number).cmp(&Bignum::from_tag(env, other)),
+            },
+            _ => Self::as_f64(env, number)
+                .partial_cmp(&Self::as_f64(env, other))
+                .unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+pub trait CoreFn {
+    fn math_abs(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_ceiling(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_cos(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_double(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_exp(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_fequal(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_fgreater_than(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_float(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_floor(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_log(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_max(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_min(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_mod(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_rem(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_round(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_sin(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_sqrt(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn math_truncate(_: &Env, _: &mut Frame) -> exception::Result<()>;
+}
+
+impl CoreFn for Feature {
+    fn math_abs(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:abs", &[Type::Number], fp)?;
+
+        let arg = fp.argv[0];
+
+        fp.value = match arg.type_of() {
+            Type::Fixnum => Bignum::with_i128(env, i128::from(Fixnum::as_i64(arg)).abs())?,
+            Type::Bignum => {
+                let mut bignum = Bignum::to_image(env, arg);
+
+                bignum.negative = false;
+                bignum.integer(env, "math:abs")?
+            }
+            Type::Float => Float::as_f32(env, arg).abs().into(),
+            Type::Double => Double::with_f64(env, Double::as_f64(env, arg).abs())?,
+            _ => panic!(),
+        };
+
+        Ok(())
+    }
+
+    fn math_ceiling(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::integer(env, fp, f64::ceil, "math:ceiling")?;
+
+        Ok(())
+    }
+
+    fn math_cos(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::transcendental(env, fp, f64::cos, "math:cos")?;
+
+        Ok(())
+    }
+
+    fn math_double(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:double", &[Type::Number], fp)?;
+
+        let arg = fp.argv[0];
+
+        fp.value = match arg.type_of() {
+            Type::Double => arg,
+            _ => Self::flonum(
+                env,
+                arg,
+                Self::as_f64(env, arg),
+                Class::Double,
+                "math:double",
+            )?,
+        };
+
+        Ok(())
+    }
+
+    fn math_exp(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::transcendental(env, fp, f64::exp, "math:exp")?;
+
+        Ok(())
+    }
+
+    fn math_fequal(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:fequal", &[Type::Flonum, Type::Flonum], fp)?;
+
+        #[allow(clippy::float_cmp)]
+        let equal = Double::from_tag(env, fp.argv[0]) == Double::from_tag(env, fp.argv[1]);
+
+        fp.value = if equal {
+            Symbol::keyword("t")
+        } else {
+            Tag::nil()
+        };
+
+        Ok(())
+    }
+
+    fn math_fgreater_than(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:fgreater-than", &[Type::Flonum, Type::Flonum], fp)?;
+
+        fp.value = if Double::from_tag(env, fp.argv[0]) > Double::from_tag(env, fp.argv[1]) {
+            Symbol::keyword("t")
+        } else {
+            Tag::nil()
+        };
+
+        Ok(())
+    }
+
+    fn math_float(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:float", &[Type::Number], fp)?;
+
+        let arg = fp.argv[0];
+
+        fp.value = match arg.type_of() {
+            Type::Float => arg,
+            _ => Self::flonum(env, arg, Self::as_f64(env, arg), Class::Float, "math:float")?,
+        };
+
+        Ok(())
+    }
+
+    fn math_floor(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::integer(env, fp, f64::floor, "math:floor")?;
+
+        Ok(())
+    }
+
+    fn math_log(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:log", &[Type::Number], fp)?;
+
+        if Self::as_f64(env, fp.argv[0]) <= 0.0 {
+            Err(Exception::err(
+                env,
+                fp.argv[0],
+                Condition::Range,
+                "math:log",
+            ))?;
+        }
+
+        fp.value = Self::transcendental(env, fp, f64::ln, "math:log")?;
+
+        Ok(())
+    }
+
+    fn math_max(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:max", &[Type::Number, Type::Number], fp)?;
+
+        fp.value = match Self::compare(env, fp.argv[0], fp.argv[1]) {
+            Ordering::Less => fp.argv[1],
+            _ => fp.argv[0],
+        };
+
+        Ok(())
+    }
+
+    fn math_min(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:min", &[Type::Number, Type::Number], fp)?;
+
+        fp.value = match Self::compare(env, fp.argv[0], fp.argv[1]) {
+            Ordering::Greater => fp.argv[1],
+            _ => fp.argv[0],
+        };
+
+        Ok(())
+    }
+
+    fn math_mod(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::remainder(env, fp, true, "math:mod")?;
+
+        Ok(())
+    }
+
+    fn math_rem(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::remainder(env, fp, false, "math:rem")?;
+
+        Ok(())
+    }
+
+    fn math_round(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::integer(env, fp, f64::round_ties_even, "math:round")?;
+
+        Ok(())
+    }
+
+    fn math_sin(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::transcendental(env, fp, f64::sin, "math:sin")?;
+
+        Ok(())
+    }
+
+    fn math_sqrt(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("math:sqrt", &[Type::Number], fp)?;
+
+        if Self::as_f64(env, fp.argv[0]) < 0.0 {
+            Err(Exception::err(
+                env,
+                fp.argv[0],
+                Condition::Range,
+                "math:sqrt",
+            ))?;
+        }
+
+        fp.value = Self::transcendental(env, fp, f64::sqrt, "math:sqrt")?;
+
+        Ok(())
+    }
+
+    fn math_truncate(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::integer(env, fp, f64::trunc, "math:truncate")?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn math_test() {}
+}
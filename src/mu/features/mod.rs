@@ -2,6 +2,8 @@
 //  SPDX-License-Identifier: MIT
 
 //! features crate
+#[cfg(feature = "binary")]
+pub mod binary;
 #[cfg(feature = "core")]
 pub mod core;
 #[cfg(feature = "env")]
@@ -9,5 +11,11 @@ pub mod env;
 pub mod feature;
 #[cfg(feature = "instrument")]
 pub mod instrument;
+#[cfg(feature = "math")]
+pub mod math;
+#[cfg(feature = "string")]
+pub mod string;
 #[cfg(feature = "system")]
 pub mod system;
+#[cfg(feature = "vector")]
+pub mod vector;
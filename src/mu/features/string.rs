@@ -0,0 +1,271 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+//! string feature
+//!
+//! string primitives on direct and heap char vectors. indices and
+//! lengths count characters, not bytes.
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            tag::Tag,
+            type_::Type,
+        },
+        features::feature::Feature,
+        types::{
+            bignum::Bignum,
+            char::Char,
+            cons::Cons,
+            double::Double,
+            fixnum::Fixnum,
+            symbol::Symbol,
+            vector::{Vector, VectorType},
+        },
+    },
+    std::cmp::Ordering,
+};
+
+pub trait Strings {
+    fn feature() -> Feature;
+}
+
+impl Strings for Feature {
+    fn feature() -> Feature {
+        Feature {
+            functions: Some(vec![
+                ("compare", 2, Feature::string_compare),
+                ("concat", 1, Feature::string_concat),
+                ("downcase", 1, Feature::string_downcase),
+                ("index", 2, Feature::string_index),
+                ("less-than", 2, Feature::string_less_than),
+                ("parse-float", 1, Feature::string_parse_float),
+                ("parse-integer", 1, Feature::string_parse_integer),
+                ("search", 2, Feature::string_search),
+                ("split", 2, Feature::string_split),
+                ("substring", 3, Feature::string_substring),
+                ("trim", 1, Feature::string_trim),
+                ("upcase", 1, Feature::string_upcase),
+            ]),
+            symbols: None,
+            namespace: "feature/string".into(),
+        }
+    }
+}
+
+impl Feature {
+    // the character index of a byte offset, or nil
+    fn char_index(str: &str, offset: Option<usize>) -> Tag {
+        match offset {
+            Some(offset) => Fixnum::with_usize_or_panic(str[..offset].chars().count()),
+            None => Tag::nil(),
+        }
+    }
+
+    fn string_compare(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:compare", &[Type::String, Type::String], fp)?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+
+        fp.value = Fixnum::with_i64_or_panic(match str.cmp(&Vector::as_string(env, fp.argv[1])) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        });
+
+        Ok(())
+    }
+
+    fn string_concat(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:concat", &[Type::List], fp)?;
+
+        let list = fp.argv[0];
+
+        let type_check = Cons::list_iter(env, list).find(|str| {
+            !matches!(str.type_of(), Type::Vector if Vector::type_of(env, *str) == VectorType::Char)
+        });
+
+        if let Some(str) = type_check {
+            Err(Exception::err(env, str, Condition::Type, "string:concat"))?;
+        }
+
+        let concat = Cons::list_iter(env, list)
+            .map(|str| Vector::as_string(env, str))
+            .collect::<String>();
+
+        fp.value = Vector::from(concat).with_heap(env)?;
+
+        Ok(())
+    }
+
+    fn string_downcase(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:downcase", &[Type::String], fp)?;
+
+        fp.value =
+            Vector::from(Vector::as_string(env, fp.argv[0]).to_lowercase()).with_heap(env)?;
+
+        Ok(())
+    }
+
+    fn string_index(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:index", &[Type::String, Type::Char], fp)?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+
+        fp.value = Self::char_index(&str, str.find(Char::as_char(env, fp.argv[1])));
+
+        Ok(())
+    }
+
+    fn string_less_than(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:less-than", &[Type::String, Type::String], fp)?;
+
+        fp.value = if Vector::as_string(env, fp.argv[0]) < Vector::as_string(env, fp.argv[1]) {
+            Symbol::keyword("t")
+        } else {
+            Tag::nil()
+        };
+
+        Ok(())
+    }
+
+    // a float, or a double with a d exponent
+    fn string_parse_float(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:parse-float", &[Type::String], fp)?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+        let token = str.trim();
+
+        fp.value = match Double::parse(token) {
+            Some(fl) if fl.is_finite() => Double::with_f64(env, fl)?,
+            Some(_) => Err(Exception::err(
+                env,
+                fp.argv[0],
+                Condition::Over,
+                "string:parse-float",
+            ))?,
+            None => match token.parse::<f32>() {
+                Ok(fl) if fl.is_finite() => fl.into(),
+                _ => Err(Exception::err(
+                    env,
+                    fp.argv[0],
+                    Condition::Syntax,
+                    "string:parse-float",
+                ))?,
+            },
+        };
+
+        Ok(())
+    }
+
+    // a fixnum, or a bignum if it doesn't fit
+    fn string_parse_integer(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:parse-integer", &[Type::String], fp)?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+
+        fp.value = match Bignum::parse(str.trim(), 10) {
+            Some(bignum) => bignum.integer(env, "string:parse-integer")?,
+            None => Err(Exception::err(
+                env,
+                fp.argv[0],
+                Condition::Syntax,
+                "string:parse-integer",
+            ))?,
+        };
+
+        Ok(())
+    }
+
+    fn string_search(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:search", &[Type::String, Type::String], fp)?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+
+        fp.value = Self::char_index(&str, str.find(&Vector::as_string(env, fp.argv[1])));
+
+        Ok(())
+    }
+
+    // a list of the substrings between separators
+    fn string_split(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:split", &[Type::String, Type::Char], fp)?;
+
+        let strings = Vector::as_string(env, fp.argv[0])
+            .split(Char::as_char(env, fp.argv[1]))
+            .map(|str| Vector::from(str).with_heap(env))
+            .collect::<exception::Result<Vec<Tag>>>()?;
+
+        fp.value = Cons::list(env, &strings);
+
+        Ok(())
+    }
+
+    fn string_substring(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "string:substring",
+            &[Type::String, Type::Fixnum, Type::Fixnum],
+            fp,
+        )?;
+
+        let str = Vector::as_string(env, fp.argv[0]);
+        let length = i64::try_from(str.chars().count()).unwrap();
+        let start = Fixnum::as_i64(fp.argv[1]);
+        let end = Fixnum::as_i64(fp.argv[2]);
+
+        if !(0..=length).contains(&start) {
+            Err(Exception::err(
+                env,
+                fp.argv[1],
+                Condition::Range,
+                "string:substring",
+            ))?;
+        }
+
+        if !(start..=length).contains(&end) {
+            Err(Exception::err(
+                env,
+                fp.argv[2],
+                Condition::Range,
+                "string:substring",
+            ))?;
+        }
+
+        let substring = str
+            .chars()
+            .skip(usize::try_from(start).unwrap())
+            .take(usize::try_from(end - start).unwrap())
+            .collect::<String>();
+
+        fp.value = Vector::from(substring).with_heap(env)?;
+
+        Ok(())
+    }
+
+    fn string_trim(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:trim", &[Type::String], fp)?;
+
+        fp.value = Vector::from(Vector::as_string(env, fp.argv[0]).trim()).with_heap(env)?;
+
+        Ok(())
+    }
+
+    fn string_upcase(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("string:upcase", &[Type::String], fp)?;
+
+        fp.value =
+            Vector::from(Vector::as_string(env, fp.argv[0]).to_uppercase()).with_heap(env)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn string_test() {}
+}
@@ -14,6 +14,7 @@ use {
             type_::Type,
         },
         features::feature::Feature,
+        namespaces::persist::Persist as _,
         types::{
             cons::Cons,
             fixnum::Fixnum,
@@ -101,6 +102,8 @@ impl CoreFn for Feature {
     fn system_exit(env: &Env, fp: &mut Frame) -> exception::Result<()> {
         env.argv_check("system:exit", &[Type::Fixnum], fp)?;
 
+        let _ = env.heap_sync();
+
         std::process::exit(i32::from(fp.argv[0]));
     }
 
@@ -144,32 +147,32 @@ impl CoreFn for Feature {
                         Cons::cons(
                             env,
                             Symbol::keyword("sysname"),
-                            Vector::from(info.sysname().to_str().unwrap()).with_heap(env),
+                            Vector::from(info.sysname().to_str().unwrap()).with_heap(env)?,
                         ),
                         Cons::cons(
                             env,
                             Symbol::keyword("node"),
-                            Vector::from(info.nodename().to_str().unwrap()).with_heap(env),
+                            Vector::from(info.nodename().to_str().unwrap()).with_heap(env)?,
                         ),
                         Cons::cons(
                             env,
                             Symbol::keyword("release"),
-                            Vector::from(info.release().to_str().unwrap()).with_heap(env),
+                            Vector::from(info.release().to_str().unwrap()).with_heap(env)?,
                         ),
                         Cons::cons(
                             env,
                             Symbol::keyword("version"),
-                            Vector::from(info.version().to_str().unwrap()).with_heap(env),
+                            Vector::from(info.version().to_str().unwrap()).with_heap(env)?,
                         ),
                         Cons::cons(
                             env,
                             Symbol::keyword("machine"),
-                            Vector::from(info.machine().to_str().unwrap()).with_heap(env),
+                            Vector::from(info.machine().to_str().unwrap()).with_heap(env)?,
                         ),
                     ],
                 )];
 
-                Struct::new(env, "uname", uname).with_heap(env)
+                Struct::new(env, "uname", uname)?.with_heap(env)?
             }
         };
 
@@ -198,68 +201,68 @@ impl CoreFn for Feature {
                     &[
                         Cons::cons(
                             env,
-                            Vector::from("uptime").with_heap(env),
+                            Vector::from("uptime").with_heap(env)?,
                             Fixnum::etry_from(env, sysinfo.uptime, "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("loads").with_heap(env),
-                            Vector::from(vec![loads0, loads1, loads2]).with_heap(env),
+                            Vector::from("loads").with_heap(env)?,
+                            Vector::from(vec![loads0, loads1, loads2]).with_heap(env)?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("totalram").with_heap(env),
+                            Vector::from("totalram").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.totalram.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("freeram").with_heap(env),
+                            Vector::from("freeram").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.freeram.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("sharedram").with_heap(env),
+                            Vector::from("sharedram").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.sharedram.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("bufferram").with_heap(env),
+                            Vector::from("bufferram").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.bufferram.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("totalswap").with_heap(env),
+                            Vector::from("totalswap").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.totalswap.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("freeswap").with_heap(env),
+                            Vector::from("freeswap").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.freeswap.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("procs").with_heap(env),
+                            Vector::from("procs").with_heap(env)?,
                             sysinfo.procs.into(),
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("totalhigh").with_heap(env),
+                            Vector::from("totalhigh").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.totalhigh.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("freehigh").with_heap(env),
+                            Vector::from("freehigh").with_heap(env)?,
                             Fixnum::with_u64(env, sysinfo.freehigh.into(), "system:sysinfo")?,
                         ),
                         Cons::cons(
                             env,
-                            Vector::from("mem_unit").with_heap(env),
+                            Vector::from("mem_unit").with_heap(env)?,
                             sysinfo.mem_unit.into(),
                         ),
                     ],
                 )];
 
-                Vector::from(sysinfo).with_heap(env)
+                Vector::from(sysinfo).with_heap(env)?
             }
         };
 
@@ -0,0 +1,431 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+//! vector feature
+//!
+//! bulk operations on typed vectors. element-wise arithmetic and
+//! reductions on fixnum, float and double vectors, logical operations
+//! on bit vectors, and fill, subsequence, concatenation and search on
+//! byte vectors. results are new vectors.
+#![allow(clippy::cast_possible_truncation)]
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            tag::Tag,
+            type_::Type,
+        },
+        features::feature::Feature,
+        types::{
+            bignum::Bignum,
+            cons::Cons,
+            double::Double,
+            fixnum::Fixnum,
+            vector::{Vector, VectorType},
+        },
+        vectors::image::VectorImageType,
+    },
+    std::ops::{Add, Mul, Sub},
+};
+
+pub trait Vectors {
+    fn feature() -> Feature;
+}
+
+impl Vectors for Feature {
+    fn feature() -> Feature {
+        Feature {
+            functions: Some(vec![
+                ("add", 2, Feature::vector_add),
+                ("bit-and", 2, Feature::vector_bit_and),
+                ("bit-not", 1, Feature::vector_bit_not),
+                ("bit-or", 2, Feature::vector_bit_or),
+                ("bit-xor", 2, Feature::vector_bit_xor),
+                ("concat", 1, Feature::vector_concat),
+                ("fill", 4, Feature::vector_fill),
+                ("max", 1, Feature::vector_max),
+                ("min", 1, Feature::vector_min),
+                ("mul", 2, Feature::vector_mul),
+                ("popcount", 1, Feature::vector_popcount),
+                ("search", 2, Feature::vector_search),
+                ("sub", 2, Feature::vector_sub),
+                ("subseq", 3, Feature::vector_subseq),
+                ("sum", 1, Feature::vector_sum),
+            ]),
+            symbols: None,
+            namespace: "feature/vector".into(),
+        }
+    }
+}
+
+impl Feature {
+    // the data of a vector argument of one of the given types
+    fn vector_data(
+        env: &Env,
+        vector: Tag,
+        types: &[VectorType],
+        source: &str,
+    ) -> exception::Result<VectorImageType> {
+        if vector.type_of() != Type::Vector || !types.contains(&Vector::type_of(env, vector)) {
+            Err(Exception::err(env, vector, Condition::Type, source))?;
+        }
+
+        Ok(Vector::to_data(env, vector))
+    }
+
+    // start and end of a subsequence, 0 <= start <= end <= length
+    fn bounds(
+        env: &Env,
+        start: Tag,
+        end: Tag,
+        length: usize,
+        source: &str,
+    ) -> exception::Result<(usize, usize)> {
+        let start_ = Fixnum::as_i64(start);
+        let end_ = Fixnum::as_i64(end);
+
+        if start_ < 0 || start_ > end_ {
+            Err(Exception::err(env, start, Condition::Range, source))?;
+        }
+
+        if usize::try_from(end_).unwrap() > length {
+            Err(Exception::err(env, end, Condition::Range, source))?;
+        }
+
+        Ok((
+            usize::try_from(start_).unwrap(),
+            usize::try_from(end_).unwrap(),
+        ))
+    }
+
+    // element-wise arithmetic, floats are computed as doubles
+    fn elementwise(
+        env: &Env,
+        fp: &Frame,
+        fixnum_op: fn(i64, i64) -> Option<i64>,
+        float_op: fn(f64, f64) -> f64,
+        source: &str,
+    ) -> exception::Result<Tag> {
+        let numeric = [VectorType::Double, VectorType::Fixnum, VectorType::Float];
+        let data = Self::vector_data(env, fp.argv[0], &numeric, source)?;
+        let other = Self::vector_data(env, fp.argv[1], &numeric, source)?;
+
+        if Vector::length(env, fp.argv[0]) != Vector::length(env, fp.argv[1]) {
+            Err(Exception::err(env, fp.argv[1], Condition::Range, source))?;
+        }
+
+        let over = || Exception::err(env, fp.argv[0], Condition::Over, source);
+
+        let vector = match (data, other) {
+            (VectorImageType::Fixnum(vec), VectorImageType::Fixnum(other)) => {
+                let vec = vec
+                    .iter()
+                    .zip(other)
+                    .map(|(fx, other)| match fixnum_op(*fx, other) {
+                        Some(fx) if Fixnum::is_i56(fx) => Ok(fx),
+                        _ => Err(over()),
+                    })
+                    .collect::<exception::Result<Vec<i64>>>()?;
+
+                Vector::from(vec)
+            }
+            (VectorImageType::Float(vec), VectorImageType::Float(other)) => {
+                let vec = vec
+                    .iter()
+                    .zip(other)
+                    .map(|(fl, other)| float_op(f64::from(*fl), f64::from(other)) as f32)
+                    .collect::<Vec<f32>>();
+
+                if vec.iter().any(|fl| fl.is_infinite()) {
+                    Err(over())?;
+                }
+
+                Vector::from(vec)
+            }
+            (VectorImageType::Double(vec), VectorImageType::Double(other)) => {
+                let vec = vec
+                    .iter()
+                    .zip(other)
+                    .map(|(fl, other)| float_op(*fl, other))
+                    .collect::<Vec<f64>>();
+
+                if vec.iter().any(|fl| fl.is_infinite()) {
+                    Err(over())?;
+                }
+
+                Vector::from(vec)
+            }
+            _ => Err(Exception::err(env, fp.argv[1], Condition::Type, source))?,
+        };
+
+        vector.with_heap(env)
+    }
+
+    // the least or greatest element of a non-empty numeric vector
+    fn extremum(env: &Env, fp: &Frame, max: bool, source: &str) -> exception::Result<Tag> {
+        let numeric = [VectorType::Double, VectorType::Fixnum, VectorType::Float];
+        let vector = fp.argv[0];
+
+        let data = Self::vector_data(env, vector, &numeric, source)?;
+
+        if Vector::length(env, vector) == 0 {
+            Err(Exception::err(env, vector, Condition::Range, source))?;
+        }
+
+        Ok(match data {
+            VectorImageType::Fixnum(vec) => Fixnum::with_i64_or_panic(if max {
+                vec.into_iter().max().unwrap()
+            } else {
+                vec.into_iter().min().unwrap()
+            }),
+            VectorImageType::Float(vec) => vec
+                .into_iter()
+                .reduce(if max { f32::max } else { f32::min })
+                .unwrap()
+                .into(),
+            VectorImageType::Double(vec) => Double::with_f64(
+                env,
+                vec.into_iter()
+                    .reduce(if max { f64::max } else { f64::min })
+                    .unwrap(),
+            )?,
+            _ => panic!(),
+        })
+    }
+
+    fn bit_data(env: &Env, vector: Tag, source: &str) -> exception::Result<Vec<u8>> {
+        match Self::vector_data(env, vector, &[VectorType::Bit], source)? {
+            VectorImageType::Bit(vec) => Ok(vec),
+            _ => panic!(),
+        }
+    }
+
+    fn byte_data(env: &Env, vector: Tag, source: &str) -> exception::Result<Vec<u8>> {
+        match Self::vector_data(env, vector, &[VectorType::Byte], source)? {
+            VectorImageType::Byte(vec) => Ok(vec),
+            _ => panic!(),
+        }
+    }
+
+    // a bit vector, bits past the end of the vector are cleared
+    fn bit_vector(env: &Env, mut vec: Vec<u8>, length: usize) -> exception::Result<Tag> {
+        if !length.is_multiple_of(8) {
+            if let Some(last) = vec.last_mut() {
+                *last &= 0xff << (8 - length % 8);
+            }
+        }
+
+        Vector::from((vec, length)).with_heap(env)
+    }
+
+    fn bitwise(
+        env: &Env,
+        fp: &Frame,
+        op: fn(u8, u8) -> u8,
+        source: &str,
+    ) -> exception::Result<Tag> {
+        let vec = Self::bit_data(env, fp.argv[0], source)?;
+        let other = Self::bit_data(env, fp.argv[1], source)?;
+        let length = Vector::length(env, fp.argv[0]);
+
+        if length != Vector::length(env, fp.argv[1]) {
+            Err(Exception::err(env, fp.argv[1], Condition::Range, source))?;
+        }
+
+        let vec = vec
+            .iter()
+            .zip(other)
+            .map(|(byte, other)| op(*byte, other))
+            .collect::<Vec<u8>>();
+
+        Self::bit_vector(env, vec, length)
+    }
+
+    fn vector_add(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::elementwise(env, fp, i64::checked_add, f64::add, "vector:add")?;
+
+        Ok(())
+    }
+
+    fn vector_bit_and(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::bitwise(env, fp, |byte, other| byte & other, "vector:bit-and")?;
+
+        Ok(())
+    }
+
+    fn vector_bit_not(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let vec = Self::bit_data(env, fp.argv[0], "vector:bit-not")?;
+
+        fp.value = Self::bit_vector(
+            env,
+            vec.iter().map(|byte| !byte).collect(),
+            Vector::length(env, fp.argv[0]),
+        )?;
+
+        Ok(())
+    }
+
+    fn vector_bit_or(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::bitwise(env, fp, |byte, other| byte | other, "vector:bit-or")?;
+
+        Ok(())
+    }
+
+    fn vector_bit_xor(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::bitwise(env, fp, |byte, other| byte ^ other, "vector:bit-xor")?;
+
+        Ok(())
+    }
+
+    fn vector_concat(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("vector:concat", &[Type::List], fp)?;
+
+        let mut vec = Vec::<u8>::new();
+
+        for vector in Cons::list_iter(env, fp.argv[0]) {
+            vec.extend(Self::byte_data(env, vector, "vector:concat")?);
+        }
+
+        fp.value = Vector::from(vec).with_heap(env)?;
+
+        Ok(())
+    }
+
+    // a copy of a byte vector with start to end set to a byte
+    fn vector_fill(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "vector:fill",
+            &[Type::Vector, Type::Byte, Type::Fixnum, Type::Fixnum],
+            fp,
+        )?;
+
+        let mut vec = Self::byte_data(env, fp.argv[0], "vector:fill")?;
+        let (start, end) = Self::bounds(env, fp.argv[2], fp.argv[3], vec.len(), "vector:fill")?;
+
+        vec[start..end].fill(u8::try_from(Fixnum::as_i64(fp.argv[1])).unwrap());
+
+        fp.value = Vector::from(vec).with_heap(env)?;
+
+        Ok(())
+    }
+
+    fn vector_max(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::extremum(env, fp, true, "vector:max")?;
+
+        Ok(())
+    }
+
+    fn vector_min(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::extremum(env, fp, false, "vector:min")?;
+
+        Ok(())
+    }
+
+    fn vector_mul(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::elementwise(env, fp, i64::checked_mul, f64::mul, "vector:mul")?;
+
+        Ok(())
+    }
+
+    fn vector_popcount(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let vec = Self::bit_data(env, fp.argv[0], "vector:popcount")?;
+
+        fp.value = Fixnum::with_usize_or_panic(
+            vec.iter()
+                .map(|byte| byte.count_ones() as usize)
+                .sum::<usize>(),
+        );
+
+        Ok(())
+    }
+
+    // the index of the first occurrence of a byte vector, or nil
+    fn vector_search(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let vec = Self::byte_data(env, fp.argv[0], "vector:search")?;
+        let pattern = Self::byte_data(env, fp.argv[1], "vector:search")?;
+
+        let index = if pattern.is_empty() {
+            Some(0)
+        } else {
+            vec.windows(pattern.len())
+                .position(|window| window == pattern.as_slice())
+        };
+
+        fp.value = index.map_or(Tag::nil(), Fixnum::with_usize_or_panic);
+
+        Ok(())
+    }
+
+    fn vector_sub(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::elementwise(env, fp, i64::checked_sub, f64::sub, "vector:sub")?;
+
+        Ok(())
+    }
+
+    fn vector_subseq(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check(
+            "vector:subseq",
+            &[Type::Vector, Type::Fixnum, Type::Fixnum],
+            fp,
+        )?;
+
+        let vec = Self::byte_data(env, fp.argv[0], "vector:subseq")?;
+        let (start, end) = Self::bounds(env, fp.argv[1], fp.argv[2], vec.len(), "vector:subseq")?;
+
+        fp.value = Vector::from(&vec[start..end]).with_heap(env)?;
+
+        Ok(())
+    }
+
+    // fixnum sums are integers, float sums are accumulated as doubles
+    fn vector_sum(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let numeric = [VectorType::Double, VectorType::Fixnum, VectorType::Float];
+
+        fp.value = match Self::vector_data(env, fp.argv[0], &numeric, "vector:sum")? {
+            VectorImageType::Fixnum(vec) => {
+                Bignum::with_i128(env, vec.into_iter().map(i128::from).sum())?
+            }
+            VectorImageType::Float(vec) => {
+                let sum = vec.into_iter().map(f64::from).sum::<f64>() as f32;
+
+                if sum.is_infinite() {
+                    Err(Exception::err(
+                        env,
+                        fp.argv[0],
+                        Condition::Over,
+                        "vector:sum",
+                    ))?;
+                }
+
+                sum.into()
+            }
+            VectorImageType::Double(vec) => {
+                let sum = vec.into_iter().sum::<f64>();
+
+                if sum.is_infinite() {
+                    Err(Exception::err(
+                        env,
+                        fp.argv[0],
+                        Condition::Over,
+                        "vector:sum",
+                    ))?;
+                }
+
+                Double::with_f64(env, sum)?
+            }
+            _ => panic!(),
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn vector_test() {}
+}
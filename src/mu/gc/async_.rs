@@ -20,12 +20,17 @@ impl Gc for Async {
     }
 
     fn mark(context: &mut GcContext, env: &Env, function: Tag) {
-        let mark = context.mark_image(function).unwrap();
+        match function {
+            Tag::Direct(_) => (),
+            Tag::Indirect(_) => {
+                let mark = context.mark_image(function).unwrap();
 
-        if !mark {
-            let form = Self::ref_form(context, function);
+                if !mark {
+                    let form = Self::ref_form(context, function);
 
-            context.mark(env, form);
+                    context.mark(env, form);
+                }
+            }
         }
     }
 
@@ -50,7 +55,5 @@ impl Gc for Async {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn as_tag() {
-        assert_eq!(true, true)
-    }
+    fn as_tag() {}
 }
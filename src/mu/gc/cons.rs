@@ -54,23 +54,22 @@ impl Gc for Cons {
         }
     }
 
+    // follow the cdr chain iteratively, long lists would overflow the stack
     fn mark(context: &mut GcContext, env: &Env, cons: Tag) {
-        match cons {
-            Tag::Direct(_) => {
-                let car = Self::ref_car(context, cons);
-                let cdr = Self::ref_cdr(context, cons);
+        let mut cons = cons;
 
-                context.mark(env, car);
-                context.mark(env, cdr);
-            }
-            Tag::Indirect(_) => {
-                let mark = context.mark_image(cons).unwrap();
-                if !mark {
-                    context.mark(env, Self::ref_car(context, cons));
-                    context.mark(env, Self::ref_cdr(context, cons));
+        while cons.type_of() == Type::Cons {
+            if let Tag::Indirect(_) = cons {
+                if context.mark_image(cons).unwrap() {
+                    return;
                 }
             }
+
+            context.mark(env, Self::ref_car(context, cons));
+            cons = Self::ref_cdr(context, cons);
         }
+
+        context.mark(env, cons);
     }
 }
 
@@ -80,8 +79,6 @@ mod tests {
 
     #[test]
     fn cons_test() {
-        match Cons::new(Tag::nil(), Tag::nil()) {
-            _ => assert!(true),
-        }
+        let _ = Cons::new(Tag::nil(), Tag::nil());
     }
 }
@@ -29,12 +29,17 @@ impl Gc for Function {
     }
 
     fn mark(context: &mut GcContext, env: &Env, function: Tag) {
-        let mark = context.mark_image(function).unwrap();
+        match function {
+            Tag::Direct(_) => (),
+            Tag::Indirect(_) => {
+                let mark = context.mark_image(function).unwrap();
 
-        if !mark {
-            let form = Self::ref_form(context, function);
+                if !mark {
+                    let form = Self::ref_form(context, function);
 
-            context.mark(env, form);
+                    context.mark(env, form);
+                }
+            }
         }
     }
 
@@ -59,7 +64,5 @@ impl Gc for Function {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn as_tag() {
-        assert!(true)
-    }
+    fn as_tag() {}
 }
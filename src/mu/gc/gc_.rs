@@ -4,44 +4,91 @@
 //! env gc
 #![allow(dead_code)]
 use crate::{
-    core::{env::Env, exception, frame::Frame, tag::Tag, type_::Type},
+    core::{
+        config::GcMode, direct::DirectImage, env::Env, exception, frame::Frame, tag::Tag,
+        type_::Type,
+    },
     gc::{
-        async_::Gc as _, cons::Gc as _, function::Gc as _, struct_::Gc as _, symbol::Gc as _,
-        vector::Gc as _,
+        async_::Gc as _, cons::Gc as _, function::Gc as _, hash::Gc as _, struct_::Gc as _,
+        symbol::Gc as _, vector::Gc as _,
     },
     namespaces::{
         heap::{Gc as _, Heap},
         namespace::Namespace,
     },
     types::{
-        async_::Async, cons::Cons, function::Function, struct_::Struct, symbol::Symbol,
-        vector::Vector,
+        async_::Async, cons::Cons, function::Function, future::Future, hash::Hash, stream::Stream,
+        struct_::Struct, symbol::Symbol, vector::Vector,
     },
 };
 
-use futures_lite::future::block_on;
+use {futures_lite::future::block_on, std::collections::HashSet};
 
 pub struct GcContext<'a> {
     pub heap_ref: &'a mut futures_locks::RwLockWriteGuard<Heap>,
+    pub worklist: Vec<Tag>,    // reached but not yet traced
+    pub marking: bool,         // worklist is being drained
+    pub minor: bool,           // old generation is taken as marked
+    pub streams: HashSet<u64>, // stream ids reached by the mark
+    pub futures: HashSet<u64>, // future ids reached by the mark
 }
 
 pub trait Gc {
+    fn caches(&mut self, _: &Env);
+    fn collect(_: &Env, minor: bool) -> exception::Result<bool>;
+    fn compact(_: &Env, _: &mut [&mut Tag]);
+    fn forward(&mut self, _: &Env);
+    fn futures(&mut self, _: &Env);
+    fn futures_unreached(&self, _: &Env) -> Vec<u64>;
     fn gc(_: &Env) -> exception::Result<bool>;
+    fn gc_safepoint(_: &Env, _: Tag, _: &[Tag]) -> exception::Result<()>;
+    fn heap_exhausted(_: &Env, _: Tag, _: &[Tag]) -> exception::Result<bool>;
     fn lexicals(&mut self, _: &Env);
     fn mark(&mut self, _: &Env, tag: Tag);
+    fn mark_tag(&mut self, _: &Env, tag: Tag);
     fn mark_image(&mut self, _: Tag) -> Option<bool>;
+    fn mark_sweep(_: &Env, minor: bool) -> (Vec<u64>, Vec<u64>);
     fn namespaces(&mut self, _: &Env);
+    fn pop_roots(_: &Env, _: usize);
+    fn push_roots(_: &Env, _: &[Tag]) -> usize;
+    fn remembered(&mut self, _: &Env);
+    fn roots(&mut self, _: &Env);
+    fn streams(&self, _: &Env) -> Vec<u64>;
+    fn vector_cache(&mut self, _: &Env);
 }
 
 impl Gc for GcContext<'_> {
+    // marks reached while tracing go on the worklist, deep structures
+    // would overflow the stack
     fn mark(&mut self, env: &Env, tag: Tag) {
+        self.worklist.push(tag);
+
+        if self.marking {
+            return;
+        }
+
+        self.marking = true;
+        while let Some(tag) = self.worklist.pop() {
+            self.mark_tag(env, tag);
+        }
+        self.marking = false;
+    }
+
+    fn mark_tag(&mut self, env: &Env, tag: Tag) {
         match tag.type_of() {
             Type::Async => Async::mark(self, env, tag),
+            Type::Bignum | Type::Double => {
+                self.mark_image(tag);
+            }
             Type::Cons => Cons::mark(self, env, tag),
             Type::Function => Function::mark(self, env, tag),
+            Type::Hash => Hash::mark(self, env, tag),
             Type::Struct => Struct::mark(self, env, tag),
-            Type::Symbol => Symbol::mark(self, env, tag),
+            Type::Symbol | Type::Keyword => Symbol::mark(self, env, tag),
             Type::Vector => Vector::mark(self, env, tag),
+            Type::Stream => {
+                self.streams.insert(Stream::stream_id(tag).unwrap());
+            }
             _ => (),
         }
     }
@@ -49,19 +96,19 @@ impl Gc for GcContext<'_> {
     fn mark_image(&mut self, tag: Tag) -> Option<bool> {
         match tag {
             Tag::Direct(_) => None,
+            // the system heap is never collected
+            Tag::Indirect(indirect) if indirect.heap_id() != Heap::PRIVATE_ID => Some(true),
             Tag::Indirect(indirect) => {
-                let marked = self
-                    .heap_ref
-                    .get_image_mark(usize::try_from(indirect.image_id()).unwrap());
-
-                match marked {
-                    None => (),
-                    Some(mark) => {
-                        if !mark {
-                            self.heap_ref
-                                .set_image_mark(usize::try_from(indirect.image_id()).unwrap());
-                        }
-                    }
+                let index = usize::try_from(indirect.image_id()).unwrap();
+
+                if self.minor && self.heap_ref.is_old(index) {
+                    return Some(true);
+                }
+
+                let marked = self.heap_ref.get_image_mark(index);
+
+                if marked == Some(false) {
+                    self.heap_ref.set_image_mark(index);
                 }
 
                 marked
@@ -72,25 +119,23 @@ impl Gc for GcContext<'_> {
     fn lexicals(&mut self, env: &Env) {
         let lexical_ref = block_on(env.lexical.read());
 
-        for frame_vec in (*lexical_ref).values() {
-            let frame_vec_ref = &frame_vec;
+        for frame in &lexical_ref.frames {
+            self.mark(env, frame.func);
 
-            for frame in *frame_vec_ref {
-                self.mark(env, frame.func);
-
-                for arg in &frame.argv {
-                    self.mark(env, *arg);
-                }
-
-                self.mark(env, frame.value);
+            for arg in &frame.argv {
+                self.mark(env, *arg);
             }
+
+            self.mark(env, frame.value);
         }
     }
 
     fn namespaces(&mut self, env: &Env) {
         let ns_ref = block_on(env.ns_map.read());
 
-        for (_, ns_map) in ns_ref.values() {
+        for (ns, ns_map) in ns_ref.values() {
+            self.mark(env, *ns);
+
             match ns_map {
                 Namespace::Static(static_) => {
                     if let Some(hash) = &static_ {
@@ -110,17 +155,357 @@ impl Gc for GcContext<'_> {
         }
     }
 
+    // image cache entries are never released
+    fn caches(&mut self, env: &Env) {
+        let cache_ref = block_on(env.cache.read());
+
+        for image in cache_ref.image_hash.values() {
+            match image {
+                DirectImage::Async(image) => {
+                    self.mark(env, image.arity);
+                    self.mark(env, image.form);
+                }
+                DirectImage::Cons(image) => {
+                    self.mark(env, image.car);
+                    self.mark(env, image.cdr);
+                }
+                DirectImage::Function(image) => {
+                    self.mark(env, image.arity);
+                    self.mark(env, image.form);
+                }
+                DirectImage::Symbol(image) => {
+                    self.mark(env, image.namespace);
+                    self.mark(env, image.name);
+                    self.mark(env, image.value);
+                }
+            }
+        }
+    }
+
+    // old images written since the last collection and young objects
+    // stored in namespaces stand in for the old generation
+    fn remembered(&mut self, env: &Env) {
+        let remembered = self
+            .heap_ref
+            .remembered
+            .iter()
+            .copied()
+            .collect::<Vec<usize>>();
+        let roots = self.heap_ref.remembered_roots.clone();
+
+        for index in remembered {
+            for offset in self.heap_ref.image_tags(index) {
+                let tag = Tag::from_slice(self.heap_ref.image_slice(offset).unwrap());
+
+                self.mark(env, tag);
+            }
+        }
+
+        for root in roots {
+            self.mark(env, (&root.to_le_bytes()).into());
+        }
+    }
+
+    fn roots(&mut self, env: &Env) {
+        let roots_ref = block_on(env.gc_roots.read());
+
+        for root in roots_ref.iter() {
+            self.mark(env, *root);
+        }
+    }
+
+    // tasks hold their tags until their handles are released
+    fn futures(&mut self, env: &Env) {
+        let futures_ref = block_on(env.futures.read());
+
+        for task in futures_ref.values() {
+            for tag in task.future.tags() {
+                self.mark(env, tag);
+            }
+        }
+    }
+
+    // futures whose handles the mark did not reach, see streams
+    fn futures_unreached(&self, env: &Env) -> Vec<u64> {
+        if self.minor {
+            return Vec::new();
+        }
+
+        block_on(env.futures.read())
+            .keys()
+            .filter(|future_id| !self.futures.contains(future_id))
+            .copied()
+            .collect()
+    }
+
+    // streams opened in this environment the mark did not reach, a
+    // minor collection does not see into the old generation and
+    // reaches nothing it can vouch for
+    fn streams(&self, env: &Env) -> Vec<u64> {
+        if self.minor {
+            return Vec::new();
+        }
+
+        block_on(env.streams.read())
+            .iter()
+            .filter(|stream_id| !self.streams.contains(stream_id))
+            .copied()
+            .collect()
+    }
+
+    // the vector cache holds its vectors weakly
+    fn vector_cache(&mut self, env: &Env) {
+        let cache_ref = block_on(env.vector_cache.read());
+
+        for vectors in cache_ref.values() {
+            block_on(vectors.write()).retain(|vector| match vector {
+                Tag::Direct(_) => true,
+                Tag::Indirect(indirect) if indirect.heap_id() != Heap::PRIVATE_ID => true,
+                Tag::Indirect(indirect) => {
+                    let index = usize::try_from(indirect.image_id()).unwrap();
+
+                    (self.minor && self.heap_ref.is_old(index))
+                        || self.heap_ref.get_image_mark(index) == Some(true)
+                }
+            });
+        }
+    }
+
+    // protect tags held on the rust side, returns the mark to pop back to
+    fn push_roots(env: &Env, roots: &[Tag]) -> usize {
+        let mut roots_ref = block_on(env.gc_roots.write());
+        let mark = roots_ref.len();
+
+        roots_ref.extend_from_slice(roots);
+
+        mark
+    }
+
+    fn pop_roots(env: &Env, mark: usize) {
+        block_on(env.gc_roots.write()).truncate(mark);
+    }
+
+    // called on function application, collects in auto mode once enough
+    // has been allocated since the last collection. a heap left short of
+    // room for the next threshold's worth of allocation is collected
+    // whole before it is grown
+    fn gc_safepoint(env: &Env, func: Tag, argv: &[Tag]) -> exception::Result<()> {
+        if env.config.gc_mode == GcMode::None
+            || block_on(env.heap.read()).gc_allocated < env.config.gc_threshold
+        {
+            return Ok(());
+        }
+
+        let mark = Self::push_roots(env, &[func]);
+
+        Self::push_roots(env, argv);
+
+        let minor = block_on(env.heap.read()).minor_collections + 1 < Heap::MAJOR_INTERVAL;
+        let mut gc = Self::collect(env, minor);
+
+        if gc.is_ok() && block_on(env.heap.read()).free_space < env.config.gc_threshold {
+            if minor {
+                gc = Self::collect(env, false);
+            }
+
+            let _ = block_on(env.heap.write()).reserve(env.config.gc_threshold);
+        }
+
+        Self::pop_roots(env, mark);
+        gc?;
+
+        Ok(())
+    }
+
+    // a heap crossing into the reserve is collected whole in auto mode,
+    // it is exhausted if what survives is still over the ceiling
+    fn heap_exhausted(env: &Env, func: Tag, argv: &[Tag]) -> exception::Result<bool> {
+        if env.config.gc_mode == GcMode::None {
+            return Ok(true);
+        }
+
+        let mark = Self::push_roots(env, &[func]);
+
+        Self::push_roots(env, argv);
+
+        let gc = Self::collect(env, false);
+
+        Self::pop_roots(env, mark);
+        gc?;
+
+        Ok(block_on(env.heap.read()).is_over_ceiling())
+    }
+
     fn gc(env: &Env) -> exception::Result<bool> {
+        Self::collect(env, false)
+    }
+
+    // a major collection traces from the namespaces, a minor one only
+    // the young generation from the remembered set. survivors of
+    // either are promoted. unreachable streams and futures are released
+    // once the heap is unlocked.
+    fn collect(env: &Env, minor: bool) -> exception::Result<bool> {
+        let (streams, futures) = Self::mark_sweep(env, minor);
+
+        for stream_id in streams {
+            Stream::release(env, stream_id);
+        }
+
+        for future_id in futures {
+            Future::release(env, future_id);
+        }
+
+        Ok(true)
+    }
+
+    fn mark_sweep(env: &Env, minor: bool) -> (Vec<u64>, Vec<u64>) {
         let mut gc = GcContext {
             heap_ref: &mut block_on(env.heap.write()),
+            worklist: Vec::new(),
+            marking: false,
+            minor,
+            streams: HashSet::new(),
+            futures: HashSet::new(),
         };
+        let from = if minor { gc.heap_ref.old_barrier } else { 0 };
 
-        gc.heap_ref.clear_marks();
-        gc.namespaces(env);
+        gc.heap_ref.clear_marks(from);
+
+        if minor {
+            gc.remembered(env);
+        } else {
+            gc.namespaces(env);
+        }
+
+        gc.caches(env);
+        gc.roots(env);
+        gc.futures(env);
         gc.lexicals(env);
-        gc.heap_ref.sweep();
+        gc.vector_cache(env);
+        gc.heap_ref.sweep(from);
+        gc.heap_ref.fragmented = gc.heap_ref.free_map.iter().any(|free| !free.is_empty());
+        gc.heap_ref.promote();
 
-        Ok(true)
+        gc.heap_ref.minor_collections = if minor {
+            gc.heap_ref.minor_collections + 1
+        } else {
+            0
+        };
+
+        (gc.streams(env), gc.futures_unreached(env))
+    }
+
+    // rewrite the tags held outside the heap, see Heap::relocate
+    fn forward(&mut self, env: &Env) {
+        let mut ns_ref = block_on(env.ns_map.write());
+
+        for (ns, ns_map) in ns_ref.values_mut() {
+            *ns = self.heap_ref.forward(*ns);
+
+            match ns_map {
+                Namespace::Static(static_) => {
+                    if let Some(hash) = static_ {
+                        for symbol in hash.values_mut() {
+                            *symbol = self.heap_ref.forward(*symbol);
+                        }
+                    }
+                }
+                Namespace::Dynamic(ref hash) => {
+                    for symbol in block_on(hash.write()).values_mut() {
+                        *symbol = self.heap_ref.forward(*symbol);
+                    }
+                }
+            }
+        }
+
+        // runtime namespaces are allocated first and never move
+        assert!(self.heap_ref.forward(env.keyword_ns).eq_(&env.keyword_ns));
+        assert!(self.heap_ref.forward(env.mu_ns).eq_(&env.mu_ns));
+
+        for image in block_on(env.cache.write()).image_hash.values_mut() {
+            match image {
+                DirectImage::Async(image) => image.form = self.heap_ref.forward(image.form),
+                DirectImage::Cons(image) => {
+                    image.car = self.heap_ref.forward(image.car);
+                    image.cdr = self.heap_ref.forward(image.cdr);
+                }
+                DirectImage::Function(image) => image.form = self.heap_ref.forward(image.form),
+                DirectImage::Symbol(image) => {
+                    image.namespace = self.heap_ref.forward(image.namespace);
+                    image.name = self.heap_ref.forward(image.name);
+                    image.value = self.heap_ref.forward(image.value);
+                }
+            }
+        }
+
+        for vectors in block_on(env.vector_cache.read()).values() {
+            for vector in block_on(vectors.write()).iter_mut() {
+                *vector = self.heap_ref.forward(*vector);
+            }
+        }
+
+        for task in block_on(env.futures.write()).values_mut() {
+            task.handle = self.heap_ref.forward(task.handle);
+
+            for tag in task.future.tags_mut() {
+                *tag = self.heap_ref.forward(*tag);
+            }
+        }
+
+        #[cfg(feature = "instrument")]
+        for (func, _) in block_on(env.prof.write()).iter_mut() {
+            *func = self.heap_ref.forward(*func);
+        }
+    }
+
+    // compaction moves images out from under any tag held on the rust
+    // side, so it waits for a collection to leave the heap fragmented
+    // and runs only between top-level evaluations, when the only live
+    // tags are the caller's roots.
+    fn compact(env: &Env, roots: &mut [&mut Tag]) {
+        if !block_on(env.gc_roots.read()).is_empty() || !block_on(env.lexical.read()).is_empty() {
+            return;
+        }
+
+        let mut gc = GcContext {
+            heap_ref: &mut block_on(env.heap.write()),
+            worklist: Vec::new(),
+            marking: false,
+            minor: false,
+            streams: HashSet::new(),
+            futures: HashSet::new(),
+        };
+
+        if !gc.heap_ref.fragmented {
+            return;
+        }
+
+        gc.heap_ref.clear_marks(0);
+        gc.namespaces(env);
+        gc.caches(env);
+        gc.futures(env);
+
+        for root in roots.iter() {
+            gc.mark(env, **root);
+        }
+
+        gc.vector_cache(env);
+
+        // handles are compared by identity, a task whose handle is not
+        // moving with the live images goes
+        for future_id in gc.futures_unreached(env) {
+            Future::release(env, future_id);
+        }
+
+        gc.heap_ref.relocate();
+        gc.forward(env);
+
+        for root in roots.iter_mut() {
+            **root = gc.heap_ref.forward(**root);
+        }
+
+        block_on(env.lexical.write()).clear();
+        gc.heap_ref.compact();
     }
 }
 
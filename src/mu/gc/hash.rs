@@ -0,0 +1,52 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// hash table type
+use crate::{
+    core::{env::Env, tag::Tag, type_::Type},
+    gc::gc_::{Gc as _, GcContext},
+    types::hash::Hash,
+};
+
+pub trait Gc {
+    fn gc_ref_image(_: &mut GcContext, tag: Tag) -> Self;
+    fn mark(_: &mut GcContext, env: &Env, hash: Tag);
+}
+
+impl Gc for Hash {
+    fn gc_ref_image(context: &mut GcContext, tag: Tag) -> Self {
+        assert_eq!(tag.type_of(), Type::Hash);
+
+        match tag {
+            Tag::Indirect(image) => {
+                let heap_ref = &context.heap_ref;
+                let slice = usize::try_from(image.image_id()).unwrap();
+
+                Hash {
+                    test: Tag::from_slice(heap_ref.image_slice(slice).unwrap()),
+                    count: Tag::from_slice(heap_ref.image_slice(slice + 1).unwrap()),
+                    stamp: Tag::from_slice(heap_ref.image_slice(slice + 2).unwrap()),
+                    buckets: Tag::from_slice(heap_ref.image_slice(slice + 3).unwrap()),
+                }
+            }
+            Tag::Direct(_) => panic!(),
+        }
+    }
+
+    fn mark(context: &mut GcContext, env: &Env, hash: Tag) {
+        let mark = context.mark_image(hash).unwrap();
+
+        if !mark {
+            let image = Self::gc_ref_image(context, hash);
+
+            context.mark(env, image.test);
+            context.mark(env, image.buckets);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn hash_test() {}
+}
@@ -6,6 +6,7 @@ pub mod async_;
 pub mod cons;
 pub mod function;
 pub mod gc_;
+pub mod hash;
 pub mod namespace;
 pub mod struct_;
 pub mod symbol;
@@ -41,7 +41,5 @@ impl Gc for Namespace {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn namespace_test() {
-        assert!(true)
-    }
+    fn namespace_test() {}
 }
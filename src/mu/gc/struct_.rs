@@ -4,8 +4,11 @@
 // struct type
 use crate::{
     core::{env::Env, tag::Tag, type_::Type},
-    gc::gc_::{Gc as _, GcContext},
-    types::struct_::Struct,
+    gc::{
+        gc_::{Gc as _, GcContext},
+        vector::Gc as _,
+    },
+    types::{fixnum::Fixnum, struct_::Struct, symbol::Symbol, vector::Vector},
 };
 
 pub trait Gc {
@@ -35,9 +38,23 @@ impl Gc for Struct {
         let mark = context.mark_image(struct_).unwrap();
 
         if !mark {
-            let vector = Self::gc_ref_image(context, struct_).vector;
+            let image = Self::gc_ref_image(context, struct_);
 
-            context.mark(env, vector);
+            // a future handle keeps its task
+            if image.stype.eq_(&Symbol::keyword("future"))
+                && matches!(image.vector, Tag::Indirect(_))
+            {
+                if let Some(id) = Vector::gc_image_ref(context, image.vector, 0) {
+                    if id.type_of() == Type::Fixnum {
+                        if let Ok(id) = u64::try_from(Fixnum::as_i64(id)) {
+                            context.futures.insert(id);
+                        }
+                    }
+                }
+            }
+
+            context.mark(env, image.stype);
+            context.mark(env, image.vector);
         }
     }
 }
@@ -45,7 +62,5 @@ impl Gc for Struct {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn struct_test() {
-        assert!(true);
-    }
+    fn struct_test() {}
 }
@@ -22,7 +22,7 @@ pub trait Gc {
 
 impl Gc for Symbol {
     fn gc_ref_image(context: &mut GcContext, tag: Tag) -> SymbolImage {
-        assert_eq!(tag.type_of(), Type::Symbol);
+        assert!(matches!(tag.type_of(), Type::Symbol | Type::Keyword));
 
         match tag {
             Tag::Indirect(main) => {
@@ -46,7 +46,7 @@ impl Gc for Symbol {
                     DirectExt::Length(dir.ext() as usize),
                     DirectType::String,
                 ),
-                Tag::Indirect(_) => panic!(),
+                Tag::Indirect(_) => Self::gc_ref_image(context, symbol).name,
             },
             Type::Symbol => Self::gc_ref_image(context, symbol).name,
             _ => panic!(),
@@ -68,9 +68,11 @@ impl Gc for Symbol {
                 let mark = context.mark_image(symbol).unwrap();
 
                 if !mark {
+                    let namespace = Self::gc_ref_image(context, symbol).namespace;
                     let name = Self::ref_name(context, symbol);
                     let value = Self::ref_value(context, symbol);
 
+                    context.mark(env, namespace);
                     context.mark(env, name);
                     context.mark(env, value);
                 }
@@ -82,7 +84,5 @@ impl Gc for Symbol {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn symbol_test() {
-        assert!(true);
-    }
+    fn symbol_test() {}
 }
@@ -16,7 +16,7 @@ pub trait Gc {
     fn gc_image_ref(_: &mut GcContext, _: Tag, _: usize) -> Option<Tag>;
     fn gc_ref_image(_: &mut GcContext, _: Tag) -> VectorImage;
     fn gc_ref(_: &mut GcContext, _: &Env, _: Tag, _: usize) -> Option<Tag>;
-    fn ref_type_of(_: &Env, _: Tag) -> Type;
+    fn ref_type_of(_: &mut GcContext, _: Tag) -> Type;
     fn ref_length(_: &mut GcContext, _: Tag) -> usize;
     fn mark(_: &mut GcContext, _: &Env, _: Tag);
 }
@@ -25,7 +25,9 @@ impl Gc for Vector {
     fn gc_image_ref(context: &mut GcContext, vector: Tag, index: usize) -> Option<Tag> {
         let image = Vector::gc_ref_image(context, vector);
 
-        if index >= usize::try_from(Fixnum::as_i64(image.length)).unwrap() {
+        let length = Vector::image_length(&image);
+
+        if index >= length {
             None?;
         }
 
@@ -49,10 +51,7 @@ impl Gc for Vector {
                 Some(slice[0].into())
             }
             VectorType::Char => {
-                let slice = context.heap_ref.image_data_slice(slice, index, 1)?;
-                let ch: char = slice[0].into();
-
-                Some(ch.into())
+                Vector::char_image_ref(context.heap_ref, slice, length, index).map(Into::into)
             }
             VectorType::T => Some(Tag::from_slice(context.heap_ref.image_data_slice(
                 slice,
@@ -71,6 +70,8 @@ impl Gc for Vector {
 
                 Some(f32::from_le_bytes(slice[0..4].try_into().unwrap()).into())
             }
+            // doubles are not tags, and the collector only walks t vectors
+            VectorType::Double => None,
         }
     }
 
@@ -95,25 +96,30 @@ impl Gc for Vector {
     fn gc_ref(context: &mut GcContext, env: &Env, vector: Tag, index: usize) -> Option<Tag> {
         match vector.type_of() {
             Type::Vector => match vector {
-                Tag::Direct(_direct) => {
-                    let ch: char = vector.data(env).to_le_bytes()[index].into();
-
-                    Some(ch.into())
-                }
+                Tag::Direct(direct) => Vector::utf8_nth(
+                    &vector.data(env).to_le_bytes()[..direct.ext() as usize],
+                    index,
+                )
+                .map(Into::into),
                 Tag::Indirect(_) => <Vector as Gc>::gc_image_ref(context, vector, index),
             },
             _ => panic!(),
         }
     }
 
-    fn ref_type_of(env: &Env, vector: Tag) -> Type {
+    // the heap is locked for the collection, read the type from the image
+    fn ref_type_of(context: &mut GcContext, vector: Tag) -> Type {
         match vector {
             Tag::Direct(direct) => match direct.dtype() {
                 DirectType::String => Type::Char,
                 DirectType::ByteVec => Type::Byte,
                 _ => panic!(),
             },
-            Tag::Indirect(_) => Vector::type_of(env, vector).map_type(),
+            Tag::Indirect(_) => {
+                let image = Self::gc_ref_image(context, vector);
+
+                Vector::to_type(image.type_).unwrap().map_type()
+            }
         }
     }
 
@@ -123,7 +129,7 @@ impl Gc for Vector {
             Tag::Indirect(_) => {
                 let image = Self::gc_ref_image(context, vector);
 
-                usize::try_from(Fixnum::as_i64(image.length)).unwrap()
+                Vector::image_length(&image)
             }
         }
     }
@@ -134,7 +140,7 @@ impl Gc for Vector {
             Tag::Indirect(_) => {
                 let marked = context.mark_image(vector).unwrap();
 
-                if !marked && Self::ref_type_of(env, vector) == Type::T {
+                if !marked && Self::ref_type_of(context, vector) == Type::T {
                     for index in 0..Self::ref_length(context, vector) {
                         let value = Self::gc_ref(context, env, vector, index).unwrap();
 
@@ -149,7 +155,5 @@ impl Gc for Vector {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn vector_test() {
-        assert!(true);
-    }
+    fn vector_test() {}
 }
@@ -42,7 +42,7 @@
 //!    - lambdas with lexical variables
 //!    - specialized (byte, fixnum, single float, character) vectors
 //!    - immediate strings (seven character limit)
-//!    - immediate keywords (seven character limit)
+//!    - immediate keywords (seven characters or less), heap keywords
 //!    - immediate single float 32 bit IEEE float
 //!    - structs
 //!
@@ -83,6 +83,7 @@ pub type Condition = core::exception::Condition;
 pub type Config = core::config::Config;
 /// Exception representation
 pub type Exception = core::exception::Exception;
+pub type SystemHeap = namespaces::system::SystemHeap;
 /// API function Result
 pub type Result<T> = core::exception::Result<T>;
 
@@ -99,11 +100,13 @@ pub mod mu {
                 exception::{self, Condition, Exception},
                 tag::Tag,
             },
+            gc::gc_::{Gc as _, GcContext},
+            namespaces::{system::SystemHeap, verify::Verify as _},
             reader::read::Reader,
             streams::{builder::StreamBuilder, writer::StreamWriter},
             types::stream::Stream,
         },
-        std::fs,
+        std::{fs, sync::Arc},
     };
 
     /// the Mu namespace
@@ -214,6 +217,54 @@ pub mod mu {
             Env::new(config)
         }
 
+        /// Create an Env from a Config, reporting heap file problems.
+        ///
+        /// returns an Env struct Result.
+        ///
+        /// # Errors
+        ///
+        /// - the heap file can't be read
+        /// - the heap file is not a heap file, or was saved with
+        ///   a different geometry or by an incompatible runtime
+        ///
+        /// a missing or empty heap file opens a fresh heap.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// let env = Mu::try_env(&Mu::config(Some(config))).unwrap();
+        /// ```
+        pub fn try_env(config: &Config) -> std::io::Result<Env> {
+            Env::open(config)
+        }
+
+        /// Freeze an Env's heap into a shared read-only system heap.
+        ///
+        /// the Env is collected first. Envs created on the system
+        /// heap start with its namespaces and allocate on their own
+        /// private heaps.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// Mu::load(&env, "core.sys").unwrap();
+        /// let system = Mu::system_heap(&env);
+        /// ```
+        pub fn system_heap(env: &Env) -> Arc<SystemHeap> {
+            Arc::new(SystemHeap::new(env))
+        }
+
+        /// Create an Env from a Config on a shared system heap.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// let env: Env = Mu::env_with_system(&Mu::config(None), &system);
+        /// ```
+        pub fn env_with_system(config: &Config, system: &Arc<SystemHeap>) -> Env {
+            Env::with_system(config, system)
+        }
+
         /// Compile a tagged mu form to a tagged form Result.
         ///
         /// returns a tagged mu form Result.
@@ -251,6 +302,31 @@ pub mod mu {
             tag.eq_(&tag1)
         }
 
+        /// Pin heap objects held by the caller across evaluations.
+        ///
+        /// returns a mark to unpin them with. Pinned objects are
+        /// protected from collection, and the heap is not compacted
+        /// while anything is pinned.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// let eof = Mu::eval_str(env, "(mu:make-symbol \"eof\")").unwrap();
+        /// let pins = Mu::pin(env, &[eof]);
+        ///
+        /// Mu::unpin(env, pins);
+        /// ```
+        pub fn pin(env: &Env, tags: &[Tag]) -> usize {
+            GcContext::push_roots(env, tags)
+        }
+
+        /// Unpin the heap objects pinned since a mark.
+        ///
+        /// pins are released in the reverse order they were made.
+        pub fn unpin(env: &Env, mark: usize) {
+            GcContext::pop_roots(env, mark);
+        }
+
         /// Compile and evaluate a mu form.
         ///
         /// returns a tagged mu form Result.
@@ -260,6 +336,11 @@ pub mod mu {
         /// constant forms simply returns the form at a slight mu
         /// function call overhead.
         ///
+        /// A top-level evaluation may compact the heap once it returns,
+        /// the result is relocated but other heap objects held by the
+        /// caller across calls may move unless they are pinned, see
+        /// `Mu::pin`.
+        ///
         /// # Errors
         ///
         /// - compile exception
@@ -277,7 +358,18 @@ pub mod mu {
         /// let two: Tag = Mu::eval(Mu::read_str(env, "(mu:add 1 1)").unwrap()).unwrap();
         /// ```
         pub fn eval(env: &Env, expr: Tag) -> exception::Result<Tag> {
-            Apply::eval(env, Self::compile(env, expr)?)
+            let form = Self::compile(env, expr)?;
+            let roots = GcContext::push_roots(env, &[form]);
+            let mut value = Apply::eval(env, form);
+
+            GcContext::pop_roots(env, roots);
+
+            match &mut value {
+                Ok(tag) => GcContext::compact(env, &mut [tag]),
+                Err(e) => GcContext::compact(env, &mut [&mut e.object, &mut e.source]),
+            }
+
+            value
         }
 
         /// Read a mu form from a str, compile and evaluate it.
@@ -469,6 +561,36 @@ pub mod mu {
             )
         }
 
+        /// Check the heap for consistency.
+        ///
+        /// returns a description of each problem found, empty
+        /// if the heap is consistent.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// for error in Mu::heap_verify(env) {
+        ///     eprintln!("heap: {error}");
+        /// }
+        /// ```
+        pub fn heap_verify(env: &Env) -> Vec<String> {
+            env.heap_verify()
+        }
+
+        /// Write the heap objects reachable from a root to a file.
+        ///
+        /// the graph is written as DOT, or JSON if the file name
+        /// ends in .json. returns the number of objects written.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// Mu::heap_graph(env, form, "form.dot").unwrap();
+        /// ```
+        pub fn heap_graph(env: &Env, root: Tag, file_path: &str) -> std::io::Result<usize> {
+            env.heap_graph(root, file_path)
+        }
+
         /// Load a file by filename.
         ///
         /// returns a speecialized Result of type bool.
@@ -489,16 +611,26 @@ pub mod mu {
                 let load_form = format!("(mu:open :file :input \"{file_path}\" :t)");
                 let istream = Self::eval(env, Self::read_str(env, &load_form)?)?;
                 let eof_value = Self::eval(env, Self::read_str(env, "(mu:make-symbol \"eof\")")?)?;
+                let roots = GcContext::push_roots(env, &[istream, eof_value]);
 
-                loop {
-                    let form = Self::read(env, istream, false, eof_value)?;
+                let loaded = loop {
+                    let form = match Self::read(env, istream, false, eof_value) {
+                        Ok(form) => form,
+                        Err(e) => break Err(e),
+                    };
 
                     if Self::eq(form, eof_value) {
                         break Ok(true);
                     }
 
-                    Self::eval(env, Self::compile(env, form)?)?;
-                }
+                    if let Err(e) = Self::compile(env, form).and_then(|form| Self::eval(env, form))
+                    {
+                        break Err(e);
+                    }
+                };
+
+                GcContext::pop_roots(env, roots);
+                loaded
             } else {
                 Err(Exception::err(
                     env,
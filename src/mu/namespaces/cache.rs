@@ -147,7 +147,5 @@ impl Cache {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn cache_test() {
-        assert!(true);
-    }
+    fn cache_test() {}
 }
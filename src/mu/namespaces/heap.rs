@@ -10,26 +10,34 @@ use {
             config::Config,
             direct::DirectTag,
             env::Env,
+            indirect::IndirectTag,
             tag::Tag,
             type_::Type,
         },
+        namespaces::persist::Persist as _,
         types::{
+            bignum::Bignum,
+            double::Double,
             cons::Cons,
+            fixnum::Fixnum,
             function::Function,
+            hash::Hash,
             struct_::Struct,
             symbol::Symbol,
-            vector::Vector
+            vector::{Vector, VectorType},
         },
     },
     futures_lite::future::block_on,
     memmap,
-    modular_bitfield::specifiers::{B11, B4},
+    modular_bitfield::specifiers::{B26, B5},
     page_size,
     std::{
+        collections::HashSet,
         fmt,
-        fs::{remove_file, OpenOptions},
-        io::{Seek, SeekFrom, Write},
+        fs::{remove_file, rename, File, OpenOptions},
+        io::{Read, Seek, SeekFrom, Write},
         mem::size_of,
+        ops::Deref,
     },
 };
 
@@ -37,12 +45,10 @@ use {
 #[bitfield]
 #[derive(Specifier, Debug, Copy, Clone)]
 pub struct HeapImageInfo {
-    pub reloc: u32, // relocation
-    #[skip]
-    __: B11, // expansion
-    pub mark: bool, // reference counting
-    pub len: u16,   // in bytes
-    pub image_type: B4, // tag type
+    pub reloc: u32,     // relocation
+    pub mark: bool,     // reference counting
+    pub len: B26,       // in bytes
+    pub image_type: B5, // tag type
 }
 
 impl Default for HeapImageInfo {
@@ -72,17 +78,28 @@ pub struct HeapTypeInfo {
 
 #[derive(Debug)]
 pub struct Heap {
+    pub file: Option<File>,         // heap backing file, or a heap file to load
+    pub path: Option<String>,       // persistent heap file path
     pub mmap: Box<memmap::MmapMut>, // heap base
     pub npages: usize,              // total size of heap in pages
+    pub max_pages: usize,           // heap growth ceiling in pages
     pub size: usize,                // total size of heap in bytes
     pub page_size: usize,           // system page size
     pub alloc_map: [HeapTypeInfo; Type::NTYPES],
     // map of allocated objects
     pub alloc_barrier: usize, // unallocated space barrier
-    pub free_map: [Vec<usize>; Type::NTYPES],
-    // map of free objects
+    pub free_map: [Vec<usize>; Heap::NCLASSES],
+    // free blocks by size class
     pub free_space: usize,   // number of aggregate free bytes
     pub gc_allocated: usize, // bytes allocated since last gc
+    pub exhausted: bool,     // heap has crossed into the reserve
+    pub fragmented: bool,    // free images below the barrier
+    // generations
+    pub old_barrier: usize,         // images below are in the old generation
+    pub remembered: HashSet<usize>, // old images written since the last collection
+    pub remembered_roots: Vec<u64>, // tags stored in namespaces since the last collection
+    pub minor_collections: usize,   // minor collections since the last major
+    pub relocations: usize,         // compactions since the heap was created
 }
 
 pub struct HeapRequest<'a> {
@@ -92,65 +109,343 @@ pub struct HeapRequest<'a> {
     pub vdata: Option<&'a [u8]>,
 }
 
+// the heap an image lives on, the private heap is locked for reading,
+// the system heap is read-only and shared
+pub enum HeapRef<'a> {
+    Private(futures_locks::RwLockReadGuard<Heap>),
+    System(&'a Heap),
+}
+
+impl Deref for HeapRef<'_> {
+    type Target = Heap;
+
+    fn deref(&self) -> &Heap {
+        match self {
+            HeapRef::Private(heap) => heap,
+            HeapRef::System(heap) => heap,
+        }
+    }
+}
+
 impl Heap {
+    // indirect tag heap ids, an environment allocates on its private
+    // heap and may share a read-only system heap
+    pub const PRIVATE_ID: u8 = 1;
+    pub const SYSTEM_ID: u8 = 2;
+
+    // pages mapped past the ceiling so an exhausted heap can raise a condition
+    const RESERVE_PAGES: usize = 32;
+
+    // largest image, header included, that fits in HeapImageInfo.len
+    pub const IMAGE_MAX: usize = (1 << 26) - 1;
+
+    // smallest image, a header and one word
+    pub const IMAGE_MIN: usize = 2 * size_of::<u64>();
+
+    // number of free list size classes
+    pub const NCLASSES: usize = 9;
+
+    // image type of a free block, no heap image has the synthetic List type
+    pub const FREE_IMAGE: u8 = Type::List as u8;
+
+    // automatic collections are minor, every MAJOR_INTERVAL'th is major
+    pub const MAJOR_INTERVAL: usize = 8;
+
     pub fn new(config: &Config) -> Self {
-        let path = &format!("/var/tmp/mu.{}.heap", std::process::id());
+        Self::open(config).expect("heap: unable to map heap")
+    }
 
+    // a heap file is read into a private mapping and written back
+    // whole by write_state, so the file only ever holds a synced heap
+    pub fn open(config: &Config) -> std::io::Result<Self> {
         let page_size = page_size::get();
-        let npages = config.npages;
-
-        let mut f = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(path)
-            .expect("unable to open heap mmap file");
-
-        remove_file(path).unwrap();
-
-        f.seek(SeekFrom::Start((npages * page_size) as u64))
-            .unwrap();
-        f.write_all(&[0]).unwrap();
-        f.rewind().unwrap();
-
-        let data = unsafe {
-            memmap::MmapOptions::new()
-                .map_mut(&f)
-                .expect("Could not access data from memory mapped file")
+        let mut npages = config.npages;
+
+        let file = if let Some(path) = &config.heap_file {
+            let file = Self::heap_file(path, page_size)?;
+
+            if let Some((_, size)) = file {
+                npages = size / page_size;
+            }
+
+            file.map(|(file, _)| file)
+        } else {
+            let path = &format!("/var/tmp/mu.{}.heap", std::process::id());
+
+            let mut f = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(path)?;
+
+            remove_file(path)?;
+
+            f.seek(SeekFrom::Start((npages * page_size) as u64))?;
+            f.write_all(&[0])?;
+            f.rewind()?;
+
+            Some(f)
         };
 
-        Heap {
+        let size = npages * page_size;
+        let data = if let (None, Some(f)) = (&config.heap_file, &file) {
+            unsafe { memmap::MmapOptions::new().len(size).map_mut(f)? }
+        } else {
+            let mut data = memmap::MmapOptions::new().len(size).map_anon()?;
+
+            if let Some(mut f) = file.as_ref() {
+                f.read_exact(&mut data[..])?;
+            }
+
+            data
+        };
+
+        Ok(Heap {
+            file,
+            path: config.heap_file.clone(),
             mmap: Box::new(data),
             npages,
+            max_pages: config.max_pages.max(npages),
             page_size,
-            size: npages * page_size,
+            size,
             alloc_map: [HeapTypeInfo {
                 size: 0,
                 total: 0,
                 free: 0,
             }; Type::NTYPES],
             alloc_barrier: 0,
-            free_map: [const { Vec::new() }; Type::NTYPES],
-            free_space: npages * page_size,
+            free_map: [const { Vec::new() }; Self::NCLASSES],
+            free_space: size,
             gc_allocated: 0,
+            exhausted: false,
+            fragmented: false,
+            old_barrier: 0,
+            remembered: HashSet::new(),
+            remembered_roots: Vec::new(),
+            minor_collections: 0,
+            relocations: 0,
+        })
+    }
+
+    // a missing or empty heap file, or one without a trailer, is a fresh
+    // heap. a saved heap keeps the size it had grown to
+    fn heap_file(path: &str, page_size: usize) -> std::io::Result<Option<(File, usize)>> {
+        let Ok(mut file) = File::open(path) else {
+            return Ok(None);
+        };
+
+        let len = file.metadata()?.len();
+
+        if len < 8 {
+            return Ok(None);
+        }
+
+        let mut trailer = [0u8; 8];
+
+        file.seek(SeekFrom::Start(len - 8))?;
+        file.read_exact(&mut trailer)?;
+        file.rewind()?;
+
+        match u64::from_le_bytes(trailer) {
+            0 => Ok(None),
+            size if size % page_size as u64 == 0 && size + 8 < len => {
+                Ok(Some((file, usize::try_from(size).unwrap())))
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "heap-file: not a heap file",
+            )),
         }
     }
 
+    // heap image and environment state, see namespaces/persist.rs
     pub fn image(env: &Env) -> (Vec<u8>, Vec<u8>) {
-        let heap_ref = block_on(env.heap.write());
+        let state = env.save_state();
+        let heap_ref = block_on(env.heap.read());
         let image = heap_ref.heap_slice();
 
-        (image.to_vec(), vec![])
+        (image.to_vec(), state)
+    }
+
+    // the environment state is written past the end of the mapped heap,
+    // followed by the size of the heap
+    pub fn read_state(&mut self) -> Option<Vec<u8>> {
+        self.path.as_ref()?;
+
+        let size = self.size as u64;
+        let file = self.file.as_mut()?;
+        let len = file.metadata().ok()?.len();
+
+        if len <= size + 8 {
+            return None;
+        }
+
+        let mut state = vec![0; usize::try_from(len - size - 8).ok()?];
+
+        file.seek(SeekFrom::Start(size)).ok()?;
+        file.read_exact(&mut state).ok()?;
+
+        Some(state)
+    }
+
+    // a heap file is replaced by renaming a complete new one over it
+    pub fn write_state(&mut self, state: &[u8]) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return self.mmap.flush();
+        };
+
+        let size = self.size as u64;
+        let temp = format!("{path}.tmp");
+        let mut f = File::create(&temp)?;
+
+        f.write_all(self.heap_slice())?;
+        f.write_all(state)?;
+        f.write_all(&size.to_le_bytes())?;
+        f.sync_all()?;
+
+        rename(&temp, path)
+    }
+
+    // grow the heap in page multiples up to the ceiling, past the
+    // ceiling map the reserve
+    fn grow(&mut self, image_size: usize) -> bool {
+        let npages = (self.alloc_barrier + image_size).div_ceil(self.page_size);
+
+        let npages = if npages <= self.max_pages {
+            (self.npages * 2).clamp(npages, self.max_pages)
+        } else if npages <= self.max_pages + Self::RESERVE_PAGES {
+            self.max_pages + Self::RESERVE_PAGES
+        } else {
+            return false;
+        };
+
+        self.remap(npages).is_ok()
+    }
+
+    // grow ahead of allocation until free bytes are free, never
+    // past the ceiling
+    pub fn reserve(&mut self, free: usize) -> std::io::Result<()> {
+        if self.free_space >= free || self.npages >= self.max_pages {
+            return Ok(());
+        }
+
+        let npages = (self.size + free - self.free_space).div_ceil(self.page_size);
+
+        self.remap((self.npages * 2).max(npages).min(self.max_pages))
+    }
+
+    // bytes in use are over the ceiling
+    pub fn is_over_ceiling(&self) -> bool {
+        self.size - self.free_space > self.max_pages * self.page_size
+    }
+
+    fn remap(&mut self, npages: usize) -> std::io::Result<()> {
+        let size = npages * self.page_size;
+
+        let data = if let (None, Some(file)) = (&self.path, &self.file) {
+            self.mmap.flush()?;
+            file.set_len(size as u64)?;
+
+            unsafe { memmap::MmapOptions::new().len(size).map_mut(file)? }
+        } else {
+            let mut data = memmap::MmapOptions::new().len(size).map_anon()?;
+
+            data[..self.size].copy_from_slice(self.heap_slice());
+            data
+        };
+
+        *self.mmap = data;
+        self.free_space += size - self.size;
+        self.size = size;
+        self.npages = npages;
+
+        Ok(())
+    }
+
+    // an exhausted heap is reported once per trip into the reserve
+    pub fn is_exhausted(env: &Env) -> bool {
+        if !block_on(env.heap.read()).exhausted {
+            return false;
+        }
+
+        let mut heap_ref = block_on(env.heap.write());
+
+        std::mem::replace(&mut heap_ref.exhausted, false)
     }
 
     pub fn iter(&self) -> HeapIter<'_> {
+        self.iter_from(0)
+    }
+
+    // images from a heap offset on an image boundary
+    pub fn iter_from(&self, off: usize) -> HeapIter<'_> {
         HeapIter {
             heap: self,
-            index: 1,
+            index: off / size_of::<u64>() + 1,
+        }
+    }
+
+    pub fn image_heap(env: &Env, tag: IndirectTag) -> HeapRef<'_> {
+        match &env.system {
+            Some(system) if tag.heap_id() == Self::SYSTEM_ID => HeapRef::System(&system.heap),
+            _ => HeapRef::Private(block_on(env.heap.read())),
         }
     }
 
+    pub fn is_old(&self, index: usize) -> bool {
+        (index - 1) * size_of::<u64>() < self.old_barrier
+    }
+
+    // the generational write barrier, an old image written or allocated
+    // may now refer to young ones
+    fn remember(&mut self, index: usize) {
+        if self.is_old(index) {
+            self.remembered.insert(index);
+        }
+    }
+
+    // a young object stored outside the heap, a minor collection does
+    // not trace the namespaces
+    pub fn remember_root(&mut self, tag: Tag) {
+        if let Tag::Indirect(indirect) = tag {
+            if indirect.heap_id() == Self::PRIVATE_ID
+                && !self.is_old(usize::try_from(indirect.image_id()).unwrap())
+            {
+                self.remembered_roots.push(tag.as_u64());
+            }
+        }
+    }
+
+    // everything allocated so far is old
+    pub fn promote(&mut self) {
+        self.old_barrier = self.alloc_barrier;
+        self.remembered.clear();
+        self.remembered_roots.clear();
+    }
+
+    // offsets of the words of an image that hold tags
+    pub fn image_tags(&self, index: usize) -> std::ops::Range<usize> {
+        let info = self.image_info(index).unwrap();
+        let ntags = match Type::try_from(info.image_type()) {
+            Ok(Type::Async | Type::Cons | Type::Function | Type::Struct) => 2,
+            Ok(Type::Symbol) => 3,
+            Ok(Type::Hash) => 4,
+            Ok(Type::Vector) => {
+                let image = Tag::from_slice(self.image_slice(index).unwrap());
+                let length = Tag::from_slice(self.image_slice(index + 1).unwrap());
+
+                match Vector::to_type(image) {
+                    Some(VectorType::T) => usize::try_from(Fixnum::as_i64(length)).unwrap() + 2,
+                    _ => 0,
+                }
+            }
+            _ => 0,
+        };
+
+        index..index + ntags
+    }
+
     // allocate
     pub fn alloc(&mut self, req: &HeapRequest) -> Option<usize> {
         let image_len = req.image.len();
@@ -160,83 +455,127 @@ impl Heap {
         };
         let image_size = ((image_len + 1) * size_of::<u64>()) + vdata_size;
 
-        self.free_space -= image_size + size_of::<HeapImageInfo>();
-
-        let index = if let Some(index) =
-            self.alloc_free(req.type_id, (image_len * size_of::<u64>()) + vdata_size)
-        {
-            let data = &mut self.mmap;
-            let mut off = index * size_of::<u64>();
+        if image_size > Self::IMAGE_MAX {
+            None?;
+        }
 
-            for image_slice in req.image {
-                data[off..(off + size_of::<u64>())].copy_from_slice(image_slice);
-                off += size_of::<u64>();
+        let (mut off, len) = if let Some((off, len)) = self.alloc_free(image_size) {
+            self.remember(off / size_of::<u64>() + 1);
+            (off, len)
+        } else {
+            if self.alloc_barrier + image_size > self.size && !self.grow(image_size) {
+                None?;
             }
 
-            match req.vdata {
-                Some(vdata) if vdata_size != 0 => {
-                    data[off..(off + vdata.len())].copy_from_slice(vdata);
-                }
-                _ => (),
-            }
+            let off = self.alloc_barrier;
+            let ceiling = self.max_pages * self.page_size;
 
-            index
-        } else {
-            if self.alloc_barrier + image_size > self.size {
-                None?;
+            self.alloc_barrier += image_size;
+
+            // crossed into the reserve
+            if off <= ceiling && self.alloc_barrier > ceiling {
+                self.exhausted = true;
             }
 
-            self.gc_allocated += image_size;
+            (off, image_size)
+        };
 
-            let hinfo = HeapImageInfo::new()
-                .with_reloc(0)
-                .with_len(u16::try_from(image_size).unwrap())
-                .with_mark(false)
-                .with_image_type(req.type_id)
-                .into_bytes();
+        let hinfo = HeapImageInfo::new()
+            .with_reloc(0)
+            .with_len(u32::try_from(len).unwrap())
+            .with_mark(false)
+            .with_image_type(req.type_id)
+            .into_bytes();
 
-            let data = &mut self.mmap;
+        let data = &mut self.mmap;
 
-            data[self.alloc_barrier..(self.alloc_barrier + 8)].copy_from_slice(&hinfo);
-            self.alloc_barrier += size_of::<HeapImageInfo>();
+        data[off..(off + size_of::<HeapImageInfo>())].copy_from_slice(&hinfo);
+        off += size_of::<HeapImageInfo>();
 
-            let index = self.alloc_barrier / size_of::<u64>();
+        let index = off / size_of::<u64>();
 
-            for image_slice in req.image {
-                data[self.alloc_barrier..(self.alloc_barrier + size_of::<u64>())]
-                    .copy_from_slice(image_slice);
-                self.alloc_barrier += size_of::<u64>();
-            }
+        for image_slice in req.image {
+            data[off..(off + size_of::<u64>())].copy_from_slice(image_slice);
+            off += size_of::<u64>();
+        }
 
-            if vdata_size != 0 {
-                data[self.alloc_barrier..(self.alloc_barrier + req.vdata.unwrap().len())]
-                    .copy_from_slice(req.vdata.unwrap());
-                self.alloc_barrier += vdata_size;
-            }
+        if let Some(vdata) = req.vdata {
+            data[off..(off + vdata.len())].copy_from_slice(vdata);
+        }
 
-            self.alloc_map[req.type_id as usize].size +=
-                (image_len * size_of::<u64>()) + vdata_size;
-            self.alloc_map[req.type_id as usize].total += 1;
+        self.alloc_map[req.type_id as usize].size += len - size_of::<HeapImageInfo>();
+        self.alloc_map[req.type_id as usize].total += 1;
 
-            index
-        };
+        self.gc_allocated += len;
+        self.free_space -= len;
 
         Some(index)
     }
 
-    // try first fit
-    fn alloc_free(&mut self, type_id: u8, size: usize) -> Option<usize> {
-        for (index, off) in self.free_map[type_id as usize].iter().enumerate() {
-            if self.image_info(*off).unwrap().len() >= u16::try_from(size).unwrap() {
-                self.alloc_map[type_id as usize].total += 1;
+    // segregated free lists, size classes are powers of two from
+    // IMAGE_MIN, the last class holds everything larger
+    pub fn size_class(len: usize) -> usize {
+        (len.ilog2() as usize - Self::IMAGE_MIN.ilog2() as usize).min(Self::NCLASSES - 1)
+    }
+
+    // a block from the first class that fits, split if the rest is
+    // large enough to be an image, returns heap offset and length
+    fn alloc_free(&mut self, size: usize) -> Option<(usize, usize)> {
+        for class in Self::size_class(size)..Self::NCLASSES {
+            let fit = self.free_map[class]
+                .iter()
+                .position(|index| self.image_length(*index).unwrap() >= size);
+
+            if let Some(nth) = fit {
+                let index = self.free_map[class].swap_remove(nth);
+                let len = self.image_length(index).unwrap();
+                let off = (index - 1) * size_of::<u64>();
+
+                if len - size >= Self::IMAGE_MIN {
+                    self.free_block(off + size, len - size);
+
+                    return Some((off, size));
+                }
 
-                return Some(self.free_map[type_id as usize].remove(index));
+                return Some((off, len));
             }
         }
 
         None
     }
 
+    fn free_block(&mut self, off: usize, len: usize) {
+        let hinfo = HeapImageInfo::new()
+            .with_reloc(0)
+            .with_len(u32::try_from(len).unwrap())
+            .with_mark(false)
+            .with_image_type(Self::FREE_IMAGE);
+
+        self.write_info(hinfo, off / size_of::<u64>() + 1);
+        self.free_map[Self::size_class(len)].push(off / size_of::<u64>() + 1);
+    }
+
+    // size class, number of blocks, and bytes on each free list
+    pub fn heap_free_classes(env: &Env) -> Vec<(usize, usize, usize)> {
+        let heap_ref = block_on(env.heap.read());
+
+        heap_ref
+            .free_map
+            .iter()
+            .enumerate()
+            .map(|(class, free_map)| {
+                (
+                    Self::IMAGE_MIN << class,
+                    free_map.len(),
+                    free_map
+                        .iter()
+                        .map(|index| heap_ref.image_length(*index).unwrap())
+                        .sum(),
+                )
+            })
+            .collect()
+    }
+
     // rewrite info header
     pub fn write_info(&mut self, info: HeapImageInfo, index: usize) {
         let off = index * size_of::<u64>();
@@ -277,7 +616,14 @@ impl Heap {
 
     // read and write image data
     pub fn write_image(&mut self, image: &[[u8; 8]], index: usize) {
-        let mut off = index * size_of::<u64>();
+        self.write_image_slice(image, index, 0);
+    }
+
+    // write words of an image starting offset words in
+    pub fn write_image_slice(&mut self, image: &[[u8; 8]], index: usize, offset: usize) {
+        let mut off = (index + offset) * size_of::<u64>();
+
+        self.remember(index);
 
         for image_slice in image {
             self.mmap[off..(off + 8)].copy_from_slice(image_slice);
@@ -315,10 +661,13 @@ impl Heap {
 
     pub fn image_size(env: &Env, tag: Tag) -> usize {
         match tag.type_of() {
+            Type::Bignum => Bignum::image_size(env, tag),
+            Type::Double => Double::image_size(env, tag),
             Type::Cons => Cons::image_size(env, tag),
             Type::Function => Function::image_size(env, tag),
+            Type::Hash => Hash::image_size(env, tag),
             Type::Struct => Struct::image_size(env, tag),
-            Type::Symbol => Symbol::image_size(env, tag),
+            Type::Symbol | Type::Keyword => Symbol::image_size(env, tag),
             Type::Vector => Vector::image_size(env, tag),
             _ => size_of::<DirectTag>(),
         }
@@ -344,43 +693,185 @@ impl Heap {
 }
 
 pub trait Gc {
-    fn clear_marks(&mut self);
-    fn sweep(&mut self);
+    fn clear_marks(&mut self, _: usize);
+    fn compact(&mut self);
+    fn forward(&self, _: Tag) -> Tag;
+    fn relocate(&mut self);
+    fn sweep(&mut self, _: usize);
     fn set_image_mark(&mut self, _: usize);
     fn get_image_mark(&self, _: usize) -> Option<bool>;
 }
 
 impl Gc for Heap {
-    fn clear_marks(&mut self) {
-        let mut index: usize = 1;
+    // clear marks from a heap offset, a minor collection starts at the
+    // old generation barrier
+    fn clear_marks(&mut self, from: usize) {
+        let images = self
+            .iter_from(from)
+            .collect::<Vec<(HeapImageInfo, usize)>>();
 
-        while let Some(mut info) = self.image_info(index) {
+        for (mut info, index) in images {
             info.set_mark(false);
             self.write_info(info, index);
-            index += (info.len() as usize) / size_of::<u64>();
         }
 
         for type_map in &mut self.alloc_map {
             type_map.free = 0;
         }
+    }
+
+    // dead images and the free blocks around them coalesce, a free
+    // run at the end of the heap gives its space back to the barrier
+    fn sweep(&mut self, from: usize) {
+        let images = self
+            .iter_from(from)
+            .collect::<Vec<(HeapImageInfo, usize)>>();
+        let mut run: Option<(usize, usize)> = None;
 
         for free_map in &mut self.free_map {
-            free_map.clear();
+            free_map.retain(|index| (index - 1) * size_of::<u64>() < from);
+        }
+
+        for (info, index) in images {
+            if info.mark() {
+                if let Some((off, len)) = run.take() {
+                    self.free_block(off, len);
+                }
+
+                continue;
+            }
+
+            if info.image_type() != Self::FREE_IMAGE {
+                self.alloc_map[info.image_type() as usize].free += 1;
+            }
+
+            let off = (index - 1) * size_of::<u64>();
+            let len = info.len() as usize;
+
+            run = match run {
+                Some((run_off, run_len)) if run_len + len <= Self::IMAGE_MAX => {
+                    Some((run_off, run_len + len))
+                }
+                Some((run_off, run_len)) => {
+                    self.free_block(run_off, run_len);
+                    Some((off, len))
+                }
+                None => Some((off, len)),
+            };
+        }
+
+        if let Some((off, _)) = run {
+            self.alloc_barrier = off;
+        }
+
+        self.free_space = self.size - self.alloc_barrier
+            + self
+                .free_map
+                .iter()
+                .flatten()
+                .map(|index| self.image_length(*index).unwrap())
+                .sum::<usize>();
+        self.gc_allocated = 0;
+    }
+
+    // compacting collector, after marking:
+    //
+    //     relocate    record each live image's new index in reloc
+    //     forward     rewrite tags through the reloc of their image
+    //     compact     slide live images down onto their new index
+    //
+    // live images keep their order, so an image never moves up and a
+    // forwarded tag always fits wherever the original did.
+    fn relocate(&mut self) {
+        let live = self
+            .iter()
+            .filter(|(info, _)| info.mark())
+            .collect::<Vec<(HeapImageInfo, usize)>>();
+        let mut barrier = 0;
+
+        for (info, index) in &live {
+            self.write_info(
+                info.with_reloc(u32::try_from(barrier / size_of::<u64>() + 1).unwrap()),
+                *index,
+            );
+            barrier += info.len() as usize;
+        }
+
+        // forward the tags held in live images before anything moves
+        for (_, index) in live {
+            for offset in self.image_tags(index) {
+                let tag = self.forward(Tag::from_slice(self.image_slice(offset).unwrap()));
+
+                self.write_image(&[tag.as_slice()], offset);
+            }
         }
     }
 
-    fn sweep(&mut self) {
-        let free_list = self
+    fn forward(&self, tag: Tag) -> Tag {
+        match tag {
+            Tag::Direct(_) => {
+                if tag.type_of() == Type::Cons {
+                    let (car, cdr) = DirectTag::cons_destruct(tag);
+
+                    DirectTag::cons(self.forward(car), self.forward(cdr)).unwrap()
+                } else {
+                    tag
+                }
+            }
+            Tag::Indirect(indirect) if indirect.heap_id() != Self::PRIVATE_ID => tag,
+            Tag::Indirect(indirect) => {
+                match self.image_info(usize::try_from(indirect.image_id()).unwrap()) {
+                    Some(info) if info.mark() => {
+                        Tag::Indirect(indirect.with_image_id(u64::from(info.reloc())))
+                    }
+                    _ => tag,
+                }
+            }
+        }
+    }
+
+    fn compact(&mut self) {
+        let live = self
             .iter()
-            .filter(|(info, _)| !info.mark())
+            .filter(|(info, _)| info.mark())
             .collect::<Vec<(HeapImageInfo, usize)>>();
 
-        for (info, index) in free_list {
-            let type_id = info.image_type() as usize;
+        for type_info in &mut self.alloc_map {
+            *type_info = HeapTypeInfo {
+                size: 0,
+                total: 0,
+                free: 0,
+            };
+        }
 
-            self.alloc_map[type_id].free += 1;
-            self.free_map[type_id].push(index);
+        let mut barrier = 0;
+
+        for (info, index) in live {
+            let len = info.len() as usize;
+            let from = (index - 1) * size_of::<u64>();
+
+            self.mmap.copy_within(from..from + len, barrier);
+            self.mmap[barrier..barrier + size_of::<HeapImageInfo>()]
+                .copy_from_slice(&info.with_reloc(0).with_mark(false).into_bytes());
+
+            let type_info = &mut self.alloc_map[info.image_type() as usize];
+
+            type_info.size += len - size_of::<HeapImageInfo>();
+            type_info.total += 1;
+
+            barrier += len;
         }
+
+        for free_map in &mut self.free_map {
+            free_map.clear();
+        }
+
+        self.alloc_barrier = barrier;
+        self.free_space = self.size - barrier;
+        self.gc_allocated = 0;
+        self.fragmented = false;
+        self.relocations += 1;
+        self.promote();
     }
 
     fn set_image_mark(&mut self, index: usize) {
@@ -422,7 +913,5 @@ impl Iterator for HeapIter<'_> {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn heap_test() {
-        assert!(true);
-    }
+    fn heap_test() {}
 }
@@ -5,6 +5,9 @@
 pub mod cache;
 pub mod heap;
 pub mod namespace;
+pub mod persist;
+pub mod system;
+pub mod verify;
 
 #[rustfmt::skip]
 #[allow (unused_imports)]
@@ -11,10 +11,16 @@ use {
             env::Env,
             exception::{self, Condition, Exception},
             frame::Frame,
+            indirect::ExtTag,
             tag::Tag,
             type_::Type,
         },
-        types::{struct_::Struct, symbol::Symbol, vector::Vector},
+        namespaces::heap::Heap,
+        types::{
+            struct_::Struct,
+            symbol::{Symbol, SymbolImage, UNBOUND},
+            vector::Vector,
+        },
     },
     futures_lite::future::block_on,
     futures_locks::RwLock,
@@ -39,14 +45,16 @@ impl Namespace {
 
             return Err(Exception::err(
                 env,
-                Vector::from(name).with_heap(env),
+                Vector::from(name).with_heap(env)?,
                 Condition::Type,
                 "mu:make-namespace",
             ));
         }
 
-        let ns = Struct::new(env, "ns", vec![Vector::from(name).with_heap(env)]).with_heap(env);
+        let ns =
+            Struct::new(env, "ns", vec![Vector::from(name).with_heap(env)?])?.with_heap(env)?;
 
+        block_on(env.heap.write()).remember_root(ns);
         ns_ref.insert(
             name.to_string(),
             (
@@ -66,18 +74,19 @@ impl Namespace {
 
             return Err(Exception::err(
                 env,
-                Vector::from(name).with_heap(env),
+                Vector::from(name).with_heap(env)?,
                 Condition::Type,
                 "mu:make-namespace",
             ));
         }
 
-        let ns = Struct::new(env, "ns", vec![Vector::from(name).with_heap(env)]).with_heap(env);
+        let ns =
+            Struct::new(env, "ns", vec![Vector::from(name).with_heap(env)?])?.with_heap(env)?;
         let mut ns_map = HashMap::new();
 
         if let Some(sym_defs) = defs.0 {
             for def in sym_defs {
-                let symbol = Symbol::new(env, ns, &def.0, def.1).with_heap(env);
+                let symbol = Symbol::new(env, ns, &def.0, def.1)?.with_heap(env)?;
 
                 ns_map.insert(def.0, symbol).unwrap();
             }
@@ -87,19 +96,22 @@ impl Namespace {
             for def in fn_defs {
                 let name = def.0;
 
+                // features may share function names
                 let (ndef, _) = CORE
                     .fn_defs
                     .iter()
                     .enumerate()
-                    .find(|(_, static_)| name == static_.0)
+                    .find(|(_, static_)| {
+                        name == static_.0 && std::ptr::fn_addr_eq(def.2, static_.2)
+                    })
                     .unwrap();
                 let symbol = Symbol::new(
                     env,
                     ns,
                     name,
                     DirectTag::function(ndef + CORE_FUNCTIONS.len()),
-                )
-                .with_heap(env);
+                )?
+                .with_heap(env)?;
 
                 ns_map.insert(name.to_string(), symbol);
             }
@@ -110,17 +122,18 @@ impl Namespace {
         Ok(ns)
     }
 
-    pub fn with_mu_static(env: &Env, defs: StaticSymbols) -> Tag {
+    pub fn with_mu_static(env: &Env, defs: StaticSymbols) -> exception::Result<Tag> {
         let mut ns_ref = block_on(env.ns_map.write());
 
         assert!(!ns_ref.contains_key("mu"));
 
-        let ns = Struct::new(env, "ns", vec![Vector::from("mu").with_heap(env)]).with_heap(env);
+        let ns =
+            Struct::new(env, "ns", vec![Vector::from("mu").with_heap(env)?])?.with_heap(env)?;
         let mut ns_map = HashMap::new();
 
         if let Some(sym_defs) = defs.0 {
             for def in sym_defs {
-                let symbol = Symbol::new(env, ns, &def.0, def.1).with_heap(env);
+                let symbol = Symbol::new(env, ns, &def.0, def.1)?.with_heap(env)?;
 
                 ns_map.insert(def.0, symbol);
             }
@@ -130,7 +143,8 @@ impl Namespace {
             for (ndef, def) in fn_defs.iter().enumerate() {
                 let name = def.0;
 
-                let symbol = Symbol::new(env, ns, name, DirectTag::function(ndef)).with_heap(env);
+                let symbol =
+                    Symbol::new(env, ns, name, DirectTag::function(ndef))?.with_heap(env)?;
 
                 ns_map.insert(name.to_string(), symbol);
             }
@@ -138,7 +152,7 @@ impl Namespace {
 
         ns_ref.insert("mu".to_string(), (ns, Namespace::Static(Some(ns_map))));
 
-        ns
+        Ok(ns)
     }
 
     fn is_namespace(env: &Env, ns: Tag) -> bool {
@@ -209,19 +223,26 @@ impl Namespace {
         )
     }
 
-    pub fn intern(env: &Env, ns: Tag, name: String, value: Tag) -> Option<Tag> {
+    pub fn intern(env: &Env, ns: Tag, name: String, value: Tag) -> exception::Result<Option<Tag>> {
         if env.keyword_ns.eq_(&ns) {
-            if name.len() > DirectTag::DIRECT_STR_MAX {
-                None?;
-            }
-
-            return Some(Symbol::keyword(&name));
+            return match name.len() {
+                0 => Ok(None),
+                len if len <= DirectTag::DIRECT_STR_MAX => Ok(Some(Symbol::keyword(&name))),
+                _ => match Self::find_symbol(env, ns, &name) {
+                    Some(symbol) => Ok(Some(symbol)),
+                    None => Ok(Some(Self::intern_keyword(env, name)?)),
+                },
+            };
         }
 
-        if let Some(symbol) = Self::find_symbol(env, ns, &name) {
-            if Symbol::is_bound(env, symbol) {
-                Some(symbol)
-            } else {
+        match Self::find_symbol(env, ns, &name) {
+            Some(symbol) if Symbol::is_bound(env, symbol) => Ok(Some(symbol)),
+            // the system heap is read-only, a symbol it left unbound is
+            // shadowed by a private one
+            Some(Tag::Indirect(indirect)) if indirect.heap_id() != Heap::PRIVATE_ID => {
+                Ok(Some(Self::intern_symbol(env, ns, name, value)?))
+            }
+            Some(symbol) => {
                 let image = Symbol::to_image(env, symbol);
 
                 let slices: &[[u8; 8]] = &[
@@ -238,21 +259,47 @@ impl Namespace {
 
                 block_on(env.heap.write()).write_image(slices, offset);
 
-                Some(symbol)
+                Ok(Some(symbol))
             }
-        } else {
-            let symbol = Symbol::new(env, ns, &name, value).with_heap(env);
-            let ns_ref = block_on(env.ns_map.read());
+            None => Ok(Some(Self::intern_symbol(env, ns, name, value)?)),
+        }
+    }
 
-            match &ns_ref[&Self::name(env, ns)].1 {
-                Namespace::Static(_) => (),
-                Namespace::Dynamic(hash) => {
-                    block_on(hash.write()).insert(name, symbol);
-                }
-            }
+    fn intern_symbol(env: &Env, ns: Tag, name: String, value: Tag) -> exception::Result<Tag> {
+        let symbol = Symbol::new(env, ns, &name, value)?.with_heap(env)?;
+
+        Ok(Self::add_symbol(env, ns, name, symbol))
+    }
 
-            Some(symbol)
+    // keywords too long for a direct tag are heap symbols, their
+    // tags marked with ExtTag::Keyword
+    fn intern_keyword(env: &Env, name: String) -> exception::Result<Tag> {
+        let image = SymbolImage {
+            namespace: env.keyword_ns,
+            name: Vector::from(name.as_str()).with_heap(env)?,
+            value: *UNBOUND,
+        };
+
+        let symbol = match Symbol::Symbol(image).with_heap(env)? {
+            Tag::Indirect(indirect) => Tag::Indirect(indirect.with_ext(ExtTag::Keyword)),
+            Tag::Direct(_) => panic!(),
+        };
+
+        Ok(Self::add_symbol(env, env.keyword_ns, name, symbol))
+    }
+
+    fn add_symbol(env: &Env, ns: Tag, name: String, symbol: Tag) -> Tag {
+        let ns_ref = block_on(env.ns_map.read());
+
+        match &ns_ref[&Self::name(env, ns)].1 {
+            Namespace::Static(_) => (),
+            Namespace::Dynamic(hash) => {
+                block_on(env.heap.write()).remember_root(symbol);
+                block_on(hash.write()).insert(name, symbol);
+            }
         }
+
+        symbol
     }
 }
 
@@ -276,7 +323,7 @@ impl CoreFn for Namespace {
             Err(Exception::err(env, ns, Condition::Type, "mu:intern"))?;
         }
 
-        fp.value = match Self::intern(env, ns, Vector::as_string(env, name), value) {
+        fp.value = match Self::intern(env, ns, Vector::as_string(env, name), value)? {
             Some(ns) => ns,
             None => Err(Exception::err(env, name, Condition::Range, "mu:intern"))?,
         };
@@ -304,7 +351,7 @@ impl CoreFn for Namespace {
             ))?;
         }
 
-        fp.value = Vector::from(Self::name(env, ns)).with_heap(env);
+        fp.value = Vector::from(Self::name(env, ns)).with_heap(env)?;
 
         Ok(())
     }
@@ -342,7 +389,5 @@ impl CoreFn for Namespace {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn namespace_test() {
-        assert!(true)
-    }
+    fn namespace_test() {}
 }
@@ -0,0 +1,406 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// persistent heap state
+//
+// a persistent heap file is the heap image followed by the environment
+// state needed to make sense of it:
+//
+//     header    magic, version, heap geometry, core function counts
+//     heap      alloc barrier, free space, alloc_map, free_map
+//     spaces    namespace tags and the namespace table
+//     caches    image cache and vector cache
+//
+// all values are little-endian u64s, strings are length prefixed.
+// the vector cache is saved with the image cache, streams other than
+// the standard streams do not survive a restart. the stream id counter
+// is saved with the caches so their tags read as released instead of
+// naming streams opened after it.
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            core_::{CORE, CORE_FUNCTIONS},
+            direct::DirectImage,
+            env::Env,
+            tag::Tag,
+            type_::Type,
+        },
+        namespaces::namespace::Namespace,
+        types::{
+            async_::Async,
+            cons::Cons,
+            function::Function,
+            symbol::SymbolImage,
+        },
+    },
+    futures_lite::future::block_on,
+    futures_locks::RwLock,
+    std::collections::HashMap,
+};
+
+const MAGIC: u64 = u64::from_le_bytes(*b"mu-heap\0");
+const VERSION: u64 = 9;
+
+struct StateWriter(Vec<u8>);
+
+impl StateWriter {
+    fn u64(&mut self, u64_: u64) {
+        self.0.extend(u64_.to_le_bytes());
+    }
+
+    fn usize(&mut self, usize_: usize) {
+        self.u64(usize_ as u64);
+    }
+
+    fn tag(&mut self, tag: Tag) {
+        self.u64(tag.as_u64());
+    }
+
+    fn str(&mut self, str: &str) {
+        self.usize(str.len());
+        self.0.extend(str.as_bytes());
+    }
+}
+
+struct StateReader<'a> {
+    state: &'a [u8],
+    offset: usize,
+}
+
+impl StateReader<'_> {
+    fn u64(&mut self) -> Option<u64> {
+        let bytes = self.state.get(self.offset..self.offset + 8)?;
+
+        self.offset += 8;
+        Some(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn usize(&mut self) -> Option<usize> {
+        usize::try_from(self.u64()?).ok()
+    }
+
+    fn tag(&mut self) -> Option<Tag> {
+        Some((&self.u64()?.to_le_bytes()).into())
+    }
+
+    fn str(&mut self) -> Option<String> {
+        let len = self.usize()?;
+        let bytes = self.state.get(self.offset..self.offset + len)?;
+
+        self.offset += len;
+        String::from_utf8(bytes.to_vec()).ok()
+    }
+}
+
+pub trait Persist {
+    fn save_state(&self) -> Vec<u8>;
+    fn restore_state(&mut self, _: &[u8]) -> std::io::Result<()>;
+    fn heap_sync(&self) -> std::io::Result<()>;
+}
+
+impl Persist for Env {
+    fn save_state(&self) -> Vec<u8> {
+        let mut state = StateWriter(Vec::new());
+
+        state.u64(MAGIC);
+        state.u64(VERSION);
+
+        PersistState::save_heap(self, &mut state);
+        PersistState::save_namespaces(self, &mut state);
+        PersistState::save_caches(self, &mut state);
+
+        state.0
+    }
+
+    fn restore_state(&mut self, state: &[u8]) -> std::io::Result<()> {
+        let mut state = StateReader { state, offset: 0 };
+
+        if state.u64() != Some(MAGIC) {
+            Err(PersistState::invalid(
+                "heap-file: not a heap file or heap geometry does not match configuration",
+            ))?;
+        }
+
+        if state.u64() != Some(VERSION) {
+            Err(PersistState::invalid("heap-file: unsupported heap version"))?;
+        }
+
+        PersistState::restore_header(self, &mut state)?;
+
+        let mut restore = || -> Option<()> {
+            PersistState::restore_heap(self, &mut state)?;
+            PersistState::restore_namespaces(self, &mut state)?;
+            PersistState::restore_caches(self, &mut state)
+        };
+
+        restore().ok_or_else(|| PersistState::invalid("heap-file: truncated heap state"))
+    }
+
+    fn heap_sync(&self) -> std::io::Result<()> {
+        if self.config.heap_file.is_none() {
+            return Ok(());
+        }
+
+        let state = self.save_state();
+
+        block_on(self.heap.write()).write_state(&state)
+    }
+}
+
+struct PersistState;
+
+impl PersistState {
+    fn save_heap(env: &Env, state: &mut StateWriter) {
+        let heap_ref = block_on(env.heap.read());
+
+        state.usize(heap_ref.page_size);
+        state.usize(heap_ref.npages);
+        state.usize(CORE_FUNCTIONS.len());
+        state.usize(CORE.fn_defs.len());
+
+        state.usize(heap_ref.alloc_barrier);
+        state.usize(heap_ref.free_space);
+        state.usize(heap_ref.gc_allocated);
+        state.usize(heap_ref.relocations);
+
+        for type_info in &heap_ref.alloc_map {
+            state.usize(type_info.size);
+            state.usize(type_info.total);
+            state.usize(type_info.free);
+        }
+
+        for free_map in &heap_ref.free_map {
+            state.usize(free_map.len());
+            for index in free_map {
+                state.usize(*index);
+            }
+        }
+    }
+
+    fn invalid(message: &str) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+    }
+
+    fn restore_header(env: &Env, state: &mut StateReader) -> std::io::Result<()> {
+        let heap_ref = block_on(env.heap.read());
+
+        if state.usize() != Some(heap_ref.page_size) || state.usize() != Some(heap_ref.npages) {
+            Err(Self::invalid(
+                "heap-file: heap geometry does not match configuration",
+            ))?;
+        }
+
+        if state.usize() != Some(CORE_FUNCTIONS.len()) || state.usize() != Some(CORE.fn_defs.len())
+        {
+            Err(Self::invalid(
+                "heap-file: heap was saved by an incompatible runtime",
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    fn restore_heap(env: &Env, state: &mut StateReader) -> Option<()> {
+        let mut heap_ref = block_on(env.heap.write());
+
+        heap_ref.alloc_barrier = state.usize()?;
+        heap_ref.free_space = state.usize()?;
+        heap_ref.gc_allocated = state.usize()?;
+        heap_ref.relocations = state.usize()?;
+
+        for type_info in &mut heap_ref.alloc_map {
+            type_info.size = state.usize()?;
+            type_info.total = state.usize()?;
+            type_info.free = state.usize()?;
+        }
+
+        for free_map in &mut heap_ref.free_map {
+            let len = state.usize()?;
+
+            free_map.clear();
+            for _ in 0..len {
+                free_map.push(state.usize()?);
+            }
+        }
+
+        Some(())
+    }
+
+    fn save_namespaces(env: &Env, state: &mut StateWriter) {
+        let ns_ref = block_on(env.ns_map.read());
+
+        state.tag(env.keyword_ns);
+        state.tag(env.mu_ns);
+
+        state.usize(ns_ref.len());
+        for (name, (ns, namespace)) in ns_ref.iter() {
+            let (kind, symbols) = match namespace {
+                Namespace::Static(static_) => (
+                    0,
+                    static_
+                        .as_ref()
+                        .map(|hash| {
+                            hash.iter()
+                                .map(|(name, tag)| (name.clone(), *tag))
+                                .collect::<Vec<(String, Tag)>>()
+                        })
+                        .unwrap_or_default(),
+                ),
+                Namespace::Dynamic(hash) => (
+                    1,
+                    block_on(hash.read())
+                        .iter()
+                        .map(|(name, tag)| (name.clone(), *tag))
+                        .collect::<Vec<(String, Tag)>>(),
+                ),
+            };
+
+            state.str(name);
+            state.tag(*ns);
+            state.u64(kind);
+            state.usize(symbols.len());
+
+            for (name, symbol) in symbols {
+                state.str(&name);
+                state.tag(symbol);
+            }
+        }
+    }
+
+    fn restore_namespaces(env: &mut Env, state: &mut StateReader) -> Option<()> {
+        env.keyword_ns = state.tag()?;
+        env.mu_ns = state.tag()?;
+
+        let mut ns_ref = block_on(env.ns_map.write());
+
+        for _ in 0..state.usize()? {
+            let name = state.str()?;
+            let ns = state.tag()?;
+            let kind = state.u64()?;
+            let mut hash = HashMap::new();
+
+            for _ in 0..state.usize()? {
+                let name = state.str()?;
+
+                hash.insert(name, state.tag()?);
+            }
+
+            let namespace = match kind {
+                0 => Namespace::Static(Some(hash)),
+                _ => Namespace::Dynamic(RwLock::new(hash)),
+            };
+
+            ns_ref.insert(name, (ns, namespace));
+        }
+
+        Some(())
+    }
+
+    fn save_caches(env: &Env, state: &mut StateWriter) {
+        let cache_ref = block_on(env.cache.read());
+
+        state.u64(cache_ref.tag_id);
+        state.u64(*block_on(CORE.stream_id.read()));
+        for type_info in &cache_ref.type_info {
+            state.usize(type_info.size);
+            state.usize(type_info.total);
+        }
+
+        state.usize(cache_ref.image_hash.len());
+        for (id, image) in &cache_ref.image_hash {
+            state.u64(*id);
+            state.u64(u64::from(image.type_of() as u8));
+
+            match image {
+                DirectImage::Async(image) => {
+                    state.tag(image.arity);
+                    state.tag(image.form);
+                }
+                DirectImage::Cons(image) => {
+                    state.tag(image.car);
+                    state.tag(image.cdr);
+                }
+                DirectImage::Function(image) => {
+                    state.tag(image.arity);
+                    state.tag(image.form);
+                }
+                DirectImage::Symbol(image) => {
+                    state.tag(image.namespace);
+                    state.tag(image.name);
+                    state.tag(image.value);
+                }
+            }
+        }
+
+        let vector_cache_ref = block_on(env.vector_cache.read());
+
+        state.usize(vector_cache_ref.len());
+        for ((type_, length), vectors) in vector_cache_ref.iter() {
+            let vectors = block_on(vectors.read());
+
+            state.u64(u64::from(*type_ as u8));
+            state.usize(usize::try_from(*length).unwrap());
+            state.usize(vectors.len());
+            for vector in vectors.iter() {
+                state.tag(*vector);
+            }
+        }
+    }
+
+    fn restore_caches(env: &Env, state: &mut StateReader) -> Option<()> {
+        let mut cache_ref = block_on(env.cache.write());
+
+        cache_ref.tag_id = state.u64()?;
+
+        let stream_id = state.u64()?;
+        let mut stream_id_ref = block_on(CORE.stream_id.write());
+
+        *stream_id_ref = (*stream_id_ref).max(stream_id);
+        drop(stream_id_ref);
+
+        for type_info in &mut cache_ref.type_info {
+            type_info.size = state.usize()?;
+            type_info.total = state.usize()?;
+        }
+
+        for _ in 0..state.usize()? {
+            let id = state.u64()?;
+            let image = match Type::try_from(u8::try_from(state.u64()?).ok()?).ok()? {
+                Type::Async => DirectImage::Async(Async::new(state.tag()?, state.tag()?)),
+                Type::Cons => DirectImage::Cons(Cons::new(state.tag()?, state.tag()?)),
+                Type::Function => DirectImage::Function(Function::new(state.tag()?, state.tag()?)),
+                Type::Symbol => DirectImage::Symbol(SymbolImage {
+                    namespace: state.tag()?,
+                    name: state.tag()?,
+                    value: state.tag()?,
+                }),
+                _ => None?,
+            };
+
+            cache_ref.image_hash.insert(id, image);
+        }
+
+        let mut vector_cache_ref = block_on(env.vector_cache.write());
+
+        for _ in 0..state.usize()? {
+            let type_ = Type::try_from(u8::try_from(state.u64()?).ok()?).ok()?;
+            let length = i32::try_from(state.usize()?).ok()?;
+            let mut vectors = Vec::new();
+
+            for _ in 0..state.usize()? {
+                vectors.push(state.tag()?);
+            }
+
+            vector_cache_ref.insert((type_, length), RwLock::new(vectors));
+        }
+
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn persist_test() {}
+}
@@ -0,0 +1,184 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// system heap
+//
+// a read-only heap frozen from a loaded environment and shared by the
+// environments created on it. its images carry Heap::SYSTEM_ID in their
+// indirect tags, environments allocate on their own private heap, and
+// the collector never marks or sweeps the system heap.
+//
+// an environment starts with copies of the system namespace table and
+// image cache. binding a symbol the system heap left unbound interns a
+// private symbol that shadows it, and system code referring to the
+// unbound symbol sees the private binding.
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            config::Config,
+            direct::{DirectImage, DirectTag, DirectType, ExtType},
+            env::Env,
+            tag::Tag,
+        },
+        gc::gc_::{Gc as _, GcContext},
+        namespaces::{
+            cache::Cache,
+            heap::Heap,
+            namespace::Namespace,
+        },
+    },
+    futures_lite::future::block_on,
+    futures_locks::RwLock,
+    std::{collections::HashMap, mem::size_of},
+};
+
+pub struct SystemHeap {
+    pub heap: Heap,
+    pub ns_map: HashMap<String, (Tag, Namespace)>,
+    pub cache: Cache,
+    pub keyword_ns: Tag,
+    pub mu_ns: Tag,
+}
+
+impl SystemHeap {
+    // freeze an environment's heap, collected and compacted
+    pub fn new(env: &Env) -> Self {
+        assert!(
+            env.system.is_none(),
+            "system heap: environment already has a system heap"
+        );
+
+        GcContext::gc(env).unwrap();
+        GcContext::compact(env, &mut []);
+
+        let heap_ref = block_on(env.heap.read());
+        let config = Config {
+            heap_file: None,
+            npages: heap_ref.npages,
+            max_pages: heap_ref.npages,
+            ..env.config.clone()
+        };
+        let mut heap = Heap::new(&config);
+        let barrier = heap_ref.alloc_barrier;
+
+        heap.mmap[..barrier].copy_from_slice(&heap_ref.mmap[..barrier]);
+        heap.alloc_barrier = barrier;
+        heap.alloc_map = heap_ref.alloc_map;
+        heap.free_map.clone_from(&heap_ref.free_map);
+        heap.free_space = heap_ref.free_space;
+        heap.relocations = heap_ref.relocations;
+
+        let mut off = 0;
+        while off < barrier {
+            let index = off / size_of::<u64>() + 1;
+            let info = heap.image_info(index).unwrap();
+
+            if info.image_type() != Heap::FREE_IMAGE {
+                for offset in heap.image_tags(index) {
+                    let tag = Self::retag(Tag::from_slice(heap.image_slice(offset).unwrap()));
+
+                    heap.write_image(&[tag.as_slice()], offset);
+                }
+            }
+
+            off += info.len() as usize;
+        }
+
+        heap.promote();
+
+        let ns_map = block_on(env.ns_map.read())
+            .iter()
+            .map(|(name, (ns, namespace))| {
+                let retag = |hash: &HashMap<String, Tag>| {
+                    hash.iter()
+                        .map(|(name, symbol)| (name.clone(), Self::retag(*symbol)))
+                        .collect::<HashMap<String, Tag>>()
+                };
+
+                let namespace = match namespace {
+                    Namespace::Static(static_) => Namespace::Static(static_.as_ref().map(retag)),
+                    Namespace::Dynamic(hash) => {
+                        Namespace::Dynamic(RwLock::new(retag(&block_on(hash.read()))))
+                    }
+                };
+
+                (name.clone(), (Self::retag(*ns), namespace))
+            })
+            .collect();
+
+        let mut cache = block_on(env.cache.read()).clone();
+
+        for image in cache.image_hash.values_mut() {
+            match image {
+                DirectImage::Async(image) => {
+                    image.arity = Self::retag(image.arity);
+                    image.form = Self::retag(image.form);
+                }
+                DirectImage::Cons(image) => {
+                    image.car = Self::retag(image.car);
+                    image.cdr = Self::retag(image.cdr);
+                }
+                DirectImage::Function(image) => {
+                    image.arity = Self::retag(image.arity);
+                    image.form = Self::retag(image.form);
+                }
+                DirectImage::Symbol(image) => {
+                    image.namespace = Self::retag(image.namespace);
+                    image.name = Self::retag(image.name);
+                    image.value = Self::retag(image.value);
+                }
+            }
+        }
+
+        SystemHeap {
+            heap,
+            ns_map,
+            cache,
+            keyword_ns: Self::retag(env.keyword_ns),
+            mu_ns: Self::retag(env.mu_ns),
+        }
+    }
+
+    // a private namespace table for an environment on this heap
+    pub fn namespaces(&self) -> HashMap<String, (Tag, Namespace)> {
+        self.ns_map
+            .iter()
+            .map(|(name, (ns, namespace))| {
+                let namespace = match namespace {
+                    Namespace::Static(static_) => Namespace::Static(static_.clone()),
+                    Namespace::Dynamic(hash) => {
+                        Namespace::Dynamic(RwLock::new(block_on(hash.read()).clone()))
+                    }
+                };
+
+                (name.clone(), (*ns, namespace))
+            })
+            .collect()
+    }
+
+    // move a private heap tag to the system heap, direct conses
+    // are repacked
+    fn retag(tag: Tag) -> Tag {
+        match tag {
+            Tag::Direct(direct) => {
+                if direct.dtype() == DirectType::Ext
+                    && matches!(ExtType::try_from(direct.ext()), Ok(ExtType::Cons))
+                {
+                    let (car, cdr) = DirectTag::cons_destruct(tag);
+
+                    DirectTag::cons(Self::retag(car), Self::retag(cdr)).unwrap()
+                } else {
+                    tag
+                }
+            }
+            Tag::Indirect(indirect) => Tag::Indirect(indirect.with_heap_id(Heap::SYSTEM_ID)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn system_test() {}
+}
@@ -0,0 +1,559 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// heap verifier
+//
+// a walk of the heap that checks what the collector relies on:
+//
+//     headers     image lengths and types, the walk ends on the barrier
+//     tags        an indirect tag held in an image names the header of
+//                 an allocated image of the tag's type, on the system
+//                 heap if the tag says so
+//     free lists  every free image is on the list for its size class
+//     counters    free space and the alloc_map agree with the walk
+//
+// problems are reported, not repaired, and the walk does not trust
+// the heap, a bad header ends it rather than panicking.
+//
+// the graph dump writes the private heap images reachable from a root
+// as DOT, or as JSON if the file name ends in .json.
+#[rustfmt::skip]
+use {
+    crate::{
+        core::{
+            direct::{DirectTag, DirectType, ExtType},
+            env::Env,
+            indirect::{ExtTag, IndirectTag},
+            tag::{Tag, TagType},
+            type_::Type,
+        },
+        namespaces::heap::{Heap, HeapImageInfo},
+        types::{
+            fixnum::Fixnum,
+            vector::{Vector, VectorType},
+        },
+    },
+    futures_lite::future::block_on,
+    std::{
+        collections::{HashMap, HashSet, VecDeque},
+        fs::File,
+        io::{BufWriter, Write},
+        mem::size_of,
+        ops::Range,
+    },
+};
+
+pub trait Verify {
+    fn heap_verify(&self) -> Vec<String>;
+    fn heap_graph(&self, _: Tag, _: &str) -> std::io::Result<usize>;
+}
+
+impl Verify for Env {
+    fn heap_verify(&self) -> Vec<String> {
+        let heap_ref = block_on(self.heap.read());
+        let mut verifier = Verifier::new(&heap_ref, Heap::PRIVATE_ID);
+        let mut errors = Vec::new();
+
+        if let Some(system) = &self.system {
+            let mut system = Verifier::new(&system.heap, Heap::SYSTEM_ID);
+
+            system.tags();
+            system.free_lists();
+            system.counters();
+            errors.extend(
+                system
+                    .errors
+                    .iter()
+                    .map(|error| format!("system heap: {error}")),
+            );
+            verifier.system = Some(system.images);
+        }
+
+        verifier.tags();
+        verifier.free_lists();
+        verifier.counters();
+        errors.extend(verifier.errors);
+
+        errors
+    }
+
+    fn heap_graph(&self, root: Tag, path: &str) -> std::io::Result<usize> {
+        let heap_ref = block_on(self.heap.read());
+        let verifier = Verifier::new(&heap_ref, Heap::PRIVATE_ID);
+        let mut out = BufWriter::new(File::create(path)?);
+        let json = std::path::Path::new(path)
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+
+        let mut queue = VecDeque::new();
+        let mut seen = HashSet::new();
+        let mut nodes = Vec::new();
+
+        Verifier::indirects(root, &mut queue);
+        while let Some(index) = queue.pop_front() {
+            if !seen.insert(index) {
+                continue;
+            }
+
+            let mut refs = VecDeque::new();
+
+            if let Some(range) = verifier.tag_words(index) {
+                for offset in range {
+                    Verifier::indirects(verifier.tag_at(offset), &mut refs);
+                }
+            }
+
+            queue.extend(refs.iter());
+            nodes.push((index, refs));
+        }
+
+        if json {
+            writeln!(out, "{{")?;
+            writeln!(out, "  \"root\": \"{:#x}\",", root.as_u64())?;
+            writeln!(out, "  \"nodes\": [")?;
+            for (nth, (index, refs)) in nodes.iter().enumerate() {
+                let refs = refs
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                writeln!(
+                    out,
+                    "    {{ \"index\": {index}, \"type\": \"{}\", \"len\": {}, \"refs\": [{refs}] }}{}",
+                    verifier.type_name(*index),
+                    verifier.images.get(index).map_or(0, HeapImageInfo::len),
+                    if nth + 1 == nodes.len() { "" } else { "," },
+                )?;
+            }
+            writeln!(out, "  ]")?;
+            writeln!(out, "}}")?;
+        } else {
+            writeln!(out, "digraph heap {{")?;
+            for (index, refs) in &nodes {
+                writeln!(
+                    out,
+                    "  n{index} [label=\"{} {index}\\n{}\"];",
+                    verifier.type_name(*index),
+                    verifier.images.get(index).map_or(0, HeapImageInfo::len),
+                )?;
+                for ref_ in refs {
+                    writeln!(out, "  n{index} -> n{ref_};")?;
+                }
+            }
+            writeln!(out, "}}")?;
+        }
+
+        out.flush()?;
+
+        Ok(nodes.len())
+    }
+}
+
+struct Verifier<'a> {
+    heap: &'a Heap,
+    heap_id: u8,
+    images: HashMap<usize, HeapImageInfo>,
+    system: Option<HashMap<usize, HeapImageInfo>>,
+    errors: Vec<String>,
+}
+
+impl<'a> Verifier<'a> {
+    // walk the headers up to the barrier
+    fn new(heap: &'a Heap, heap_id: u8) -> Self {
+        let mut verifier = Verifier {
+            heap,
+            heap_id,
+            images: HashMap::new(),
+            system: None,
+            errors: Vec::new(),
+        };
+        let mut off = 0;
+
+        if heap.alloc_barrier > heap.size {
+            verifier.errors.push(format!(
+                "alloc barrier {} is past the end of the heap",
+                heap.alloc_barrier
+            ));
+
+            return verifier;
+        }
+
+        while off < heap.alloc_barrier {
+            let index = off / size_of::<u64>() + 1;
+            let info = heap.image_info(index).unwrap();
+            let len = info.len() as usize;
+
+            if len < Heap::IMAGE_MIN || !len.is_multiple_of(size_of::<u64>()) {
+                verifier
+                    .errors
+                    .push(format!("image {index}: bad length {len}, heap walk ends"));
+                break;
+            }
+
+            if off + len > heap.alloc_barrier {
+                verifier.errors.push(format!(
+                    "image {index}: length {len} runs past the alloc barrier"
+                ));
+                break;
+            }
+
+            if info.image_type() != Heap::FREE_IMAGE && Self::heap_type(info).is_none() {
+                verifier
+                    .errors
+                    .push(format!("image {index}: bad type {}", info.image_type()));
+            }
+
+            if info.reloc() != 0 {
+                verifier
+                    .errors
+                    .push(format!("image {index}: stale relocation {}", info.reloc()));
+            }
+
+            verifier.images.insert(index, info);
+            off += len;
+        }
+
+        verifier
+    }
+
+    // the types that live on the heap
+    fn heap_type(info: HeapImageInfo) -> Option<Type> {
+        match Type::try_from(info.image_type()) {
+            Ok(
+                type_ @ (Type::Async
+                | Type::Bignum
+                | Type::Cons
+                | Type::Double
+                | Type::Function
+                | Type::Hash
+                | Type::Struct
+                | Type::Symbol
+                | Type::Vector),
+            ) => Some(type_),
+            _ => None,
+        }
+    }
+
+    fn tag_type(indirect: IndirectTag) -> Option<Type> {
+        match indirect.tag() {
+            TagType::Async => Some(Type::Async),
+            TagType::Cons => Some(Type::Cons),
+            TagType::Function => Some(Type::Function),
+            TagType::Struct => Some(Type::Struct),
+            TagType::Symbol => Some(Type::Symbol),
+            TagType::Vector => Some(Type::Vector),
+            TagType::Ext => match indirect.ext() {
+                ExtTag::Bignum => Some(Type::Bignum),
+                ExtTag::Double => Some(Type::Double),
+                ExtTag::Hash => Some(Type::Hash),
+                ExtTag::None | ExtTag::Keyword => None,
+            },
+            TagType::Direct => None,
+        }
+    }
+
+    fn type_name(&self, index: usize) -> String {
+        match self.images.get(&index) {
+            Some(info) if info.image_type() == Heap::FREE_IMAGE => "free".into(),
+            Some(info) => match Self::heap_type(*info) {
+                Some(type_) => format!("{type_:?}").to_lowercase(),
+                None => "invalid".into(),
+            },
+            None => "dangling".into(),
+        }
+    }
+
+    fn tag_at(&self, offset: usize) -> Tag {
+        Tag::from_slice(self.heap.image_slice(offset).unwrap())
+    }
+
+    fn is_fixnum(tag: Tag) -> bool {
+        match tag {
+            Tag::Direct(direct) => {
+                direct.dtype() == DirectType::Ext
+                    && matches!(ExtType::try_from(direct.ext()), Ok(ExtType::Fixnum))
+            }
+            Tag::Indirect(_) => false,
+        }
+    }
+
+    // the words of an allocated image that hold tags, checked against
+    // the image length before the heap's own image_tags trusts them
+    fn tag_words(&self, index: usize) -> Option<Range<usize>> {
+        let info = self.images.get(&index)?;
+        let nwords = info.len() as usize / size_of::<u64>() - 1;
+
+        let type_ = Self::heap_type(*info)?;
+
+        if type_ == Type::Vector {
+            let image = self.tag_at(index);
+            let length = self.tag_at(index + 1);
+
+            if nwords < 2 || !Self::is_fixnum(length) || Fixnum::as_i64(length) < 0 {
+                return None;
+            }
+
+            if Vector::to_type(image) == Some(VectorType::T)
+                && usize::try_from(Fixnum::as_i64(length)).unwrap() + 2 > nwords
+            {
+                return None;
+            }
+        }
+
+        let range = self.heap.image_tags(index);
+
+        if range.end > index + nwords {
+            None
+        } else {
+            Some(range)
+        }
+    }
+
+    // the indirect tags a tag holds, direct conses may hold two
+    fn indirects(tag: Tag, out: &mut VecDeque<usize>) {
+        match tag {
+            Tag::Direct(direct) => {
+                if direct.dtype() == DirectType::Ext
+                    && matches!(ExtType::try_from(direct.ext()), Ok(ExtType::Cons))
+                {
+                    let (car, cdr) = DirectTag::cons_destruct(tag);
+
+                    Self::indirects(car, out);
+                    Self::indirects(cdr, out);
+                }
+            }
+            Tag::Indirect(indirect) if indirect.heap_id() == Heap::PRIVATE_ID => {
+                out.push_back(usize::try_from(indirect.image_id()).unwrap());
+            }
+            Tag::Indirect(_) => (),
+        }
+    }
+
+    fn check_tag(&mut self, index: usize, tag: Tag) {
+        match tag {
+            Tag::Direct(direct) => {
+                if direct.dtype() != DirectType::Ext {
+                    return;
+                }
+
+                match ExtType::try_from(direct.ext()) {
+                    Ok(ExtType::Cons) => {
+                        let (car, cdr) = DirectTag::cons_destruct(tag);
+
+                        self.check_tag(index, car);
+                        self.check_tag(index, cdr);
+                    }
+                    Ok(_) => (),
+                    Err(_) => self
+                        .errors
+                        .push(format!("image {index}: bad direct tag {:#x}", tag.as_u64())),
+                }
+            }
+            Tag::Indirect(indirect) => {
+                let image_id = usize::try_from(indirect.image_id()).unwrap();
+
+                let system = match indirect.heap_id() {
+                    heap_id if heap_id == self.heap_id => false,
+                    Heap::SYSTEM_ID if self.system.is_some() => true,
+                    heap_id => {
+                        self.errors.push(format!(
+                            "image {index}: tag {:#x} names heap {heap_id}",
+                            tag.as_u64(),
+                        ));
+
+                        return;
+                    }
+                };
+
+                let Some(tag_type) = Self::tag_type(indirect) else {
+                    self.errors.push(format!(
+                        "image {index}: tag {:#x} has no heap type",
+                        tag.as_u64()
+                    ));
+
+                    return;
+                };
+
+                let images = if system {
+                    self.system.as_ref().unwrap()
+                } else {
+                    &self.images
+                };
+
+                let error = match images.get(&image_id) {
+                    None => Some(format!(
+                        "image {index}: tag {:#x} names no image",
+                        tag.as_u64()
+                    )),
+                    Some(info) if info.image_type() == Heap::FREE_IMAGE => Some(format!(
+                        "image {index}: tag {:#x} names free image {image_id}",
+                        tag.as_u64()
+                    )),
+                    Some(info) => match Self::heap_type(*info) {
+                        type_ if type_ == Some(tag_type) => None,
+                        type_ => Some(format!(
+                            "image {index}: {:?} tag names {} image {image_id}",
+                            tag_type,
+                            type_.map_or("invalid".into(), |type_| format!("{type_:?}")
+                                .to_lowercase()),
+                        )),
+                    },
+                };
+
+                self.errors.extend(error);
+            }
+        }
+    }
+
+    // the tags held in every allocated image
+    fn tags(&mut self) {
+        let mut allocated = self
+            .images
+            .iter()
+            .filter(|(_, info)| Self::heap_type(**info).is_some())
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>();
+
+        allocated.sort_unstable();
+        for index in allocated {
+            match self.tag_words(index) {
+                Some(range) => {
+                    for offset in range {
+                        self.check_tag(index, self.tag_at(offset));
+                    }
+                }
+                None => self.errors.push(format!(
+                    "image {index}: malformed {}",
+                    self.type_name(index)
+                )),
+            }
+        }
+    }
+
+    fn free_lists(&mut self) {
+        let mut listed = HashSet::new();
+
+        for (class, free_map) in self.heap.free_map.iter().enumerate() {
+            for index in free_map {
+                if !listed.insert(*index) {
+                    self.errors
+                        .push(format!("free image {index} is listed more than once"));
+                }
+
+                match self.images.get(index) {
+                    None => self
+                        .errors
+                        .push(format!("free list {class}: {index} names no image")),
+                    Some(info) if info.image_type() != Heap::FREE_IMAGE => self
+                        .errors
+                        .push(format!("free list {class}: {index} is an allocated image")),
+                    Some(info) => {
+                        if Heap::size_class(info.len() as usize) != class {
+                            self.errors.push(format!(
+                                "free list {class}: image {index} of length {} is in the wrong class",
+                                info.len()
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut unlisted = self
+            .images
+            .iter()
+            .filter(|(index, info)| {
+                info.image_type() == Heap::FREE_IMAGE && !listed.contains(*index)
+            })
+            .map(|(index, _)| *index)
+            .collect::<Vec<usize>>();
+
+        unlisted.sort_unstable();
+        for index in unlisted {
+            self.errors
+                .push(format!("free image {index} is on no free list"));
+        }
+    }
+
+    fn counters(&mut self) {
+        let heap = self.heap;
+        let free = self
+            .images
+            .values()
+            .filter(|info| info.image_type() == Heap::FREE_IMAGE)
+            .map(|info| info.len() as usize)
+            .sum::<usize>();
+
+        if heap.free_space != heap.size - heap.alloc_barrier + free {
+            self.errors.push(format!(
+                "free space {} should be {}",
+                heap.free_space,
+                heap.size - heap.alloc_barrier + free
+            ));
+        }
+
+        // the alloc_map counts every allocation since the last
+        // compaction, it can only overcount the images on the heap
+        let mut counts = [(0, 0); Type::NTYPES];
+
+        for info in self.images.values() {
+            if let Some(type_) = Self::heap_type(*info) {
+                counts[type_ as usize].0 += 1;
+                counts[type_ as usize].1 += info.len() as usize - size_of::<HeapImageInfo>();
+            }
+        }
+
+        for (type_id, (total, size)) in counts.iter().enumerate() {
+            let type_map = heap.alloc_map[type_id];
+
+            if *total > type_map.total || *size > type_map.size {
+                self.errors.push(format!(
+                    "alloc map for {:?} has {} images of {} bytes, the heap {total} of {size}",
+                    Type::try_from(u8::try_from(type_id).unwrap()).unwrap(),
+                    type_map.total,
+                    type_map.size,
+                ));
+            }
+
+            if type_map.free > type_map.total {
+                self.errors.push(format!(
+                    "alloc map for {:?} frees {} of {} images",
+                    Type::try_from(u8::try_from(type_id).unwrap()).unwrap(),
+                    type_map.free,
+                    type_map.total,
+                ));
+            }
+        }
+
+        if heap.old_barrier != heap.alloc_barrier
+            && !self
+                .images
+                .contains_key(&(heap.old_barrier / size_of::<u64>() + 1))
+        {
+            self.errors.push(format!(
+                "old generation barrier {} is not on an image",
+                heap.old_barrier
+            ));
+        }
+
+        let mut remembered = heap
+            .remembered
+            .iter()
+            .filter(|index| !self.images.contains_key(*index))
+            .copied()
+            .collect::<Vec<usize>>();
+
+        remembered.sort_unstable();
+        for index in remembered {
+            self.errors
+                .push(format!("remembered image {index} names no image"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn verify() {}
+}
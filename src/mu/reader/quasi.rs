@@ -53,8 +53,8 @@ impl QuasiReader {
     pub fn new(env: &Env, stream: Tag) -> Self {
         Self {
             stream,
-            cons: Namespace::intern(env, env.mu_ns, "cons".into(), Tag::nil()).unwrap(),
-            qappend: Namespace::intern(env, env.mu_ns, "append".into(), Tag::nil()).unwrap(),
+            cons: Namespace::find_symbol(env, env.mu_ns, "cons").unwrap(),
+            qappend: Namespace::find_symbol(env, env.mu_ns, "append").unwrap(),
         }
     }
 
@@ -17,8 +17,9 @@ use {
         reader::{quasi::QuasiReader, readtable::SyntaxType},
         streams::reader::StreamReader,
         types::{
+            bignum::Bignum,
+            double::Double,
             cons::Cons,
-            fixnum::Fixnum,
             struct_::Struct,
             symbol::{Symbol, SymbolImage},
             vector::Vector
@@ -175,21 +176,22 @@ impl Reader for Env {
         }
 
         match token.parse::<i64>() {
-            Ok(fx) => {
-                if Fixnum::is_i56(fx) {
-                    Ok(Fixnum::with_i64(self, fx).unwrap())
-                } else {
-                    Err(Exception::err(
+            Ok(fx) => Bignum::with_i128(self, i128::from(fx)),
+            Err(_) => match Bignum::parse(&token, 10) {
+                Some(bignum) => bignum.integer(self, "mu:read"),
+                None => match Double::parse(&token) {
+                    Some(fl) if fl.is_finite() => Double::with_f64(self, fl),
+                    Some(_) => Err(Exception::err(
                         self,
-                        Vector::from(token).with_heap(self),
+                        Vector::from(token.as_str()).with_heap(self)?,
                         Condition::Over,
                         "mu:read",
-                    ))?
-                }
-            }
-            Err(_) => match token.parse::<f32>() {
-                Ok(fl) => Ok(fl.into()),
-                Err(_) => Ok(Symbol::parse(self, &token)?),
+                    ))?,
+                    None => match token.parse::<f32>() {
+                        Ok(fl) => Ok(fl.into()),
+                        Err(_) => Ok(Symbol::parse(self, &token)?),
+                    },
+                },
             },
         }
     }
@@ -218,7 +220,7 @@ impl Reader for Env {
                                         "return" => Ok(Some('\r'.into())),
                                         _ => Err(Exception::err(
                                             self,
-                                            Vector::from(phrase).with_heap(self),
+                                            Vector::from(phrase).with_heap(self)?,
                                             Condition::Type,
                                             "mu:read",
                                         ))?,
@@ -272,7 +274,7 @@ impl Reader for Env {
                                     name,
                                     value,
                                 })
-                                .with_heap(self);
+                                .with_heap(self)?;
 
                                 Ok(Some(symbol))
                             }
@@ -299,20 +301,9 @@ impl Reader for Env {
                     Ok(token) => {
                         let hex = token.unwrap();
 
-                        match i64::from_str_radix(&hex, 16) {
-                            Ok(fx) => {
-                                if Fixnum::is_i56(fx) {
-                                    Ok(Some(Fixnum::with_i64(self, fx).unwrap()))
-                                } else {
-                                    Err(Exception::err(
-                                        self,
-                                        Vector::from(hex).with_heap(self),
-                                        Condition::Over,
-                                        "mu:read",
-                                    ))?
-                                }
-                            }
-                            Err(_) => Err(Exception::err(
+                        match Bignum::parse(&hex, 16) {
+                            Some(bignum) => Ok(Some(bignum.integer(self, "mu:read")?)),
+                            None => Err(Exception::err(
                                 self,
                                 ch.into(),
                                 Condition::Syntax,
@@ -407,7 +398,5 @@ impl Reader for Env {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn reader_test() {
-        assert!(true);
-    }
+    fn reader_test() {}
 }
@@ -117,15 +117,21 @@ static SYNTAX_MAP: LazyLock<HashMap<char, SyntaxType>> = LazyLock::new(|| {
 });
 
 impl SyntaxType {
+    // characters past ascii are constituents or whitespace
     pub fn map_char_syntax(ch: char) -> Option<&'static SyntaxType> {
-        SYNTAX_MAP.get(&ch)
+        match SYNTAX_MAP.get(&ch) {
+            None if !ch.is_ascii() => Some(if ch.is_whitespace() {
+                &SyntaxType::Whitespace
+            } else {
+                &SyntaxType::Constituent
+            }),
+            syntax => syntax,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     #[test]
-    fn readtable_test() {
-        assert!(true);
-    }
+    fn readtable_test() {}
 }
@@ -130,7 +130,5 @@ impl StreamBuilder {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn builder_test() {
-        assert!(true);
-    }
+    fn builder_test() {}
 }
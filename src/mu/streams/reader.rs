@@ -15,6 +15,7 @@ use {
         types::{char::Char, stream::Stream, symbol::Symbol},
     },
     futures_lite::future::block_on,
+    std::str,
 };
 
 pub struct StreamReader;
@@ -52,7 +53,24 @@ impl StreamReader {
 
                 if stream.unch.null_() {
                     match stream.system.read_byte(env)? {
-                        Some(byte) => Ok(Some(byte as char)),
+                        Some(byte) => {
+                            // the sequence length is in the leading byte, malformed
+                            // sequences decode as the replacement character
+                            let mut utf8 = vec![byte];
+
+                            for _ in 1..byte.leading_ones().clamp(1, 4) {
+                                match stream.system.read_byte(env)? {
+                                    Some(byte) => utf8.push(byte),
+                                    None => break,
+                                }
+                            }
+
+                            Ok(Some(
+                                str::from_utf8(&utf8).map_or(char::REPLACEMENT_CHARACTER, |str| {
+                                    str.chars().next().unwrap()
+                                }),
+                            ))
+                        }
                         None => Ok(None),
                     }
                 } else {
@@ -166,7 +184,5 @@ impl StreamReader {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn reader_test() {
-        assert!(true);
-    }
+    fn reader_test() {}
 }
@@ -189,7 +189,5 @@ impl SocketStream {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn stream() {
-        assert!(true)
-    }
+    fn stream() {}
 }
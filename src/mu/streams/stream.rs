@@ -87,6 +87,7 @@ impl SystemStream {
 
             *id += 1;
 
+            block_on(env.streams.write()).insert(stream_id);
             streams_ref.insert(
                 stream_id,
                 RwLock::new(Stream {
@@ -139,6 +140,7 @@ impl SystemStream {
 
             *id += 1;
 
+            block_on(env.streams.write()).insert(stream_id);
             streams_ref.insert(
                 stream_id,
                 RwLock::new(Stream {
@@ -216,7 +218,5 @@ impl SystemStream {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn stream() {
-        assert!(true)
-    }
+    fn stream() {}
 }
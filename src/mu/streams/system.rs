@@ -233,7 +233,5 @@ impl SystemStream {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn stream() {
-        assert!(true)
-    }
+    fn stream() {}
 }
@@ -6,14 +6,14 @@ use {
     crate::{
         core::{
             env::Env,
-            exception,
+            exception::{self, Condition, Exception},
             indirect::IndirectTag,
             tag::{Tag, TagType},
             type_::Type,
         },
-        namespaces::heap::HeapRequest,
+        namespaces::heap::{Heap, HeapRequest},
         streams::writer::StreamWriter,
-        types::{cons::Cons, fixnum::Fixnum, symbol::Symbol, vector::Vector},
+        types::{cons::Cons, fixnum::Fixnum, struct_::Struct, symbol::Symbol, vector::Vector},
     },
     futures_lite::future::block_on,
 };
@@ -34,7 +34,7 @@ impl Async {
 
         match tag {
             Tag::Indirect(fn_) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, fn_);
                 let slice = usize::try_from(fn_.image_id()).unwrap();
 
                 Self {
@@ -51,7 +51,7 @@ impl Async {
 
         match func {
             Tag::Indirect(fn_) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, fn_);
                 let slice = usize::try_from(fn_.image_id()).unwrap();
 
                 (
@@ -63,9 +63,8 @@ impl Async {
         }
     }
 
-    pub fn with_heap(&self, env: &Env) -> Tag {
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
         let image: &[[u8; 8]] = &[self.arity.as_slice(), self.form.as_slice()];
-        let mut heap_ref = block_on(env.heap.write());
         let ha = HeapRequest {
             env,
             image,
@@ -73,16 +72,18 @@ impl Async {
             type_id: Type::Async as u8,
         };
 
-        match heap_ref.alloc(&ha) {
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
             Some(image_id) => {
                 let ind = IndirectTag::new()
                     .with_image_id(image_id as u64)
-                    .with_heap_id(1)
+                    .with_heap_id(Heap::PRIVATE_ID)
                     .with_tag(TagType::Async);
 
-                Tag::Indirect(ind)
+                Ok(Tag::Indirect(ind))
             }
-            None => panic!(),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "async")),
         }
     }
 
@@ -100,17 +101,41 @@ impl Async {
         heap_ref.write_image(slices, offset);
     }
 
-    pub fn view(env: &Env, func: Tag) -> Tag {
+    pub fn view(env: &Env, func: Tag) -> exception::Result<Tag> {
         let (arity, form) = Self::destruct(env, func);
         let vec = vec![arity, form];
 
         Vector::from(vec).with_heap(env)
     }
 
+    // a closure's form is a :closure struct of the async function it
+    // runs and the values it captured, see Function::closure
+    pub fn closure(env: &Env, func: Tag, captured: Vec<Tag>) -> exception::Result<Tag> {
+        let arity = Self::destruct(env, func).0;
+        let mut vec = vec![func];
+
+        vec.extend(captured);
+
+        Self::new(arity, Struct::new(env, "closure", vec)?.with_heap(env)?).with_heap(env)
+    }
+
+    pub fn closure_destruct(env: &Env, func: Tag) -> Option<(Tag, Vec<Tag>)> {
+        let form = Self::destruct(env, func).1;
+
+        if form.type_of() != Type::Struct {
+            return None;
+        }
+
+        let mut vec = Vector::iter(env, Struct::destruct(env, form).1);
+        let func = vec.next().unwrap();
+
+        Some((func, vec.collect()))
+    }
+
     pub fn image_size(env: &Env, func: Tag) -> usize {
         let form = Self::destruct(env, func).1;
         match form.type_of() {
-            Type::Null | Type::Cons | Type::Vector => std::mem::size_of::<Async>(),
+            Type::Null | Type::Cons | Type::Vector | Type::Struct => std::mem::size_of::<Async>(),
             Type::Symbol => std::mem::size_of::<Fixnum>() + Symbol::image_size(env, form),
             _ => panic!(),
         }
@@ -136,6 +161,11 @@ impl Async {
                 ),
                 _ => panic!(),
             },
+            Type::Struct => (
+                "null".to_string(),
+                "closure".to_string(),
+                format!("{:x}", form.as_u64()),
+            ),
             _ => panic!(),
         };
 
@@ -154,7 +184,5 @@ impl Async {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn as_tag() {
-        assert_eq!(true, true)
-    }
+    fn as_tag() {}
 }
@@ -0,0 +1,623 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// bignum type
+//
+// integers past the fixnum range. a bignum image is a fixnum holding the
+// signed number of 32 bit digits, followed by the digits of the
+// magnitude, least significant first. results are normalized, an
+// integer that fits in a fixnum is always a fixnum.
+#![allow(clippy::cast_possible_truncation)]
+#![allow(clippy::cast_possible_wrap)]
+#![allow(clippy::cast_sign_loss)]
+use {
+    crate::{
+        core::{
+            env::Env,
+            exception::{self, Condition, Exception},
+            indirect::{ExtTag, IndirectTag},
+            tag::{Tag, TagType},
+            type_::Type,
+        },
+        namespaces::heap::{Heap, HeapRequest},
+        streams::writer::StreamWriter,
+        types::{fixnum::Fixnum, vector::Vector},
+    },
+    futures_lite::future::block_on,
+    std::cmp::Ordering,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bignum {
+    pub negative: bool,
+    pub digits: Vec<u32>, // magnitude, no high zero digits
+}
+
+impl Bignum {
+    // largest magnitude in digits that fits in a heap image
+    const MAX_DIGITS: usize = (Heap::IMAGE_MAX - 16) / 4;
+
+    pub fn from_i128(i128_: i128) -> Self {
+        let mut mag = i128_.unsigned_abs();
+        let mut digits = Vec::new();
+
+        while mag != 0 {
+            digits.push(mag as u32);
+            mag >>= 32;
+        }
+
+        Bignum {
+            negative: i128_ < 0,
+            digits,
+        }
+    }
+
+    // fixnum or bignum
+    pub fn from_tag(env: &Env, tag: Tag) -> Self {
+        match tag.type_of() {
+            Type::Fixnum => Self::from_i128(i128::from(Fixnum::as_i64(tag))),
+            Type::Bignum => Self::to_image(env, tag),
+            _ => panic!(),
+        }
+    }
+
+    pub fn to_image(env: &Env, tag: Tag) -> Self {
+        assert_eq!(tag.type_of(), Type::Bignum);
+
+        match tag {
+            Tag::Indirect(image) => {
+                let heap_ref = Heap::image_heap(env, image);
+                let index = usize::try_from(image.image_id()).unwrap();
+                let ndigits = Fixnum::as_i64(Tag::from_slice(heap_ref.image_slice(index).unwrap()));
+                let len = usize::try_from(ndigits.abs()).unwrap();
+                let data = heap_ref.image_data_slice(index + 1, 0, len * 4).unwrap();
+
+                Bignum {
+                    negative: ndigits < 0,
+                    digits: data
+                        .chunks(4)
+                        .map(|digit| u32::from_le_bytes(digit.try_into().unwrap()))
+                        .collect(),
+                }
+            }
+            Tag::Direct(_) => panic!(),
+        }
+    }
+
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
+        let ndigits = i64::try_from(self.digits.len()).unwrap();
+        let image: &[[u8; 8]] =
+            &[
+                Fixnum::with_i64_or_panic(if self.negative { -ndigits } else { ndigits })
+                    .as_slice(),
+            ];
+        let vdata = self
+            .digits
+            .iter()
+            .flat_map(|digit| digit.to_le_bytes())
+            .collect::<Vec<u8>>();
+
+        let ha = HeapRequest {
+            env,
+            image,
+            vdata: Some(&vdata),
+            type_id: Type::Bignum as u8,
+        };
+
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
+            Some(image_id) => Ok(Tag::Indirect(
+                IndirectTag::new()
+                    .with_image_id(image_id as u64)
+                    .with_heap_id(Heap::PRIVATE_ID)
+                    .with_ext(ExtTag::Bignum)
+                    .with_tag(TagType::Ext),
+            )),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "bignum")),
+        }
+    }
+
+    // the integer tag for a value, raises :over past the heap image limit
+    pub fn integer(self, env: &Env, source: &str) -> exception::Result<Tag> {
+        let bignum = self.normalize();
+
+        if bignum.digits.len() > Self::MAX_DIGITS {
+            return Err(Exception::err(env, Tag::nil(), Condition::Over, source));
+        }
+
+        Ok(match bignum.to_i64() {
+            Some(i64_) if Fixnum::is_i56(i64_) => Fixnum::with_i64_or_panic(i64_),
+            _ => bignum.with_heap(env)?,
+        })
+    }
+
+    pub fn with_i128(env: &Env, i128_: i128) -> exception::Result<Tag> {
+        match i64::try_from(i128_) {
+            Ok(i64_) if Fixnum::is_i56(i64_) => Ok(Fixnum::with_i64_or_panic(i64_)),
+            _ => Self::from_i128(i128_).with_heap(env),
+        }
+    }
+
+    pub fn image_size(env: &Env, tag: Tag) -> usize {
+        8 + 4 * Self::to_image(env, tag).digits.len()
+    }
+
+    pub fn write(env: &Env, tag: Tag, _escape: bool, stream: Tag) -> exception::Result<()> {
+        StreamWriter::write_str(env, &Self::to_image(env, tag).to_string(), stream)
+    }
+
+    pub fn view(env: &Env, tag: Tag) -> exception::Result<Tag> {
+        Vector::from(vec![tag]).with_heap(env)
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+        }
+
+        if self.digits.is_empty() {
+            self.negative = false;
+        }
+
+        self
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    fn to_i64(&self) -> Option<i64> {
+        if self.digits.len() > 2 {
+            return None;
+        }
+
+        let mag = self
+            .digits
+            .iter()
+            .rev()
+            .fold(0_i128, |mag, digit| (mag << 32) | i128::from(*digit));
+
+        i64::try_from(if self.negative { -mag } else { mag }).ok()
+    }
+
+    fn with_sign(digits: Vec<u32>, negative: bool) -> Self {
+        Bignum { negative, digits }.normalize()
+    }
+
+    fn negate(self) -> Self {
+        Self::with_sign(self.digits, !self.negative)
+    }
+
+    // magnitudes
+    fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+        a.len()
+            .cmp(&b.len())
+            .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+    }
+
+    fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        let mut sum = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0_u64;
+
+        for (nth, digit) in long.iter().enumerate() {
+            let total = u64::from(*digit) + u64::from(*short.get(nth).unwrap_or(&0)) + carry;
+
+            sum.push(total as u32);
+            carry = total >> 32;
+        }
+
+        if carry != 0 {
+            sum.push(carry as u32);
+        }
+
+        sum
+    }
+
+    // a - b, a >= b
+    fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut diff = Vec::with_capacity(a.len());
+        let mut borrow = 0_i64;
+
+        for (nth, digit) in a.iter().enumerate() {
+            let total = i64::from(*digit) - i64::from(*b.get(nth).unwrap_or(&0)) - borrow;
+
+            diff.push(total as u32);
+            borrow = i64::from(total < 0);
+        }
+
+        diff
+    }
+
+    fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+        let mut prod = vec![0_u32; a.len() + b.len()];
+
+        for (i, x) in a.iter().enumerate() {
+            let mut carry = 0_u64;
+
+            for (j, y) in b.iter().enumerate() {
+                let total = u64::from(*x) * u64::from(*y) + u64::from(prod[i + j]) + carry;
+
+                prod[i + j] = total as u32;
+                carry = total >> 32;
+            }
+
+            prod[i + b.len()] = carry as u32;
+        }
+
+        prod
+    }
+
+    fn shl_mag(a: &[u32], bits: usize) -> Vec<u32> {
+        let (words, bits) = (bits / 32, bits % 32);
+        let mut shifted = vec![0_u32; words];
+        let mut carry = 0_u32;
+
+        for digit in a {
+            if bits == 0 {
+                shifted.push(*digit);
+            } else {
+                shifted.push((digit << bits) | carry);
+                carry = digit >> (32 - bits);
+            }
+        }
+
+        if carry != 0 {
+            shifted.push(carry);
+        }
+
+        shifted
+    }
+
+    fn shr_mag(a: &[u32], bits: usize) -> Vec<u32> {
+        let (words, bits) = (bits / 32, bits % 32);
+
+        if words >= a.len() {
+            return Vec::new();
+        }
+
+        let a = &a[words..];
+
+        (0..a.len())
+            .map(|nth| {
+                if bits == 0 {
+                    a[nth]
+                } else {
+                    (a[nth] >> bits) | a.get(nth + 1).map_or(0, |high| high << (32 - bits))
+                }
+            })
+            .collect()
+    }
+
+    // quotient and remainder of magnitudes, Knuth's algorithm D
+    fn divrem_mag(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+        if Self::cmp_mag(u, v) == Ordering::Less {
+            return (Vec::new(), u.to_vec());
+        }
+
+        if v.len() == 1 {
+            let divisor = u64::from(v[0]);
+            let mut quot = vec![0_u32; u.len()];
+            let mut rem = 0_u64;
+
+            for nth in (0..u.len()).rev() {
+                let total = (rem << 32) | u64::from(u[nth]);
+
+                quot[nth] = (total / divisor) as u32;
+                rem = total % divisor;
+            }
+
+            return (quot, vec![rem as u32]);
+        }
+
+        let shift = v.last().unwrap().leading_zeros() as usize;
+        let v = Self::shl_mag(v, shift);
+        let mut u = Self::shl_mag(u, shift);
+        let n = v.len();
+
+        u.resize(u.len().max(n) + 1, 0);
+
+        let m = u.len() - n - 1;
+        let mut quot = vec![0_u32; m + 1];
+        let base = 1_u64 << 32;
+
+        for j in (0..=m).rev() {
+            let num = (u64::from(u[j + n]) << 32) | u64::from(u[j + n - 1]);
+            let mut qhat = num / u64::from(v[n - 1]);
+            let mut rhat = num % u64::from(v[n - 1]);
+
+            while qhat >= base
+                || qhat * u64::from(v[n - 2]) > ((rhat << 32) | u64::from(u[j + n - 2]))
+            {
+                qhat -= 1;
+                rhat += u64::from(v[n - 1]);
+                if rhat >= base {
+                    break;
+                }
+            }
+
+            let mut borrow = 0_i64;
+            let mut carry = 0_u64;
+
+            for i in 0..n {
+                let prod = qhat * u64::from(v[i]) + carry;
+                let total = i64::from(u[i + j]) - borrow - (prod & 0xffff_ffff) as i64;
+
+                carry = prod >> 32;
+                u[i + j] = total as u32;
+                borrow = i64::from(total < 0);
+            }
+
+            let total = i64::from(u[j + n]) - borrow - carry as i64;
+
+            u[j + n] = total as u32;
+            quot[j] = qhat as u32;
+
+            if total < 0 {
+                let mut carry = 0_u64;
+
+                quot[j] = quot[j].wrapping_sub(1);
+                for i in 0..n {
+                    let total = u64::from(u[i + j]) + u64::from(v[i]) + carry;
+
+                    u[i + j] = total as u32;
+                    carry = total >> 32;
+                }
+                u[j + n] = u[j + n].wrapping_add(carry as u32);
+            }
+        }
+
+        (quot, Self::shr_mag(&u[..n], shift))
+    }
+
+    // signed arithmetic
+    pub fn add(&self, other: &Self) -> Self {
+        if self.negative == other.negative {
+            return Self::with_sign(Self::add_mag(&self.digits, &other.digits), self.negative);
+        }
+
+        match Self::cmp_mag(&self.digits, &other.digits) {
+            Ordering::Less => {
+                Self::with_sign(Self::sub_mag(&other.digits, &self.digits), other.negative)
+            }
+            _ => Self::with_sign(Self::sub_mag(&self.digits, &other.digits), self.negative),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        self.add(&other.clone().negate())
+    }
+
+    pub fn mul(&self, other: &Self) -> Self {
+        Self::with_sign(
+            Self::mul_mag(&self.digits, &other.digits),
+            self.negative != other.negative,
+        )
+    }
+
+    // truncating division, the divisor is not zero
+    pub fn div(&self, other: &Self) -> Self {
+        let (quot, _) = Self::divrem_mag(&self.digits, &other.digits);
+
+        Self::with_sign(quot, self.negative != other.negative)
+    }
+
+    // truncating remainder, the sign of the dividend
+    #[cfg(feature = "math")]
+    pub fn rem(&self, other: &Self) -> Self {
+        let (_, rem) = Self::divrem_mag(&self.digits, &other.digits);
+
+        Self::with_sign(rem, self.negative)
+    }
+
+    // nearest double, inf past the double range
+    #[cfg(feature = "math")]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_f64(&self) -> f64 {
+        let bits = self.digits.len() * 32;
+
+        let mag = if bits <= 128 {
+            self.digits
+                .iter()
+                .rev()
+                .fold(0_u128, |mag, digit| (mag << 32) | u128::from(*digit)) as f64
+        } else {
+            // the high 128 bits, with a sticky bit for the rest, round once
+            let shift = bits - 128;
+            let high = Self::shr_mag(&self.digits, shift);
+            let sticky = Self::with_sign(Self::shl_mag(&high, shift), false).digits != self.digits;
+            let high = high
+                .iter()
+                .rev()
+                .fold(0_u128, |mag, digit| (mag << 32) | u128::from(*digit));
+
+            (high | u128::from(sticky)) as f64
+                * 2.0_f64.powi(i32::try_from(shift).unwrap_or(i32::MAX))
+        };
+
+        if self.negative {
+            -mag
+        } else {
+            mag
+        }
+    }
+
+    // an integral, finite double
+    #[cfg(feature = "math")]
+    pub fn from_f64(fl: f64) -> Self {
+        assert!(fl.is_finite() && fl.fract() == 0.0);
+
+        let bits = fl.to_bits();
+        let exponent = i64::try_from((bits >> 52) & 0x7ff).unwrap();
+        let mantissa = bits & ((1 << 52) - 1);
+
+        let (mantissa, exponent) = if exponent == 0 {
+            (mantissa, -1074)
+        } else {
+            (mantissa | (1 << 52), exponent - 1075)
+        };
+
+        let mag = Self::from_i128(i128::from(mantissa)).ash(exponent);
+
+        Self::with_sign(mag.digits, fl.is_sign_negative())
+    }
+
+    pub fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => Self::cmp_mag(&self.digits, &other.digits),
+            (true, true) => Self::cmp_mag(&other.digits, &self.digits),
+        }
+    }
+
+    // arithmetic shift, right shifts round toward negative infinity
+    pub fn ash(&self, shift: i64) -> Self {
+        let bits = usize::try_from(shift.unsigned_abs()).unwrap_or(usize::MAX);
+
+        if shift >= 0 {
+            Self::with_sign(Self::shl_mag(&self.digits, bits), self.negative)
+        } else if self.negative {
+            let mag = Self::sub_mag(&self.digits, &[1]);
+            let shifted = Self::shr_mag(&Self::with_sign(mag, false).digits, bits);
+
+            Self::with_sign(Self::add_mag(&shifted, &[1]), true)
+        } else {
+            Self::with_sign(Self::shr_mag(&self.digits, bits), false)
+        }
+    }
+
+    // two's complement logical operations
+    fn twos(&self, len: usize) -> Vec<u32> {
+        let mut digits = self.digits.clone();
+
+        digits.resize(len, 0);
+        if self.negative {
+            let mut carry = 1_u64;
+
+            for digit in &mut digits {
+                let total = u64::from(!*digit) + carry;
+
+                *digit = total as u32;
+                carry = total >> 32;
+            }
+        }
+
+        digits
+    }
+
+    fn from_twos(mut digits: Vec<u32>) -> Self {
+        let negative = digits.last().is_some_and(|digit| digit & 0x8000_0000 != 0);
+
+        if negative {
+            let mut carry = 1_u64;
+
+            for digit in &mut digits {
+                let total = u64::from(!*digit) + carry;
+
+                *digit = total as u32;
+                carry = total >> 32;
+            }
+        }
+
+        Self::with_sign(digits, negative)
+    }
+
+    pub fn logand(&self, other: &Self) -> Self {
+        let len = self.digits.len().max(other.digits.len()) + 1;
+
+        Self::from_twos(
+            self.twos(len)
+                .iter()
+                .zip(other.twos(len))
+                .map(|(a, b)| a & b)
+                .collect(),
+        )
+    }
+
+    pub fn logor(&self, other: &Self) -> Self {
+        let len = self.digits.len().max(other.digits.len()) + 1;
+
+        Self::from_twos(
+            self.twos(len)
+                .iter()
+                .zip(other.twos(len))
+                .map(|(a, b)| a | b)
+                .collect(),
+        )
+    }
+
+    pub fn lognot(&self) -> Self {
+        self.clone().negate().sub(&Self::from_i128(1))
+    }
+
+    // an optionally signed string of digits in a radix
+    pub fn parse(str: &str, radix: u32) -> Option<Self> {
+        let (negative, digits) = match str.as_bytes().first()? {
+            b'-' => (true, &str[1..]),
+            b'+' => (false, &str[1..]),
+            _ => (false, str),
+        };
+
+        if digits.is_empty() {
+            return None;
+        }
+
+        let mut mag = Vec::new();
+
+        for ch in digits.chars() {
+            let mut carry = u64::from(ch.to_digit(radix)?);
+
+            for digit in &mut mag {
+                let total = u64::from(*digit) * u64::from(radix) + carry;
+
+                *digit = total as u32;
+                carry = total >> 32;
+            }
+
+            if carry != 0 {
+                mag.push(carry as u32);
+            }
+        }
+
+        Some(Self::with_sign(mag, negative))
+    }
+}
+
+impl std::fmt::Display for Bignum {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        let mut mag = self.digits.clone();
+        let mut chunks = Vec::new();
+
+        while !mag.is_empty() {
+            let (quot, rem) = Self::divrem_mag(&mag, &[CHUNK]);
+
+            chunks.push(rem.first().copied().unwrap_or(0));
+            mag = Self::with_sign(quot, false).digits;
+        }
+
+        if self.negative {
+            write!(f, "-")?;
+        }
+
+        write!(f, "{}", chunks.pop().unwrap())?;
+        for chunk in chunks.iter().rev() {
+            write!(f, "{chunk:09}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn bignum() {}
+}
@@ -26,12 +26,13 @@ impl From<char> for Tag {
 }
 
 impl Char {
+    // any unicode scalar value
     pub fn as_char(env: &Env, ch: Tag) -> char {
-        ((ch.data(env) & 0xff) as u8) as char
+        char::from_u32(u32::try_from(ch.data(env)).unwrap()).unwrap()
     }
 
     pub fn write(env: &Env, chr: Tag, escape: bool, stream: Tag) -> exception::Result<()> {
-        let ch: u8 = (chr.data(env) & 0xff) as u8;
+        let ch = Self::as_char(env, chr);
 
         if escape {
             StreamWriter::write_str(env, "#\\", stream)?;
@@ -39,23 +40,23 @@ impl Char {
             let mut tmp = [0; 4];
 
             let phrase = match ch {
-                0x20 => "space",
-                0x09 => "tab",
-                0x0a => "linefeed",
-                0x0c => "page",
-                0x0d => "return",
-                _ => (ch as char).encode_utf8(&mut tmp),
+                ' ' => "space",
+                '\t' => "tab",
+                '\n' => "linefeed",
+                '\x0c' => "page",
+                '\r' => "return",
+                _ => ch.encode_utf8(&mut tmp),
             };
 
             StreamWriter::write_str(env, phrase, stream)?;
         } else {
-            StreamWriter::write_char(env, stream, ch as char)?;
+            StreamWriter::write_char(env, stream, ch)?;
         }
 
         Ok(())
     }
 
-    pub fn view(env: &Env, chr: Tag) -> Tag {
+    pub fn view(env: &Env, chr: Tag) -> exception::Result<Tag> {
         Vector::from(vec![chr]).with_heap(env)
     }
 }
@@ -66,8 +67,6 @@ mod tests {
 
     #[test]
     fn as_tag() {
-        match <char as Into<Tag>>::into('a') {
-            _ => assert!(true),
-        }
+        let _ = <char as Into<Tag>>::into('a');
     }
 }
@@ -14,7 +14,7 @@ use {
             tag::{Tag, TagType},
             type_::Type,
         },
-        namespaces::heap::HeapRequest,
+        namespaces::heap::{Heap, HeapRequest},
         reader::read::{Reader, EOL},
         streams::writer::StreamWriter,
         types::{fixnum::Fixnum, symbol::Symbol, vector::Vector},
@@ -38,7 +38,7 @@ impl Cons {
 
         match tag {
             Tag::Indirect(cons) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, cons);
                 let slice = usize::try_from(cons.image_id()).unwrap();
 
                 Self::new(
@@ -55,7 +55,7 @@ impl Cons {
 
         match cons {
             Tag::Indirect(cons) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, cons);
                 let slice = usize::try_from(cons.image_id()).unwrap();
 
                 (
@@ -127,7 +127,7 @@ impl Cons {
         }
     }
 
-    pub fn view(env: &Env, cons: Tag) -> Tag {
+    pub fn view(env: &Env, cons: Tag) -> exception::Result<Tag> {
         let (car, cdr) = Self::destruct(env, cons);
 
         Vector::from(vec![car, cdr]).with_heap(env)
@@ -171,7 +171,7 @@ impl Cons {
                 Some(image_id) => {
                     let ind = IndirectTag::new()
                         .with_image_id(image_id as u64)
-                        .with_heap_id(1)
+                        .with_heap_id(Heap::PRIVATE_ID)
                         .with_tag(TagType::Cons);
 
                     Tag::Indirect(ind)
@@ -184,7 +184,7 @@ impl Cons {
     }
 
     pub fn read(env: &Env, stream: Tag) -> exception::Result<Tag> {
-        let dot = Vector::from(".").with_heap(env);
+        let dot = Vector::from(".").with_heap(env)?;
         let car = env.read(stream, false, Tag::nil(), true)?;
 
         if EOL.eq_(&car) {
@@ -494,8 +494,6 @@ mod tests {
 
     #[test]
     fn cons_test() {
-        match Cons::new(Tag::nil(), Tag::nil()) {
-            _ => assert!(true),
-        }
+        let _ = Cons::new(Tag::nil(), Tag::nil());
     }
 }
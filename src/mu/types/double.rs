@@ -0,0 +1,122 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// double type
+//
+// 64 bit IEEE floats. a double image is a single word holding the
+// bits of the float. doubles print with the shortest digits that
+// read back to the same value, and a d exponent marker.
+use {
+    crate::{
+        core::{
+            env::Env,
+            exception::{self, Condition, Exception},
+            indirect::{ExtTag, IndirectTag},
+            tag::{Tag, TagType},
+            type_::Type,
+        },
+        namespaces::heap::{Heap, HeapRequest},
+        streams::writer::StreamWriter,
+        types::{float::Float, vector::Vector},
+    },
+    futures_lite::future::block_on,
+};
+
+pub struct Double;
+
+impl Double {
+    pub fn as_f64(env: &Env, tag: Tag) -> f64 {
+        assert_eq!(tag.type_of(), Type::Double);
+
+        match tag {
+            Tag::Indirect(image) => {
+                let heap_ref = Heap::image_heap(env, image);
+                let index = usize::try_from(image.image_id()).unwrap();
+
+                f64::from_le_bytes(heap_ref.image_slice(index).unwrap().try_into().unwrap())
+            }
+            Tag::Direct(_) => panic!(),
+        }
+    }
+
+    // float or double
+    pub fn from_tag(env: &Env, tag: Tag) -> f64 {
+        match tag.type_of() {
+            Type::Float => f64::from(Float::as_f32(env, tag)),
+            Type::Double => Self::as_f64(env, tag),
+            _ => panic!(),
+        }
+    }
+
+    pub fn with_f64(env: &Env, fl: f64) -> exception::Result<Tag> {
+        let image: &[[u8; 8]] = &[fl.to_le_bytes()];
+
+        let ha = HeapRequest {
+            env,
+            image,
+            vdata: None,
+            type_id: Type::Double as u8,
+        };
+
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
+            Some(image_id) => Ok(Tag::Indirect(
+                IndirectTag::new()
+                    .with_image_id(image_id as u64)
+                    .with_heap_id(Heap::PRIVATE_ID)
+                    .with_ext(ExtTag::Double)
+                    .with_tag(TagType::Ext),
+            )),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "double")),
+        }
+    }
+
+    // [sign] digits [. digits] d [sign] digits
+    pub fn parse(token: &str) -> Option<f64> {
+        let (mantissa, exponent) = token.split_once(['d', 'D'])?;
+        let digits = mantissa.strip_prefix(['+', '-']).unwrap_or(mantissa);
+        let exp_digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+
+        if !digits.chars().any(|ch| ch.is_ascii_digit())
+            || !digits.chars().all(|ch| ch.is_ascii_digit() || ch == '.')
+            || exp_digits.is_empty()
+            || !exp_digits.chars().all(|ch| ch.is_ascii_digit())
+        {
+            return None;
+        }
+
+        format!("{mantissa}e{exponent}").parse::<f64>().ok()
+    }
+
+    // debug format is the shortest round-trip representation
+    pub fn as_string(fl: f64) -> String {
+        let digits = format!("{fl:?}");
+
+        if !fl.is_finite() {
+            digits
+        } else if digits.contains('e') {
+            digits.replace('e', "d")
+        } else {
+            format!("{digits}d0")
+        }
+    }
+
+    pub fn image_size(_env: &Env, _tag: Tag) -> usize {
+        8
+    }
+
+    pub fn write(env: &Env, tag: Tag, _escape: bool, stream: Tag) -> exception::Result<()> {
+        StreamWriter::write_str(env, &Self::as_string(Self::as_f64(env, tag)), stream)
+    }
+
+    pub fn view(env: &Env, tag: Tag) -> exception::Result<Tag> {
+        Vector::from(vec![tag]).with_heap(env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn double_test() {}
+}
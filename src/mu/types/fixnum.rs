@@ -13,9 +13,11 @@ use crate::{
         type_::Type,
     },
     streams::writer::StreamWriter,
-    types::{cons::Cons, symbol::Symbol, vector::Vector},
+    types::{bignum::Bignum, cons::Cons, symbol::Symbol, vector::Vector},
 };
 
+use std::cmp::Ordering;
+
 impl From<usize> for Tag {
     fn from(fx: usize) -> Tag {
         assert!(Fixnum::is_i56(i64::try_from(fx).unwrap()));
@@ -82,9 +84,7 @@ impl From<u8> for Tag {
 
 impl From<Tag> for i64 {
     fn from(tag: Tag) -> i64 {
-        let data: i64 = tag.into();
-
-        data >> 8
+        Fixnum::as_i64(tag)
     }
 }
 
@@ -118,6 +118,9 @@ impl Fixnum {
     const MAX: i64 = 2_i64.pow(55) - 1;
     const MIN: i64 = -(2_i64.pow(55));
 
+    // largest left shift of a bignum
+    const ASH_MAX: u64 = 1 << 28;
+
     // range checking
     pub fn is_i56(i56: i64) -> bool {
         (Self::MIN..=Self::MAX).contains(&i56)
@@ -134,6 +137,7 @@ impl Fixnum {
     }
 
     // specialized convert generic
+    #[cfg(feature = "system")]
     pub fn etry_from<T: Clone>(env: &Env, fx: T, source: &str) -> exception::Result<Tag>
     where
         i64: From<T>,
@@ -146,7 +150,7 @@ impl Fixnum {
         } else {
             Err(Exception::err(
                 env,
-                Vector::from(i64_.to_string()).with_heap(env),
+                Vector::from(i64_.to_string()).with_heap(env)?,
                 Condition::Over,
                 source,
             ))?
@@ -199,33 +203,16 @@ impl Fixnum {
         ))
     }
 
-    pub fn with_u64(env: &Env, fx: u64, source: &str) -> exception::Result<Tag> {
-        match i64::try_from(fx) {
-            Err(_) => Err(Exception::err(env, Tag::nil(), Condition::Over, "fixnum")),
-            Ok(i64_) => {
-                if !Fixnum::is_i56(i64_) {
-                    Err(Exception::err(
-                        env,
-                        Vector::from(i64_.to_string()).with_heap(env),
-                        Condition::Over,
-                        source,
-                    ))?;
-                }
-
-                Ok(DirectTag::to_tag(
-                    u64::try_from(i64::try_from(fx).unwrap() & (2_i64.pow(56) - 1)).unwrap(),
-                    DirectExt::ExtType(ExtType::Fixnum),
-                    DirectType::Ext,
-                ))
-            }
-        }
+    // values past the fixnum range are bignums
+    pub fn with_u64(env: &Env, fx: u64, _source: &str) -> exception::Result<Tag> {
+        Bignum::with_i128(env, i128::from(fx))
     }
 
     pub fn write(env: &Env, tag: Tag, _escape: bool, stream: Tag) -> exception::Result<()> {
         StreamWriter::write_str(env, &Self::as_i64(tag).to_string(), stream)
     }
 
-    pub fn view(env: &Env, fx: Tag) -> Tag {
+    pub fn view(env: &Env, fx: Tag) -> exception::Result<Tag> {
         Vector::from(vec![fx]).with_heap(env)
     }
 }
@@ -244,122 +231,134 @@ pub trait CoreFn {
 
 impl CoreFn for Fixnum {
     fn mu_ash(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:ash", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:ash", &[Type::Integer, Type::Fixnum], fp)?;
 
-        let value = Self::as_i64(fp.argv[0]);
+        let value = fp.argv[0];
         let shift = Self::as_i64(fp.argv[1]);
 
-        let result = if shift < 0 {
-            value >> shift.abs()
-        } else {
-            value << shift
-        };
+        fp.value = match value.type_of() {
+            Type::Fixnum if shift < 64 => {
+                let value = i128::from(Self::as_i64(value));
+
+                Bignum::with_i128(
+                    env,
+                    if shift < 0 {
+                        value >> shift.unsigned_abs().min(63)
+                    } else {
+                        value << shift
+                    },
+                )?
+            }
+            _ => {
+                let bignum = Bignum::from_tag(env, value);
 
-        if Self::is_i56(result) {
-            fp.value = Self::with_i64(env, result).unwrap();
-        } else {
-            Err(Exception::err(
-                env,
-                Cons::cons(env, fp.argv[0], fp.argv[1]),
-                Condition::Over,
-                "mu:ash",
-            ))?;
-        }
+                if shift > 0 && !bignum.is_zero() && shift.unsigned_abs() > Self::ASH_MAX {
+                    Err(Exception::err(
+                        env,
+                        Cons::cons(env, fp.argv[0], fp.argv[1]),
+                        Condition::Over,
+                        "mu:ash",
+                    ))?;
+                }
+
+                bignum.ash(shift).integer(env, "mu:ash")?
+            }
+        };
 
         Ok(())
     }
 
     fn mu_fxadd(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:add", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:add", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = match Self::as_i64(fx0).checked_add(Self::as_i64(fx1)) {
-            Some(sum) => {
-                if Self::is_i56(sum) {
-                    Self::with_i64(env, sum).unwrap()
-                } else {
-                    Err(Exception::err(env, fx0, Condition::Over, "mu:add"))?
-                }
-            }
-            None => Err(Exception::err(env, fx1, Condition::Over, "mu:add"))?,
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => Bignum::with_i128(
+                env,
+                i128::from(Self::as_i64(fx0)) + i128::from(Self::as_i64(fx1)),
+            )?,
+            _ => Bignum::from_tag(env, fx0)
+                .add(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:add")?,
         };
 
         Ok(())
     }
 
     fn mu_fxsub(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:sub", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:sub", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = match Self::as_i64(fx0).checked_sub(Self::as_i64(fx1)) {
-            Some(diff) => {
-                if Self::is_i56(diff) {
-                    Self::with_i64(env, diff).unwrap()
-                } else {
-                    Err(Exception::err(env, fx0, Condition::Over, "mu:sub"))?
-                }
-            }
-            None => Err(Exception::err(env, fx1, Condition::Over, "mu:sub"))?,
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => Bignum::with_i128(
+                env,
+                i128::from(Self::as_i64(fx0)) - i128::from(Self::as_i64(fx1)),
+            )?,
+            _ => Bignum::from_tag(env, fx0)
+                .sub(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:sub")?,
         };
 
         Ok(())
     }
 
     fn mu_fxmul(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:mul", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:mul", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = match Self::as_i64(fx0).checked_mul(Self::as_i64(fx1)) {
-            Some(prod) => {
-                if Self::is_i56(prod) {
-                    Self::with_i64(env, prod).unwrap()
-                } else {
-                    Err(Exception::err(env, fx1, Condition::Over, "mu:mul"))?
-                }
-            }
-            None => Err(Exception::err(env, fx1, Condition::Over, "mu:mul"))?,
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => Bignum::with_i128(
+                env,
+                i128::from(Self::as_i64(fx0)) * i128::from(Self::as_i64(fx1)),
+            )?,
+            _ => Bignum::from_tag(env, fx0)
+                .mul(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:mul")?,
         };
 
         Ok(())
     }
 
     fn mu_fxdiv(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:div", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:div", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        if Self::as_i64(fx1) == 0 {
-            Err(Exception::err(env, fx0, Condition::ZeroDivide, "mu:div"))?;
-        }
-
-        fp.value = match Self::as_i64(fx0).checked_div(Self::as_i64(fx1)) {
-            Some(div) => {
-                if Self::is_i56(div) {
-                    Self::with_i64(env, div).unwrap()
-                } else {
-                    Err(Exception::err(env, fx1, Condition::Over, "mu:div"))?
-                }
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (_, Type::Fixnum) if Self::as_i64(fx1) == 0 => {
+                Err(Exception::err(env, fx0, Condition::ZeroDivide, "mu:div"))?
             }
-            None => Err(Exception::err(env, fx1, Condition::Over, "mu:div"))?,
+            (Type::Fixnum, Type::Fixnum) => Bignum::with_i128(
+                env,
+                i128::from(Self::as_i64(fx0)) / i128::from(Self::as_i64(fx1)),
+            )?,
+            _ => Bignum::from_tag(env, fx0)
+                .div(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:div")?,
         };
 
         Ok(())
     }
 
     fn mu_fxlt(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:less-than", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:less-than", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = if Self::as_i64(fx0) < Self::as_i64(fx1) {
+        let less = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => Self::as_i64(fx0) < Self::as_i64(fx1),
+            _ => Bignum::from_tag(env, fx0).cmp(&Bignum::from_tag(env, fx1)) == Ordering::Less,
+        };
+
+        fp.value = if less {
             Symbol::keyword("t")
         } else {
             Tag::nil()
@@ -369,44 +368,52 @@ impl CoreFn for Fixnum {
     }
 
     fn mu_logand(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:logand", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:logand", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = Self::with_i64(env, Self::as_i64(fx0) & Self::as_i64(fx1))?;
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => {
+                Self::with_i64(env, Self::as_i64(fx0) & Self::as_i64(fx1))?
+            }
+            _ => Bignum::from_tag(env, fx0)
+                .logand(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:logand")?,
+        };
 
         Ok(())
     }
 
     fn mu_logor(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:logor", &[Type::Fixnum, Type::Fixnum], fp)?;
+        env.argv_check("mu:logor", &[Type::Integer, Type::Integer], fp)?;
 
         let fx0 = fp.argv[0];
         let fx1 = fp.argv[1];
 
-        fp.value = Self::with_i64(env, Self::as_i64(fx0) | Self::as_i64(fx1))?;
+        fp.value = match (fx0.type_of(), fx1.type_of()) {
+            (Type::Fixnum, Type::Fixnum) => {
+                Self::with_i64(env, Self::as_i64(fx0) | Self::as_i64(fx1))?
+            }
+            _ => Bignum::from_tag(env, fx0)
+                .logor(&Bignum::from_tag(env, fx1))
+                .integer(env, "mu:logor")?,
+        };
 
         Ok(())
     }
 
     fn mu_lognot(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:lognot", &[Type::Fixnum], fp)?;
+        env.argv_check("mu:lognot", &[Type::Integer], fp)?;
 
         let fx = fp.argv[0];
-        let mut val = Self::as_i64(fx);
 
-        for nth_bit in 0..64 {
-            let mask = 1 << nth_bit;
-
-            if val & mask == 0 {
-                val |= mask;
-            } else {
-                val &= !mask;
-            }
-        }
-
-        fp.value = Self::with_i64(env, val)?;
+        fp.value = match fx.type_of() {
+            Type::Fixnum => Self::with_i64(env, !Self::as_i64(fx))?,
+            _ => Bignum::from_tag(env, fx)
+                .lognot()
+                .integer(env, "mu:lognot")?,
+        };
 
         Ok(())
     }
@@ -415,7 +422,5 @@ impl CoreFn for Fixnum {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn as_tag() {
-        assert_eq!(true, true)
-    }
+    fn as_tag() {}
 }
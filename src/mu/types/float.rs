@@ -14,7 +14,7 @@ use {
             type_::Type,
         },
         streams::writer::StreamWriter,
-        types::{symbol::Symbol, vector::Vector},
+        types::{double::Double, symbol::Symbol, vector::Vector},
     },
     std::ops::{Add, Div, Mul, Sub},
 };
@@ -45,7 +45,7 @@ impl Float {
         f32::from_le_bytes(fl)
     }
 
-    pub fn view(env: &Env, fl: Tag) -> Tag {
+    pub fn view(env: &Env, fl: Tag) -> exception::Result<Tag> {
         Vector::from(vec![fl]).with_heap(env)
     }
 
@@ -56,6 +56,37 @@ impl Float {
             stream,
         )
     }
+
+    // a float result if both operands are floats, otherwise a double
+    fn promote(
+        env: &Env,
+        fp: &Frame,
+        op32: fn(f32, f32) -> f32,
+        op64: fn(f64, f64) -> f64,
+        condition: Condition,
+        source: &str,
+    ) -> exception::Result<Tag> {
+        let fl0 = fp.argv[0];
+        let fl1 = fp.argv[1];
+
+        if fl0.type_of() == Type::Float && fl1.type_of() == Type::Float {
+            let result = op32(Self::as_f32(env, fl0), Self::as_f32(env, fl1));
+
+            if result.is_nan() {
+                Err(Exception::err(env, fl1, condition, source))?;
+            }
+
+            Ok(result.into())
+        } else {
+            let result = op64(Double::from_tag(env, fl0), Double::from_tag(env, fl1));
+
+            if result.is_nan() {
+                Err(Exception::err(env, fl1, condition, source))?;
+            }
+
+            Double::with_f64(env, result)
+        }
+    }
 }
 
 pub trait CoreFn {
@@ -68,81 +99,50 @@ pub trait CoreFn {
 
 impl CoreFn for Float {
     fn mu_fladd(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:fl-add", &[Type::Float, Type::Float], fp)?;
-
-        let fl0 = fp.argv[0];
-        let fl1 = fp.argv[1];
+        env.argv_check("mu:fl-add", &[Type::Flonum, Type::Flonum], fp)?;
 
-        let sum = Self::as_f32(env, fl0).add(Self::as_f32(env, fl1));
-        fp.value = if sum.is_nan() {
-            Err(Exception::err(env, fl1, Condition::Over, "mu:fl-add"))?
-        } else {
-            sum.into()
-        };
+        fp.value = Self::promote(env, fp, f32::add, f64::add, Condition::Over, "mu:fl-add")?;
 
         Ok(())
     }
 
     fn mu_flsub(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:fl-sub", &[Type::Float, Type::Float], fp)?;
+        env.argv_check("mu:fl-sub", &[Type::Flonum, Type::Flonum], fp)?;
 
-        let fl0 = fp.argv[0];
-        let fl1 = fp.argv[1];
-
-        let diff = Self::as_f32(env, fl0).sub(Self::as_f32(env, fl1));
-        fp.value = if diff.is_nan() {
-            Err(Exception::err(env, fl1, Condition::Under, "mu:fl-sub"))?
-        } else {
-            diff.into()
-        };
+        fp.value = Self::promote(env, fp, f32::sub, f64::sub, Condition::Under, "mu:fl-sub")?;
 
         Ok(())
     }
 
     fn mu_flmul(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:fl-mul", &[Type::Float, Type::Float], fp)?;
-
-        let fl0 = fp.argv[0];
-        let fl1 = fp.argv[1];
+        env.argv_check("mu:fl-mul", &[Type::Flonum, Type::Flonum], fp)?;
 
-        let prod = Self::as_f32(env, fl0).mul(Self::as_f32(env, fl1));
-
-        fp.value = if prod.is_nan() {
-            Err(Exception::err(env, fl1, Condition::Over, "mu:fl-mul"))?
-        } else {
-            prod.into()
-        };
+        fp.value = Self::promote(env, fp, f32::mul, f64::mul, Condition::Over, "mu:fl-mul")?;
 
         Ok(())
     }
 
     fn mu_fldiv(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:fl-div", &[Type::Float, Type::Float], fp)?;
+        env.argv_check("mu:fl-div", &[Type::Flonum, Type::Flonum], fp)?;
 
-        let fl0 = fp.argv[0];
         let fl1 = fp.argv[1];
 
-        if Self::as_f32(env, fl1) == 0.0 {
+        if Double::from_tag(env, fl1) == 0.0 {
             Err(Exception::err(env, fl1, Condition::ZeroDivide, "fl-div"))?;
         }
 
-        let div = Self::as_f32(env, fl0).div(Self::as_f32(env, fl1));
-        fp.value = if div.is_nan() {
-            Err(Exception::err(env, fl1, Condition::Under, "mu:fl-div"))?
-        } else {
-            div.into()
-        };
+        fp.value = Self::promote(env, fp, f32::div, f64::div, Condition::Under, "mu:fl-div")?;
 
         Ok(())
     }
 
     fn mu_fllt(env: &Env, fp: &mut Frame) -> exception::Result<()> {
-        env.argv_check("mu:fl-lt", &[Type::Float, Type::Float], fp)?;
+        env.argv_check("mu:fl-lt", &[Type::Flonum, Type::Flonum], fp)?;
 
         let fl0 = fp.argv[0];
         let fl1 = fp.argv[1];
 
-        fp.value = if Self::as_f32(env, fl0) < Self::as_f32(env, fl1) {
+        fp.value = if Double::from_tag(env, fl0) < Double::from_tag(env, fl1) {
             Symbol::keyword("t")
         } else {
             Tag::nil()
@@ -158,8 +158,6 @@ mod tests {
 
     #[test]
     fn float_test() {
-        match <f32 as Into<Tag>>::into(1.0_f32) {
-            _ => assert_eq!(true, true),
-        }
+        let _ = <f32 as Into<Tag>>::into(1.0_f32);
     }
 }
@@ -9,18 +9,19 @@ use {
             core_::Core,
             direct::DirectTag,
             env::Env,
-            exception,
+            exception::{self, Condition, Exception},
             indirect::IndirectTag,
             tag::{Tag, TagType},
             type_::Type,
         },
         namespaces::{
-            heap::HeapRequest,
+            heap::{Heap, HeapRequest},
         },
         streams::writer::StreamWriter,
         types::{
             cons::Cons,
             fixnum::Fixnum,
+            struct_::Struct,
             symbol::Symbol,
             vector::Vector
         },
@@ -31,7 +32,7 @@ use {
 #[derive(Copy, Clone)]
 pub struct Function {
     pub arity: Tag, // number of required arguments
-    pub form: Tag,  // list
+    pub form: Tag,  // list, or bytecode vector
 }
 
 impl Function {
@@ -44,7 +45,7 @@ impl Function {
 
         match tag {
             Tag::Indirect(fn_) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, fn_);
                 let slice = usize::try_from(fn_.image_id()).unwrap();
 
                 Self::new(
@@ -73,7 +74,7 @@ impl Function {
                 (arity, index)
             }
             Tag::Indirect(fn_) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, fn_);
                 let slice = usize::try_from(fn_.image_id()).unwrap();
 
                 (
@@ -84,9 +85,8 @@ impl Function {
         }
     }
 
-    pub fn with_heap(&self, env: &Env) -> Tag {
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
         let image: &[[u8; 8]] = &[self.arity.as_slice(), self.form.as_slice()];
-        let mut heap_ref = block_on(env.heap.write());
         let type_id = Type::Function as u8;
 
         let ha = HeapRequest {
@@ -96,16 +96,18 @@ impl Function {
             type_id,
         };
 
-        match heap_ref.alloc(&ha) {
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
             Some(image_id) => {
                 let ind = IndirectTag::new()
                     .with_image_id(image_id as u64)
-                    .with_heap_id(1)
+                    .with_heap_id(Heap::PRIVATE_ID)
                     .with_tag(TagType::Function);
 
-                Tag::Indirect(ind)
+                Ok(Tag::Indirect(ind))
             }
-            None => panic!(),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "function")),
         }
     }
 
@@ -121,16 +123,42 @@ impl Function {
         }
     }
 
-    pub fn view(env: &Env, func: Tag) -> Tag {
+    pub fn view(env: &Env, func: Tag) -> exception::Result<Tag> {
         let (arity, form) = Self::destruct(env, func);
         let vec = vec![arity, form];
 
         Vector::from(vec).with_heap(env)
     }
 
+    // a closure's form is a :closure struct of the function it applies
+    // and the values it captured
+    pub fn closure(env: &Env, func: Tag, captured: Vec<Tag>) -> exception::Result<Tag> {
+        let arity = Self::destruct(env, func).0;
+        let mut vec = vec![func];
+
+        vec.extend(captured);
+
+        Self::new(arity, Struct::new(env, "closure", vec)?.with_heap(env)?).with_heap(env)
+    }
+
+    pub fn closure_destruct(env: &Env, func: Tag) -> Option<(Tag, Vec<Tag>)> {
+        let form = Self::destruct(env, func).1;
+
+        if form.type_of() != Type::Struct {
+            return None;
+        }
+
+        let mut vec = Vector::iter(env, Struct::destruct(env, form).1);
+        let func = vec.next().unwrap();
+
+        Some((func, vec.collect()))
+    }
+
     pub fn image_size(env: &Env, func: Tag) -> usize {
         match Function::destruct(env, func).1.type_of() {
-            Type::Null | Type::Cons | Type::Vector => std::mem::size_of::<Function>(),
+            Type::Null | Type::Cons | Type::Vector | Type::Struct => {
+                std::mem::size_of::<Function>()
+            }
             Type::Symbol => {
                 std::mem::size_of::<Fixnum>() + Symbol::image_size(env, Self::destruct(env, func).1)
             }
@@ -156,6 +184,16 @@ impl Function {
                         usize::try_from(Fixnum::as_i64(arity)).unwrap(),
                         "()".to_string(),
                     ),
+                    Type::Struct => (
+                        "closure".to_string(),
+                        usize::try_from(Fixnum::as_i64(arity)).unwrap(),
+                        format!("#x{:x}", form.as_u64()),
+                    ),
+                    Type::Vector => (
+                        "lambda".to_string(),
+                        usize::try_from(Fixnum::as_i64(arity)).unwrap(),
+                        format!("#x{:x}", form.as_u64()),
+                    ),
                     Type::Cons => match Cons::destruct(env, form).1.type_of() {
                         Type::Null | Type::Cons => (
                             "lambda".to_string(),
@@ -163,12 +201,15 @@ impl Function {
                             format!("#x{:x}", form.as_u64()),
                         ),
                         Type::Async
+                        | Type::Bignum
                         | Type::Bit
                         | Type::Byte
                         | Type::Char
+                        | Type::Double
                         | Type::Fixnum
                         | Type::Float
                         | Type::Function
+                        | Type::Hash
                         | Type::Keyword
                         | Type::Stream
                         | Type::Struct
@@ -176,23 +217,30 @@ impl Function {
                         | Type::Vector
                         | Type::T
                         | Type::List
-                        | Type::String => panic!(),
+                        | Type::String
+                        | Type::Integer
+                        | Type::Flonum
+                        | Type::Number => panic!(),
                     },
                     Type::Async
+                    | Type::Bignum
                     | Type::Bit
                     | Type::Byte
                     | Type::Char
+                    | Type::Double
                     | Type::Fixnum
                     | Type::Float
                     | Type::Function
+                    | Type::Hash
                     | Type::Keyword
                     | Type::Stream
-                    | Type::Struct
                     | Type::Symbol
-                    | Type::Vector
                     | Type::T
                     | Type::List
-                    | Type::String => panic!(),
+                    | Type::String
+                    | Type::Integer
+                    | Type::Flonum
+                    | Type::Number => panic!(),
                 }
             }
         };
@@ -208,7 +256,5 @@ impl Function {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn as_tag() {
-        assert!(true)
-    }
+    fn as_tag() {}
 }
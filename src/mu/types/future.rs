@@ -0,0 +1,302 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// futures
+//
+// applying an async function spawns the application as a task on the
+// env's executor and returns a :future struct handle. the evaluator
+// runs on the caller's thread, so the executor does too: await, join,
+// and poll run its queued tasks, in the order they were spawned, until
+// the futures they want are no longer pending. a task awaiting another
+// runs the executor from inside its own application.
+//
+// a handle is only good for the task it was made for, a struct built
+// to look like one is not a future. ids are never reused.
+//
+// tasks hold their function, arguments, and result as gc roots until
+// their handle is unreachable, see GcContext::futures.
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            tag::Tag,
+            type_::Type,
+        },
+        types::{cons::Cons, fixnum::Fixnum, struct_::Struct, symbol::Symbol, vector::Vector},
+    },
+    futures_lite::future::block_on,
+    std::{cell::Cell, ptr},
+};
+
+thread_local! {
+    // the env whose executor this thread is running, see Future::tick
+    static DRIVER: Cell<*const Env> = const { Cell::new(ptr::null()) };
+}
+
+// a task's handle, its state, and its place on the executor
+pub struct Task {
+    pub handle: Tag,
+    pub future: Future,
+    spawned: Option<smol::Task<()>>,
+}
+
+#[derive(Clone)]
+pub enum Future {
+    Pending(Tag, Vec<Tag>),
+    Running,
+    Resolved(exception::Result<Tag>),
+    Cancelled,
+}
+
+impl Future {
+    // spawn an application, returns the handle
+    pub fn spawn(env: &Env, func: Tag, argv: Vec<Tag>) -> exception::Result<Tag> {
+        let future_id = {
+            let mut future_id_ref = block_on(env.future_id.write());
+
+            *future_id_ref += 1;
+            *future_id_ref
+        };
+
+        let handle = Struct::new(
+            env,
+            "future",
+            vec![Fixnum::with_usize_or_panic(
+                usize::try_from(future_id).unwrap(),
+            )],
+        )?
+        .with_heap(env)?;
+
+        // only Future::tick runs the executor, with the env in DRIVER
+        let spawned = env.executor.spawn(async move {
+            let env = DRIVER.with(Cell::get);
+
+            Self::run(unsafe { &*env }, future_id);
+        });
+
+        block_on(env.futures.write()).insert(
+            future_id,
+            Task {
+                handle,
+                future: Future::Pending(func, argv),
+                spawned: Some(spawned),
+            },
+        );
+
+        Ok(handle)
+    }
+
+    // the id of a task's own handle
+    pub fn future_id(env: &Env, tag: Tag) -> Option<u64> {
+        if tag.type_of() != Type::Struct {
+            return None;
+        }
+
+        let (stype, vector) = Struct::destruct(env, tag);
+
+        if !stype.eq_(&Symbol::keyword("future")) || Vector::length(env, vector) != 1 {
+            return None;
+        }
+
+        let future_id = match Vector::ref_(env, vector, 0) {
+            Some(id) if id.type_of() == Type::Fixnum => u64::try_from(Fixnum::as_i64(id)).ok()?,
+            _ => None?,
+        };
+
+        match block_on(env.futures.read()).get(&future_id) {
+            Some(task) if task.handle.eq_(&tag) => Some(future_id),
+            _ => None,
+        }
+    }
+
+    // the tags a task holds
+    pub fn tags(&self) -> Vec<Tag> {
+        match self {
+            Future::Pending(func, argv) => {
+                let mut tags = vec![*func];
+
+                tags.extend(argv);
+                tags
+            }
+            Future::Resolved(Ok(value)) => vec![*value],
+            Future::Resolved(Err(e)) => vec![e.object, e.source],
+            Future::Running | Future::Cancelled => Vec::new(),
+        }
+    }
+
+    pub fn tags_mut(&mut self) -> Vec<&mut Tag> {
+        match self {
+            Future::Pending(func, argv) => {
+                let mut tags = vec![func];
+
+                tags.extend(argv.iter_mut());
+                tags
+            }
+            Future::Resolved(Ok(value)) => vec![value],
+            Future::Resolved(Err(e)) => vec![&mut e.object, &mut e.source],
+            Future::Running | Future::Cancelled => Vec::new(),
+        }
+    }
+
+    // dropping a spawned task takes it off the executor
+    pub fn release(env: &Env, future_id: u64) -> bool {
+        block_on(env.futures.write()).remove(&future_id).is_some()
+    }
+
+    // run the executor's next task on this thread, false if there was
+    // nothing to run
+    fn tick(env: &Env) -> bool {
+        let outer = DRIVER.with(|driver| driver.replace(ptr::from_ref(env)));
+        let ticked = env.executor.try_tick();
+
+        DRIVER.with(|driver| driver.set(outer));
+        ticked
+    }
+
+    // run a pending task to completion, the table is unlocked while
+    // it runs so it can spawn and await other tasks
+    fn run(env: &Env, future_id: u64) {
+        let mut futures_ref = block_on(env.futures.write());
+
+        let Some(task) = futures_ref.get_mut(&future_id) else {
+            return;
+        };
+
+        let Future::Pending(func, argv) = task.future.clone() else {
+            return;
+        };
+
+        task.future = Future::Running;
+        drop(futures_ref);
+
+        let value = Frame {
+            func,
+            argv,
+            value: Tag::nil(),
+        }
+        .run_async(env, func);
+
+        if let Some(task) = block_on(env.futures.write()).get_mut(&future_id) {
+            task.future = Future::Resolved(value);
+        }
+    }
+
+    fn is_pending(env: &Env, future_id: u64) -> bool {
+        matches!(
+            block_on(env.futures.read()).get(&future_id),
+            Some(Task {
+                future: Future::Pending(_, _),
+                ..
+            })
+        )
+    }
+
+    // run the executor until the task is no longer pending
+    fn await_(env: &Env, future: Tag, source: &str) -> exception::Result<Tag> {
+        let Some(future_id) = Self::future_id(env, future) else {
+            Err(Exception::err(env, future, Condition::Type, source))?
+        };
+
+        while Self::is_pending(env, future_id) && Self::tick(env) {}
+
+        match block_on(env.futures.read()).get(&future_id) {
+            Some(Task {
+                future: Future::Resolved(value),
+                ..
+            }) => value.clone(),
+            _ => Err(Exception::err(env, future, Condition::Future, source)),
+        }
+    }
+}
+
+pub trait CoreFn {
+    fn mu_await(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_cancel(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_join(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_poll(_: &Env, _: &mut Frame) -> exception::Result<()>;
+}
+
+impl CoreFn for Future {
+    // a task's value, or its exception. awaiting a cancelled task, or
+    // one that is running, is a :future exception
+    fn mu_await(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        fp.value = Self::await_(env, fp.argv[0], "mu:await")?;
+
+        Ok(())
+    }
+
+    // :t if a pending task was cancelled
+    fn mu_cancel(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let future = fp.argv[0];
+
+        let Some(future_id) = Self::future_id(env, future) else {
+            Err(Exception::err(env, future, Condition::Type, "mu:cancel"))?
+        };
+
+        let mut futures_ref = block_on(env.futures.write());
+
+        fp.value = match futures_ref.get_mut(&future_id) {
+            Some(task) if matches!(task.future, Future::Pending(_, _)) => {
+                task.future = Future::Cancelled;
+                task.spawned = None;
+                Symbol::keyword("t")
+            }
+            _ => Tag::nil(),
+        };
+
+        Ok(())
+    }
+
+    // a list of the values of a list of futures, in order
+    fn mu_join(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:join", &[Type::List], fp)?;
+
+        let futures = Cons::list_iter(env, fp.argv[0]).collect::<Vec<Tag>>();
+
+        if let Some(future) = futures
+            .iter()
+            .find(|future| Self::future_id(env, **future).is_none())
+        {
+            Err(Exception::err(env, *future, Condition::Type, "mu:join"))?;
+        }
+
+        let values = futures
+            .iter()
+            .map(|future| Self::await_(env, *future, "mu:join"))
+            .collect::<exception::Result<Vec<Tag>>>()?;
+
+        fp.value = Cons::list(env, &values);
+
+        Ok(())
+    }
+
+    // run the executor's next task, :t if the future is no longer pending
+    fn mu_poll(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        let future = fp.argv[0];
+
+        let Some(future_id) = Self::future_id(env, future) else {
+            Err(Exception::err(env, future, Condition::Type, "mu:poll"))?
+        };
+
+        Self::tick(env);
+
+        fp.value = match block_on(env.futures.read()).get(&future_id) {
+            Some(Task {
+                future: Future::Pending(_, _) | Future::Running,
+                ..
+            }) => Tag::nil(),
+            _ => Symbol::keyword("t"),
+        };
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn future_test() {}
+}
@@ -0,0 +1,416 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// hash table type
+//
+// a hash table image is its test keyword, entry count, relocation
+// stamp, and a :t vector of buckets. a bucket is a list of (key . value)
+// conses. :eq tables hash keys by their tags, :equal tables compare
+// and hash keys structurally, see Tag::equal.
+//
+// compaction moves images out from under the tags :eq keys hash by. a
+// table stamped before its heap's last relocation is rehashed before
+// it is next used.
+//
+// hash-put and hash-rem modify the table in place.
+use {
+    crate::{
+        core::{
+            apply::Apply as _,
+            env::Env,
+            exception::{self, Condition, Exception},
+            frame::Frame,
+            indirect::{ExtTag, IndirectTag},
+            tag::{Tag, TagType},
+            type_::Type,
+        },
+        namespaces::heap::{Heap, HeapRequest},
+        streams::writer::StreamWriter,
+        types::{cons::Cons, fixnum::Fixnum, symbol::Symbol, vector::Vector},
+    },
+    futures_lite::future::block_on,
+    std::hash::{DefaultHasher, Hash as _, Hasher},
+};
+
+#[derive(Copy, Clone)]
+pub struct Hash {
+    pub test: Tag,
+    pub count: Tag,
+    pub stamp: Tag,
+    pub buckets: Tag,
+}
+
+impl Hash {
+    const MIN_BUCKETS: usize = 8;
+
+    pub fn to_image(env: &Env, tag: Tag) -> Self {
+        assert_eq!(tag.type_of(), Type::Hash);
+
+        match tag {
+            Tag::Indirect(image) => {
+                let heap_ref = Heap::image_heap(env, image);
+                let slice = usize::try_from(image.image_id()).unwrap();
+
+                Self {
+                    test: Tag::from_slice(heap_ref.image_slice(slice).unwrap()),
+                    count: Tag::from_slice(heap_ref.image_slice(slice + 1).unwrap()),
+                    stamp: Tag::from_slice(heap_ref.image_slice(slice + 2).unwrap()),
+                    buckets: Tag::from_slice(heap_ref.image_slice(slice + 3).unwrap()),
+                }
+            }
+            Tag::Direct(_) => panic!(),
+        }
+    }
+
+    pub fn empty(env: &Env, test: Tag) -> exception::Result<Tag> {
+        let stamp = block_on(env.heap.read()).relocations;
+
+        Hash {
+            test,
+            count: Fixnum::with_usize_or_panic(0),
+            stamp: Fixnum::with_usize_or_panic(stamp),
+            buckets: Vector::from(vec![Tag::nil(); Self::MIN_BUCKETS]).with_heap(env)?,
+        }
+        .with_heap(env)
+    }
+
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
+        let image: &[[u8; 8]] = &[
+            self.test.as_slice(),
+            self.count.as_slice(),
+            self.stamp.as_slice(),
+            self.buckets.as_slice(),
+        ];
+
+        let ha = HeapRequest {
+            env,
+            image,
+            vdata: None,
+            type_id: Type::Hash as u8,
+        };
+
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
+            Some(image_id) => Ok(Tag::Indirect(
+                IndirectTag::new()
+                    .with_image_id(image_id as u64)
+                    .with_heap_id(Heap::PRIVATE_ID)
+                    .with_ext(ExtTag::Hash)
+                    .with_tag(TagType::Ext),
+            )),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "hash")),
+        }
+    }
+
+    fn update(env: &Env, hash: Tag, image: &Hash) {
+        let slices: &[[u8; 8]] = &[
+            image.test.as_slice(),
+            image.count.as_slice(),
+            image.stamp.as_slice(),
+            image.buckets.as_slice(),
+        ];
+
+        let offset = usize::try_from(match hash {
+            Tag::Indirect(heap) => heap.image_id(),
+            Tag::Direct(_) => panic!(),
+        })
+        .unwrap();
+
+        block_on(env.heap.write()).write_image(slices, offset);
+    }
+
+    fn set_bucket(env: &Env, buckets: Tag, index: usize, bucket: Tag) {
+        let offset = usize::try_from(match buckets {
+            Tag::Indirect(heap) => heap.image_id(),
+            Tag::Direct(_) => panic!(),
+        })
+        .unwrap();
+
+        block_on(env.heap.write()).write_image_slice(
+            &[bucket.as_slice()],
+            offset,
+            Vector::IMAGE_LEN + index,
+        );
+    }
+
+    pub fn is_private(hash: Tag) -> bool {
+        match hash {
+            Tag::Indirect(indirect) => indirect.heap_id() == Heap::PRIVATE_ID,
+            Tag::Direct(_) => panic!(),
+        }
+    }
+
+    pub fn count(env: &Env, hash: Tag) -> usize {
+        usize::try_from(Fixnum::as_i64(Self::to_image(env, hash).count)).unwrap()
+    }
+
+    pub fn entries(env: &Env, hash: Tag) -> Vec<Tag> {
+        Vector::iter(env, Self::to_image(env, hash).buckets)
+            .flat_map(|bucket| Cons::list_iter(env, bucket).collect::<Vec<Tag>>())
+            .collect()
+    }
+
+    // system heap images keep their ids when the heap is frozen
+    fn eq_key(key: Tag) -> u64 {
+        match key {
+            Tag::Indirect(indirect) => {
+                Tag::Indirect(indirect.with_heap_id(Heap::PRIVATE_ID)).as_u64()
+            }
+            Tag::Direct(_) => key.as_u64(),
+        }
+    }
+
+    fn is_equal_test(image: &Hash) -> bool {
+        image.test.eq_(&Symbol::keyword("equal"))
+    }
+
+    fn same_key(env: &Env, image: &Hash, key: Tag, other: Tag) -> bool {
+        if Self::is_equal_test(image) {
+            Tag::equal(env, key, other)
+        } else {
+            key.eq_(&other)
+        }
+    }
+
+    fn bucket_index(env: &Env, image: &Hash, key: Tag, nbuckets: usize) -> usize {
+        let hash = if Self::is_equal_test(image) {
+            Tag::sxhash(env, key)
+        } else {
+            let mut hasher = DefaultHasher::new();
+
+            Self::eq_key(key).hash(&mut hasher);
+            hasher.finish()
+        };
+
+        usize::try_from(hash % nbuckets as u64).unwrap()
+    }
+
+    // redistribute the entries over nbuckets buckets
+    fn rehash(env: &Env, hash: Tag, nbuckets: usize) -> exception::Result<Hash> {
+        let mut image = Self::to_image(env, hash);
+        let entries = Self::entries(env, hash);
+        let mut buckets = vec![Tag::nil(); nbuckets];
+
+        for entry in entries {
+            let index = Self::bucket_index(env, &image, Cons::destruct(env, entry).0, nbuckets);
+
+            buckets[index] = Cons::cons(env, entry, buckets[index]);
+        }
+
+        image.buckets = Vector::from(buckets).with_heap(env)?;
+        image.stamp = Fixnum::with_usize_or_panic(block_on(env.heap.read()).relocations);
+        Self::update(env, hash, &image);
+
+        Ok(image)
+    }
+
+    // the table image, rehashed if the heap has moved since it was stamped.
+    // the system heap never moves.
+    fn fresh_image(env: &Env, hash: Tag) -> exception::Result<Hash> {
+        let image = Self::to_image(env, hash);
+
+        if Self::is_private(hash)
+            && Fixnum::as_i64(image.stamp)
+                != i64::try_from(block_on(env.heap.read()).relocations).unwrap()
+        {
+            Self::rehash(env, hash, Vector::length(env, image.buckets))
+        } else {
+            Ok(image)
+        }
+    }
+
+    pub fn get(env: &Env, hash: Tag, key: Tag) -> exception::Result<Option<Tag>> {
+        let image = Self::fresh_image(env, hash)?;
+        let nbuckets = Vector::length(env, image.buckets);
+        let bucket = Vector::ref_(
+            env,
+            image.buckets,
+            Self::bucket_index(env, &image, key, nbuckets),
+        );
+
+        Ok(bucket.and_then(|bucket| {
+            Cons::list_iter(env, bucket)
+                .find(|entry| Self::same_key(env, &image, key, Cons::destruct(env, *entry).0))
+        }))
+    }
+
+    pub fn put(env: &Env, hash: Tag, key: Tag, value: Tag) -> exception::Result<()> {
+        let mut image = Self::fresh_image(env, hash)?;
+        let nbuckets = Vector::length(env, image.buckets);
+        let index = Self::bucket_index(env, &image, key, nbuckets);
+        let bucket = Vector::ref_(env, image.buckets, index).unwrap();
+        let (found, bucket) = Self::remove_from(env, &image, key, bucket);
+
+        Self::set_bucket(
+            env,
+            image.buckets,
+            index,
+            Cons::cons(env, Cons::cons(env, key, value), bucket),
+        );
+
+        if found.is_none() {
+            let count = Self::count(env, hash) + 1;
+
+            image.count = Fixnum::with_usize_or_panic(count);
+            Self::update(env, hash, &image);
+
+            if count > nbuckets * 2 {
+                Self::rehash(env, hash, nbuckets * 2)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn remove(env: &Env, hash: Tag, key: Tag) -> exception::Result<Option<Tag>> {
+        let mut image = Self::fresh_image(env, hash)?;
+        let nbuckets = Vector::length(env, image.buckets);
+        let index = Self::bucket_index(env, &image, key, nbuckets);
+        let bucket = Vector::ref_(env, image.buckets, index).unwrap();
+        let (found, bucket) = Self::remove_from(env, &image, key, bucket);
+
+        if found.is_some() {
+            Self::set_bucket(env, image.buckets, index, bucket);
+            image.count = Fixnum::with_usize_or_panic(Self::count(env, hash) - 1);
+            Self::update(env, hash, &image);
+        }
+
+        Ok(found)
+    }
+
+    // the entry for key and the bucket without it
+    fn remove_from(env: &Env, image: &Hash, key: Tag, bucket: Tag) -> (Option<Tag>, Tag) {
+        let entries = Cons::list_iter(env, bucket).collect::<Vec<Tag>>();
+
+        match entries
+            .iter()
+            .position(|entry| Self::same_key(env, image, key, Cons::destruct(env, *entry).0))
+        {
+            Some(nth) => {
+                let mut rest = entries.clone();
+                let entry = rest.remove(nth);
+
+                (Some(entry), Cons::list(env, &rest))
+            }
+            None => (None, bucket),
+        }
+    }
+
+    pub fn view(env: &Env, hash: Tag) -> exception::Result<Tag> {
+        let image = Self::to_image(env, hash);
+
+        Vector::from(vec![image.test, image.count, image.stamp, image.buckets]).with_heap(env)
+    }
+
+    pub fn image_size(env: &Env, hash: Tag) -> usize {
+        std::mem::size_of::<Hash>() + Vector::image_size(env, Self::to_image(env, hash).buckets)
+    }
+
+    pub fn write(env: &Env, hash: Tag, _: bool, stream: Tag) -> exception::Result<()> {
+        let image = Self::to_image(env, hash);
+
+        StreamWriter::write_str(env, "#<hash: ", stream)?;
+        StreamWriter::write(env, image.test, true, stream)?;
+        StreamWriter::write_str(env, " ", stream)?;
+        StreamWriter::write(env, image.count, true, stream)?;
+        StreamWriter::write_str(env, ">", stream)
+    }
+}
+
+pub trait CoreFn {
+    fn mu_make_hash(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_hash_count(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_hash_get(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_hash_list(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_hash_put(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_hash_rem(_: &Env, _: &mut Frame) -> exception::Result<()>;
+}
+
+impl CoreFn for Hash {
+    fn mu_make_hash(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:make-hash", &[Type::Keyword, Type::List], fp)?;
+
+        let test = fp.argv[0];
+        let list = fp.argv[1];
+
+        if !test.eq_(&Symbol::keyword("eq")) && !test.eq_(&Symbol::keyword("equal")) {
+            Err(Exception::err(env, test, Condition::Type, "mu:make-hash"))?;
+        }
+
+        let hash = Self::empty(env, test)?;
+
+        for entry in Cons::list_iter(env, list) {
+            if entry.type_of() != Type::Cons {
+                Err(Exception::err(env, entry, Condition::Type, "mu:make-hash"))?;
+            }
+
+            let (key, value) = Cons::destruct(env, entry);
+
+            Self::put(env, hash, key, value)?;
+        }
+
+        fp.value = hash;
+
+        Ok(())
+    }
+
+    fn mu_hash_count(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:hash-count", &[Type::Hash], fp)?;
+
+        fp.value = Fixnum::with_usize(env, Self::count(env, fp.argv[0]))?;
+
+        Ok(())
+    }
+
+    fn mu_hash_get(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:hash-get", &[Type::Hash, Type::T], fp)?;
+
+        fp.value = Self::get(env, fp.argv[0], fp.argv[1])?.unwrap_or(Tag::nil());
+
+        Ok(())
+    }
+
+    fn mu_hash_list(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:hash-list", &[Type::Hash], fp)?;
+
+        fp.value = Cons::list(env, &Self::entries(env, fp.argv[0]));
+
+        Ok(())
+    }
+
+    fn mu_hash_put(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:hash-put", &[Type::Hash, Type::T, Type::T], fp)?;
+
+        let hash = fp.argv[0];
+
+        if !Self::is_private(hash) {
+            Err(Exception::err(env, hash, Condition::Write, "mu:hash-put"))?;
+        }
+
+        Self::put(env, hash, fp.argv[1], fp.argv[2])?;
+        fp.value = hash;
+
+        Ok(())
+    }
+
+    fn mu_hash_rem(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:hash-rem", &[Type::Hash, Type::T], fp)?;
+
+        let hash = fp.argv[0];
+
+        if !Self::is_private(hash) {
+            Err(Exception::err(env, hash, Condition::Write, "mu:hash-rem"))?;
+        }
+
+        fp.value = Self::remove(env, hash, fp.argv[1])?.unwrap_or(Tag::nil());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn hash_test() {}
+}
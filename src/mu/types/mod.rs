@@ -3,11 +3,15 @@
 
 //! types
 pub mod async_;
+pub mod bignum;
 pub mod char;
 pub mod cons;
+pub mod double;
 pub mod fixnum;
 pub mod float;
 pub mod function;
+pub mod future;
+pub mod hash;
 pub mod stream;
 pub mod struct_;
 pub mod symbol;
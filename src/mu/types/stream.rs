@@ -84,7 +84,11 @@ impl Stream {
                     stream_tag,
                 )
             }
-            None => panic!(),
+            None => StreamWriter::write_str(
+                env,
+                format!("#<stream: {} :released>", Stream::stream_id(tag).unwrap()).as_str(),
+                stream_tag,
+            ),
         }
     }
 
@@ -101,33 +105,53 @@ impl Stream {
         }
     }
 
-    pub fn view(env: &Env, stream: Tag) -> Tag {
-        let core_streams_ref = block_on(CORE.streams.read());
-
-        match core_streams_ref.get(&Self::stream_id(stream).unwrap()) {
+    // a released stream views with no direction
+    pub fn view(env: &Env, stream: Tag) -> exception::Result<Tag> {
+        let stream_id = Self::stream_id(stream).unwrap();
+        let (direction, unch) = match block_on(CORE.streams.read()).get(&stream_id) {
             Some(stream_ref) => {
                 let stream = block_on(stream_ref.read());
-                let vec = vec![
-                    Fixnum::with_usize(env, usize::try_from(stream.id).unwrap()).unwrap(),
-                    stream.direction,
-                    stream.unch,
-                ];
 
-                Vector::from(vec).with_heap(env)
+                (stream.direction, stream.unch)
             }
-            None => panic!(),
-        }
+            None => (Tag::nil(), Tag::nil()),
+        };
+        let vec = vec![
+            Fixnum::with_usize(env, usize::try_from(stream_id).unwrap()).unwrap(),
+            direction,
+            unch,
+        ];
+
+        Vector::from(vec).with_heap(env)
     }
 
+    // a released stream is no longer open
     pub fn is_open(stream: Tag) -> bool {
         let core_streams_ref = block_on(CORE.streams.read());
 
         match core_streams_ref.get(&Self::stream_id(stream).unwrap()) {
             Some(stream_ref) => block_on(stream_ref.read()).open,
-            None => panic!(),
+            None => false,
         }
     }
 
+    // close a stream and drop its table entry, only streams opened in
+    // this environment are released, never the standard streams. an
+    // open stream is closed first, output not yet read is discarded
+    pub fn release(env: &Env, stream_id: u64) -> bool {
+        if !block_on(env.streams.write()).remove(&stream_id) {
+            return false;
+        }
+
+        let mut core_streams_ref = block_on(CORE.streams.write());
+
+        if let Some(stream_ref) = core_streams_ref.remove(&stream_id) {
+            SystemStream::close(&block_on(stream_ref.read()).system);
+        }
+
+        true
+    }
+
     pub fn close(stream: Tag) {
         let core_streams_ref = block_on(CORE.streams.read());
 
@@ -173,6 +197,7 @@ pub trait CoreFn {
     fn mu_read(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_read_byte(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_read_char(_: &Env, _: &mut Frame) -> exception::Result<()>;
+    fn mu_release_stream(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_unread_char(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_write(_: &Env, _: &mut Frame) -> exception::Result<()>;
     fn mu_write_byte(_: &Env, _: &mut Frame) -> exception::Result<()>;
@@ -302,7 +327,30 @@ impl CoreFn for Stream {
                     Tag::nil()
                 }
             }
-            None => panic!(),
+            None => Tag::nil(),
+        };
+
+        Ok(())
+    }
+
+    // releases open and closed streams alike, :nil if already released
+    fn mu_release_stream(env: &Env, fp: &mut Frame) -> exception::Result<()> {
+        env.argv_check("mu:release-stream", &[Type::Stream], fp)?;
+
+        let stream = fp.argv[0];
+        let stream_id = Stream::stream_id(stream)?;
+
+        fp.value = if Stream::release(env, stream_id) {
+            Symbol::keyword("t")
+        } else if block_on(CORE.streams.read()).contains_key(&stream_id) {
+            Err(Exception::err(
+                env,
+                stream,
+                Condition::Stream,
+                "mu:release-stream",
+            ))?
+        } else {
+            Tag::nil()
         };
 
         Ok(())
@@ -314,7 +362,7 @@ impl CoreFn for Stream {
         let stream = fp.argv[0];
         let string = Stream::get_string(env, stream)?;
 
-        fp.value = Vector::from(string).with_heap(env);
+        fp.value = Vector::from(string).with_heap(env)?;
 
         Ok(())
     }
@@ -401,12 +449,6 @@ mod tests {
             .input()
             .build();
 
-        match stream {
-            Some(stream) => match stream {
-                SystemStream::String(_) => assert_eq!(true, true),
-                _ => assert_eq!(true, false),
-            },
-            None => assert_eq!(true, false),
-        }
+        assert!(matches!(stream, Some(SystemStream::String(_))));
     }
 }
@@ -13,7 +13,7 @@ use {
             tag::{Tag, TagType},
             type_::Type,
         },
-        namespaces::heap::HeapRequest,
+        namespaces::heap::{Heap, HeapRequest},
         streams::{reader::StreamReader, writer::StreamWriter},
         types::{cons::Cons, symbol::Symbol, vector::Vector},
     },
@@ -33,7 +33,7 @@ impl Struct {
 
         match tag {
             Tag::Indirect(image) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, image);
                 let slice = usize::try_from(image.image_id()).unwrap();
 
                 Self {
@@ -50,7 +50,7 @@ impl Struct {
 
         match struct_ {
             Tag::Indirect(struct_) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, struct_);
                 let slice = usize::try_from(struct_.image_id()).unwrap();
 
                 (
@@ -62,10 +62,10 @@ impl Struct {
         }
     }
 
-    pub fn to_tag(env: &Env, stype: Tag, vec: Vec<Tag>) -> Tag {
+    pub fn to_tag(env: &Env, stype: Tag, vec: Vec<Tag>) -> exception::Result<Tag> {
         match stype.type_of() {
             Type::Keyword => {
-                let vector = Vector::from(vec).with_heap(env);
+                let vector = Vector::from(vec).with_heap(env)?;
 
                 Struct { stype, vector }.with_heap(env)
             }
@@ -73,14 +73,14 @@ impl Struct {
         }
     }
 
-    pub fn new(env: &Env, key: &str, vec: Vec<Tag>) -> Self {
-        Struct {
+    pub fn new(env: &Env, key: &str, vec: Vec<Tag>) -> exception::Result<Self> {
+        Ok(Struct {
             stype: Symbol::keyword(key),
-            vector: Vector::from(vec).with_heap(env),
-        }
+            vector: Vector::from(vec).with_heap(env)?,
+        })
     }
 
-    pub fn view(env: &Env, tag: Tag) -> Tag {
+    pub fn view(env: &Env, tag: Tag) -> exception::Result<Tag> {
         let image = Self::to_image(env, tag);
 
         Vector::from(vec![image.stype, image.vector]).with_heap(env)
@@ -120,11 +120,9 @@ impl Struct {
 
                 let (stype, vec) = Cons::destruct(env, vec_list);
                 match stype.type_of() {
-                    Type::Keyword => Ok(Self::to_tag(
-                        env,
-                        stype,
-                        Cons::list_iter(env, vec).collect::<Vec<Tag>>(),
-                    )),
+                    Type::Keyword => {
+                        Self::to_tag(env, stype, Cons::list_iter(env, vec).collect::<Vec<Tag>>())
+                    }
                     _ => Err(Exception::err(env, stype, Condition::Type, "mu:read"))?,
                 }
             }
@@ -132,9 +130,8 @@ impl Struct {
         }
     }
 
-    pub fn with_heap(&self, env: &Env) -> Tag {
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
         let image: &[[u8; 8]] = &[self.stype.as_slice(), self.vector.as_slice()];
-        let mut heap_ref = block_on(env.heap.write());
         let ha = HeapRequest {
             env,
             image,
@@ -142,16 +139,18 @@ impl Struct {
             type_id: Type::Struct as u8,
         };
 
-        match heap_ref.alloc(&ha) {
+        let image_id = block_on(env.heap.write()).alloc(&ha);
+
+        match image_id {
             Some(image_id) => {
                 let ind = IndirectTag::new()
                     .with_image_id(image_id as u64)
-                    .with_heap_id(1)
+                    .with_heap_id(Heap::PRIVATE_ID)
                     .with_tag(TagType::Struct);
 
-                Tag::Indirect(ind)
+                Ok(Tag::Indirect(ind))
             }
-            None => panic!(),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "struct")),
         }
     }
 }
@@ -193,9 +192,9 @@ impl CoreFn for Struct {
 
         fp.value = Struct {
             stype: type_,
-            vector: Vector::from(vec).with_heap(env),
+            vector: Vector::from(vec).with_heap(env)?,
         }
-        .with_heap(env);
+        .with_heap(env)?;
 
         Ok(())
     }
@@ -204,7 +203,5 @@ impl CoreFn for Struct {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn struct_test() {
-        assert!(true);
-    }
+    fn struct_test() {}
 }
@@ -44,32 +44,32 @@ pub struct SymbolImage {
 }
 
 impl Symbol {
-    pub fn new(env: &Env, namespace: Tag, name: &str, value: Tag) -> Self {
+    pub fn new(env: &Env, namespace: Tag, name: &str, value: Tag) -> exception::Result<Self> {
         let str = name.as_bytes();
 
         if name.is_empty() {
-            Symbol::Symbol(SymbolImage {
+            Ok(Symbol::Symbol(SymbolImage {
                 namespace,
-                name: Vector::from(name).with_heap(env),
+                name: Vector::from(name).with_heap(env)?,
                 value,
-            })
+            }))
         } else {
             match str[0] as char {
-                ':' => Symbol::Keyword(Self::keyword(&name[1..])),
-                _ => Symbol::Symbol(SymbolImage {
+                ':' => Ok(Symbol::Keyword(Self::keyword(&name[1..]))),
+                _ => Ok(Symbol::Symbol(SymbolImage {
                     namespace,
-                    name: Vector::from(name).with_heap(env),
+                    name: Vector::from(name).with_heap(env)?,
                     value,
-                }),
+                })),
             }
         }
     }
 
     pub fn to_image(env: &Env, tag: Tag) -> SymbolImage {
         match tag.type_of() {
-            Type::Symbol => match tag {
+            Type::Keyword | Type::Symbol => match tag {
                 Tag::Indirect(main) => {
-                    let heap_ref = block_on(env.heap.read());
+                    let heap_ref = Heap::image_heap(env, main);
                     let slice = usize::try_from(main.image_id()).unwrap();
 
                     SymbolImage {
@@ -113,20 +113,41 @@ impl Symbol {
                         DirectExt::Length(dir.ext() as usize),
                         DirectType::String,
                     ),
-                    Tag::Indirect(_) => panic!(),
+                    Tag::Indirect(_) => Self::to_image(env, symbol).name,
                 },
                 symbol,
             ),
             Type::Symbol => {
                 let image = Self::to_image(env, symbol);
 
-                (image.namespace, image.name, image.value)
+                (
+                    image.namespace,
+                    image.name,
+                    Self::system_value(env, symbol, &image),
+                )
             }
             _ => panic!(),
         }
     }
 
-    pub fn view(env: &Env, symbol: Tag) -> Tag {
+    // an unbound system heap symbol takes the value of the private
+    // symbol that shadows it, see Namespace::intern
+    fn system_value(env: &Env, symbol: Tag, image: &SymbolImage) -> Tag {
+        match symbol {
+            Tag::Indirect(indirect)
+                if indirect.heap_id() != Heap::PRIVATE_ID
+                    && image.value.eq_(&UNBOUND)
+                    && image.namespace.type_of() == Type::Struct =>
+            {
+                Namespace::find_symbol(env, image.namespace, &Vector::as_string(env, image.name))
+                    .filter(|shadow| !shadow.eq_(&symbol))
+                    .map_or(image.value, |shadow| Self::destruct(env, shadow).2)
+            }
+            _ => image.value,
+        }
+    }
+
+    pub fn view(env: &Env, symbol: Tag) -> exception::Result<Tag> {
         let (ns, name, value) = Self::destruct(env, symbol);
         let vec = vec![
             match ns.type_of() {
@@ -139,7 +160,7 @@ impl Symbol {
                     }
                 }
                 Type::Struct => {
-                    Vector::from(format!("\"{}\"", Namespace::name(env, ns))).with_heap(env)
+                    Vector::from(format!("\"{}\"", Namespace::name(env, ns))).with_heap(env)?
                 }
                 _ => panic!(),
             },
@@ -155,6 +176,18 @@ impl Symbol {
     }
 
     pub fn image_size(env: &Env, symbol: Tag) -> usize {
+        // a keyword's value is itself
+        if symbol.type_of() == Type::Keyword {
+            return match symbol {
+                Tag::Direct(_) => std::mem::size_of::<DirectTag>(),
+                Tag::Indirect(_) => {
+                    let name_sz = Heap::image_size(env, Self::to_image(env, symbol).name);
+
+                    std::mem::size_of::<SymbolImage>() + if name_sz > 8 { name_sz } else { 0 }
+                }
+            };
+        }
+
         let (_, name, value) = Self::destruct(env, symbol);
         let name_sz = Heap::image_size(env, name);
         let value_sz = Heap::image_size(env, value);
@@ -173,9 +206,9 @@ impl Symbol {
         }
     }
 
-    pub fn with_heap(&self, env: &Env) -> Tag {
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
         match self {
-            Symbol::Keyword(tag) => *tag,
+            Symbol::Keyword(tag) => Ok(*tag),
             Symbol::Symbol(image) => {
                 let slices: &[[u8; 8]] = &[
                     image.namespace.as_slice(),
@@ -183,7 +216,6 @@ impl Symbol {
                     image.value.as_slice(),
                 ];
 
-                let mut heap_ref = block_on(env.heap.write());
                 let ha = HeapRequest {
                     env,
                     image: slices,
@@ -191,16 +223,18 @@ impl Symbol {
                     type_id: Type::Symbol as u8,
                 };
 
-                match heap_ref.alloc(&ha) {
+                let image_id = block_on(env.heap.write()).alloc(&ha);
+
+                match image_id {
                     Some(image_id) => {
                         let ind = IndirectTag::new()
                             .with_image_id(image_id as u64)
-                            .with_heap_id(1)
+                            .with_heap_id(Heap::PRIVATE_ID)
                             .with_tag(TagType::Symbol);
 
-                        Tag::Indirect(ind)
+                        Ok(Tag::Indirect(ind))
                     }
-                    None => panic!(),
+                    None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "symbol")),
                 }
             }
         }
@@ -238,10 +272,10 @@ impl Symbol {
 
         match token.find(':') {
             Some(0) => {
-                if token.len() > DirectTag::DIRECT_STR_MAX + 1 || token.len() == 1 {
+                if token.len() == 1 {
                     Err(Exception::err(
                         env,
-                        Vector::from(token).with_heap(env),
+                        Vector::from(token).with_heap(env)?,
                         Condition::Syntax,
                         "mu:read",
                     ))?;
@@ -249,7 +283,15 @@ impl Symbol {
 
                 let keyword: String = token.chars().skip(1).collect();
 
-                Ok(Self::keyword(&keyword))
+                match Namespace::intern(env, env.keyword_ns, keyword, *UNBOUND)? {
+                    Some(keyword) => Ok(keyword),
+                    None => Err(Exception::err(
+                        env,
+                        Vector::from(token).with_heap(env)?,
+                        Condition::Syntax,
+                        "mu:read",
+                    ))?,
+                }
             }
             Some(_) => {
                 let sym: Vec<&str> = token.split(':').collect();
@@ -259,36 +301,38 @@ impl Symbol {
                 if sym.len() != 2 {
                     Err(Exception::err(
                         env,
-                        Vector::from(token).with_heap(env),
+                        Vector::from(token).with_heap(env)?,
                         Condition::Syntax,
                         "mu:read",
                     ))?;
                 }
 
                 match Namespace::find_ns(env, &ns) {
-                    Some(ns) => Ok(Namespace::intern(env, ns, name, *UNBOUND).unwrap()),
+                    Some(ns) => Ok(Namespace::intern(env, ns, name, *UNBOUND)?.unwrap()),
                     None => Err(Exception::err(
                         env,
-                        Vector::from(sym[0]).with_heap(env),
+                        Vector::from(sym[0]).with_heap(env)?,
                         Condition::Namespace,
                         "mu:read",
                     ))?,
                 }
             }
-            None => Ok(Self::new(env, *UNBOUND, token, *UNBOUND).with_cache(env)),
+            None => Ok(Self::new(env, *UNBOUND, token, *UNBOUND)?.with_cache(env)),
         }
     }
 
     pub fn write(env: &Env, symbol: Tag, _escape: bool, stream: Tag) -> exception::Result<()> {
         match symbol.type_of() {
+            Type::Keyword if matches!(symbol, Tag::Indirect(_)) => {
+                StreamWriter::write_char(env, stream, ':').unwrap();
+                StreamWriter::write(env, Self::to_image(env, symbol).name, false, stream)
+            }
             Type::Null | Type::Keyword => {
                 let str = symbol.data(env).to_le_bytes();
-                let s = str::from_utf8(&str).unwrap();
+                let s = str::from_utf8(&str[..DirectTag::length(symbol)]).unwrap();
 
                 StreamWriter::write_char(env, stream, ':').unwrap();
-                for nth in 0..DirectTag::length(symbol) {
-                    StreamWriter::write_char(env, stream, s.as_bytes()[nth] as char)?;
-                }
+                StreamWriter::write_str(env, s, stream)?;
 
                 Ok(())
             }
@@ -420,8 +464,8 @@ impl CoreFn for Symbol {
             Tag::nil(),
             Vector::as_string(env, fp.argv[0]).as_str(),
             *UNBOUND,
-        )
-        .with_heap(env);
+        )?
+        .with_heap(env)?;
 
         Ok(())
     }
@@ -430,7 +474,5 @@ impl CoreFn for Symbol {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn symbol_test() {
-        assert!(true);
-    }
+    fn symbol_test() {}
 }
@@ -11,6 +11,7 @@ use {
             tag::Tag,
             type_::Type,
         },
+        namespaces::heap::Heap,
         types::{fixnum::Fixnum, symbol::Symbol},
         vectors::{
             image::{VecImage, VecImageType, VectorImage, VectorImageType},
@@ -18,7 +19,6 @@ use {
             write::Write,
         },
     },
-    futures_lite::future::block_on,
     std::{str, sync::LazyLock},
 };
 
@@ -27,6 +27,7 @@ pub static VECTYPEMAP: LazyLock<Vec<(Tag, VectorType)>> = LazyLock::new(|| {
         (Symbol::keyword("bit"), VectorType::Bit),
         (Symbol::keyword("byte"), VectorType::Byte),
         (Symbol::keyword("char"), VectorType::Char),
+        (Symbol::keyword("double"), VectorType::Double),
         (Symbol::keyword("fixnum"), VectorType::Fixnum),
         (Symbol::keyword("float"), VectorType::Float),
         (Symbol::keyword("t"), VectorType::T),
@@ -44,6 +45,7 @@ pub enum VectorType {
     Bit,
     Byte,
     Char,
+    Double,
     Fixnum,
     Float,
     T,
@@ -55,6 +57,7 @@ impl VectorType {
             VectorType::Bit => Type::Bit,
             VectorType::Byte => Type::Byte,
             VectorType::Char => Type::Char,
+            VectorType::Double => Type::Double,
             VectorType::Fixnum => Type::Fixnum,
             VectorType::Float => Type::Float,
             VectorType::T => Type::T,
@@ -63,7 +66,7 @@ impl VectorType {
 }
 
 impl Vector {
-    const IMAGE_LEN: usize = 2; // heap words in image
+    pub const IMAGE_LEN: usize = 2; // heap words in image
 
     pub fn to_type(keyword: Tag) -> Option<VectorType> {
         VECTYPEMAP
@@ -90,18 +93,102 @@ impl Vector {
         }
     }
 
+    // char vectors hold utf-8, their lengths and indices count characters
     pub fn length(env: &Env, vector: Tag) -> usize {
         match vector {
-            Tag::Direct(direct) => direct.ext() as usize,
+            Tag::Direct(direct) => match direct.dtype() {
+                DirectType::String => {
+                    Self::utf8_length(&direct.data().to_le_bytes()[..direct.ext() as usize])
+                }
+                _ => direct.ext() as usize,
+            },
             Tag::Indirect(_) => {
                 let image = Self::to_image(env, vector);
 
-                usize::try_from(Fixnum::as_i64(image.length)).unwrap()
+                Self::image_length(&image)
+            }
+        }
+    }
+
+    // the number of elements, characters for char vectors
+    pub fn image_length(image: &VectorImage) -> usize {
+        usize::try_from(Fixnum::as_i64(image.length)).unwrap()
+    }
+
+    // char vector image data is the utf-8 byte length, the bytes, and
+    // unless they're all ascii, the byte offset of each character
+    const CHAR_BYTES: usize = 8;
+
+    pub fn char_image_data(str: &str) -> Vec<u8> {
+        let mut data = (str.len() as u64).to_le_bytes().to_vec();
+
+        data.extend(str.as_bytes());
+
+        if !str.is_ascii() {
+            data.resize(data.len().next_multiple_of(4), 0);
+
+            for (offset, _) in str.char_indices() {
+                data.extend(u32::try_from(offset).unwrap().to_le_bytes());
             }
         }
+
+        data
+    }
+
+    // the utf-8 bytes of the char vector image data at index
+    pub fn char_image_bytes(heap: &Heap, index: usize) -> Option<&[u8]> {
+        let len = u64::from_le_bytes(heap.image_data_slice(index, 0, 8)?.try_into().unwrap());
+
+        heap.image_data_slice(index, Self::CHAR_BYTES, usize::try_from(len).unwrap())
+    }
+
+    // the nth of chars characters, from the offset table if not ascii
+    pub fn char_image_ref(heap: &Heap, index: usize, chars: usize, nth: usize) -> Option<char> {
+        let bytes = Self::char_image_bytes(heap, index)?;
+
+        if bytes.len() == chars {
+            return Some(char::from(*bytes.get(nth)?));
+        }
+
+        let offsets = (Self::CHAR_BYTES + bytes.len()).next_multiple_of(4);
+        let offset = |nth: usize| match nth {
+            nth if nth == chars => Some(bytes.len()),
+            _ => {
+                let slice = heap.image_data_slice(index, offsets + nth * 4, 4)?;
+
+                usize::try_from(u32::from_le_bytes(slice.try_into().unwrap())).ok()
+            }
+        };
+
+        str::from_utf8(bytes.get(offset(nth)?..offset(nth + 1)?)?)
+            .ok()?
+            .chars()
+            .next()
+    }
+
+    pub fn utf8_length(bytes: &[u8]) -> usize {
+        bytes.iter().filter(|byte| (**byte & 0xc0) != 0x80).count()
+    }
+
+    // the nth character of utf-8 bytes
+    pub fn utf8_nth(bytes: &[u8], nth: usize) -> Option<char> {
+        let (start, lead) = bytes
+            .iter()
+            .enumerate()
+            .filter(|(_, byte)| (**byte & 0xc0) != 0x80)
+            .nth(nth)?;
+        let len = match lead.leading_ones() {
+            0 => 1,
+            len => len as usize,
+        };
+
+        str::from_utf8(bytes.get(start..start + len)?)
+            .ok()?
+            .chars()
+            .next()
     }
 
-    pub fn view(env: &Env, vector: Tag) -> Tag {
+    pub fn view(env: &Env, vector: Tag) -> exception::Result<Tag> {
         let type_ = Self::type_of(env, vector).map_type();
 
         let vec = vec![
@@ -122,17 +209,14 @@ impl Vector {
                 _ => panic!(),
             },
             Tag::Indirect(image) => {
-                let heap_ref = block_on(env.heap.read());
-                let vec: VectorImage = Self::to_image(env, tag);
+                let heap_ref = Heap::image_heap(env, image);
 
                 str::from_utf8(
-                    heap_ref
-                        .image_data_slice(
-                            usize::try_from(image.image_id()).unwrap() + Self::IMAGE_LEN,
-                            0,
-                            usize::try_from(Fixnum::as_i64(vec.length)).unwrap(),
-                        )
-                        .unwrap(),
+                    Self::char_image_bytes(
+                        &heap_ref,
+                        usize::try_from(image.image_id()).unwrap() + Self::IMAGE_LEN,
+                    )
+                    .unwrap(),
                 )
                 .unwrap()
                 .into()
@@ -141,7 +225,7 @@ impl Vector {
     }
 
     pub fn iter(env: &Env, vec: Tag) -> VectorIter<'_> {
-        VectorIter { env, vec, index: 0 }
+        VectorIter::new(env, vec)
     }
 
     pub fn ref_(env: &Env, vector: Tag, index: usize) -> Option<Tag> {
@@ -149,11 +233,11 @@ impl Vector {
 
         match vector {
             Tag::Direct(direct) => match direct.dtype() {
-                DirectType::String => {
-                    let ch: char = vector.data(env).to_le_bytes()[index].into();
-
-                    Some(ch.into())
-                }
+                DirectType::String => Self::utf8_nth(
+                    &vector.data(env).to_le_bytes()[..direct.ext() as usize],
+                    index,
+                )
+                .map(Into::into),
                 DirectType::ByteVec => {
                     let byte: u8 = vector.data(env).to_le_bytes()[index];
 
@@ -165,12 +249,45 @@ impl Vector {
         }
     }
 
+    // the vector's elements as rust data
+    pub fn to_data(env: &Env, vector: Tag) -> VectorImageType {
+        assert_eq!(vector.type_of(), Type::Vector);
+
+        match vector {
+            Tag::Direct(direct) => match direct.dtype() {
+                DirectType::String => VectorImageType::Char(Self::as_string(env, vector)),
+                DirectType::ByteVec => VectorImageType::Byte(
+                    vector.data(env).to_le_bytes()[..direct.ext() as usize].to_vec(),
+                ),
+                _ => panic!(),
+            },
+            Tag::Indirect(_) => VecImageType::to_data(env, vector),
+        }
+    }
+
+    // a double vector element, without allocating
+    pub fn double_ref(env: &Env, vector: Tag, index: usize) -> f64 {
+        let Tag::Indirect(image) = vector else {
+            panic!()
+        };
+        let heap_ref = Heap::image_heap(env, image);
+        let slice = heap_ref
+            .image_data_slice(
+                usize::try_from(image.image_id()).unwrap() + Self::IMAGE_LEN,
+                index * 8,
+                8,
+            )
+            .unwrap();
+
+        f64::from_le_bytes(slice.try_into().unwrap())
+    }
+
     pub fn to_image(env: &Env, tag: Tag) -> VectorImage {
         assert_eq!(tag.type_of(), Type::Vector);
 
         match tag {
             Tag::Indirect(image) => {
-                let heap_ref = block_on(env.heap.read());
+                let heap_ref = Heap::image_heap(env, image);
                 let slice = usize::try_from(image.image_id()).unwrap();
 
                 VectorImage {
@@ -186,20 +303,23 @@ impl Vector {
         match vector {
             Tag::Direct(_) => std::mem::size_of::<DirectTag>(),
             Tag::Indirect(_) => {
-                let len = Self::length(env, vector);
+                let len = Self::image_length(&Self::to_image(env, vector));
                 let size = match Self::type_of(env, vector) {
-                    VectorType::Bit | VectorType::Byte | VectorType::Char => 1,
-                    VectorType::Fixnum | VectorType::Float | VectorType::T => 8,
+                    VectorType::Bit => len.div_ceil(8),
+                    VectorType::Byte => len,
+                    VectorType::Char => Self::char_image_data(&Self::as_string(env, vector)).len(),
+                    VectorType::Float => len * 4,
+                    VectorType::Double | VectorType::Fixnum | VectorType::T => len * 8,
                 };
 
-                std::mem::size_of::<VectorImage>() + (size * len)
+                std::mem::size_of::<VectorImage>() + size
             }
         }
     }
 
-    pub fn with_heap(&self, env: &Env) -> Tag {
+    pub fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
         match self {
-            Vector::Direct(tag) => *tag,
+            Vector::Direct(tag) => Ok(*tag),
             Vector::Indirect(image, ivec) => {
                 let indirect = match ivec {
                     VectorImageType::T(_) => VecImageType::T(image, ivec),
@@ -208,18 +328,19 @@ impl Vector {
                     VectorImageType::Byte(_) => VecImageType::Byte(image, ivec),
                     VectorImageType::Fixnum(_) => VecImageType::Fixnum(image, ivec),
                     VectorImageType::Float(_) => VecImageType::Float(image, ivec),
+                    VectorImageType::Double(_) => VecImageType::Double(image, ivec),
                 };
 
                 match ivec {
                     VectorImageType::T(_) => indirect.with_heap(env),
                     _ => {
                         if let Some(tag) = Self::cached(env, &indirect) {
-                            tag
+                            Ok(tag)
                         } else {
-                            let tag = indirect.with_heap(env);
+                            let tag = indirect.with_heap(env)?;
 
                             Self::cache(env, tag);
-                            tag
+                            Ok(tag)
                         }
                     }
                 }
@@ -241,12 +362,17 @@ pub struct VectorIter<'a> {
     env: &'a Env,
     pub vec: Tag,
     pub index: usize,
+    length: usize,
 }
 
 impl<'a> VectorIter<'a> {
-    #[allow(dead_code)]
     pub fn new(env: &'a Env, vec: Tag) -> Self {
-        Self { env, vec, index: 0 }
+        Self {
+            env,
+            vec,
+            index: 0,
+            length: Vector::length(env, vec),
+        }
     }
 }
 
@@ -254,7 +380,7 @@ impl Iterator for VectorIter<'_> {
     type Item = Tag;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= Vector::length(self.env, self.vec) {
+        if self.index >= self.length {
             None
         } else {
             let el = Vector::ref_(self.env, self.vec, self.index).unwrap();
@@ -269,7 +395,5 @@ impl Iterator for VectorIter<'_> {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test() {
-        assert!(true);
-    }
+    fn test() {}
 }
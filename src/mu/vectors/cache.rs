@@ -18,7 +18,7 @@ pub type VecCacheMap = HashMap<(Type, i32), RwLock<Vec<Tag>>>;
 impl Vector {
     pub fn cache(env: &Env, vector: Tag) {
         let vtype = Self::type_of(env, vector).map_type();
-        let length = i32::try_from(Self::length(env, vector)).unwrap();
+        let length = i32::try_from(Self::image_length(&Self::to_image(env, vector))).unwrap();
         let mut cache = block_on(env.vector_cache.write());
 
         match (*cache).get(&(vtype, length)) {
@@ -45,10 +45,11 @@ impl Vector {
             VecImageType::Bit(image, ivec)
             | VecImageType::Byte(image, ivec)
             | VecImageType::Char(image, ivec)
+            | VecImageType::Double(image, ivec)
             | VecImageType::Fixnum(image, ivec)
             | VecImageType::Float(image, ivec) => (
                 image.type_,
-                i32::try_from(Fixnum::as_i64(image.length)).unwrap(),
+                i32::try_from(Vector::image_length(image)).unwrap(),
                 ivec,
             ),
             VecImageType::T(_, _) => panic!(),
@@ -85,6 +86,11 @@ impl Vector {
                             *float == Float::as_f32(env, Vector::ref_(env, **src, index).unwrap())
                         })
                     }),
+                    VectorImageType::Double(double_vec) => tag_vec.iter().find(|src| {
+                        double_vec.iter().enumerate().all(|(index, double)| {
+                            double.to_bits() == Vector::double_ref(env, **src, index).to_bits()
+                        })
+                    }),
                     VectorImageType::T(_) => panic!(),
                 };
 
@@ -98,7 +104,5 @@ impl Vector {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test() {
-        assert!(true);
-    }
+    fn test() {}
 }
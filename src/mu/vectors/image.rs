@@ -7,12 +7,14 @@ use {
         core::{
             direct::{DirectExt, DirectTag, DirectType},
             env::Env,
+            exception::{self, Condition, Exception},
             indirect::IndirectTag,
             tag::{Tag, TagType},
             type_::Type,
         },
-        namespaces::heap::HeapRequest,
+        namespaces::heap::{Heap, HeapRequest},
         types::{
+            double::Double,
             fixnum::Fixnum,
             symbol::Symbol,
             vector::{Vector, VectorType},
@@ -33,6 +35,7 @@ pub enum VectorImageType {
     Bit(Vec<u8>),
     Byte(Vec<u8>),
     Char(String),
+    Double(Vec<f64>),
     Fixnum(Vec<i64>),
     Float(Vec<f32>),
     T(Vec<Tag>),
@@ -43,6 +46,7 @@ pub enum VecImageType<'a> {
     Bit(&'a VectorImage, &'a VectorImageType),
     Byte(&'a VectorImage, &'a VectorImageType),
     Char(&'a VectorImage, &'a VectorImageType),
+    Double(&'a VectorImage, &'a VectorImageType),
     Fixnum(&'a VectorImage, &'a VectorImageType),
     Float(&'a VectorImage, &'a VectorImageType),
     T(&'a VectorImage, &'a VectorImageType),
@@ -66,7 +70,7 @@ impl From<&str> for Vector {
         if len > DirectTag::DIRECT_STR_MAX {
             let image = VectorImage {
                 type_: Symbol::keyword("char"),
-                length: Fixnum::with_usize_or_panic(str.len()),
+                length: Fixnum::with_usize_or_panic(str.chars().count()),
             };
 
             Vector::Indirect(image, VectorImageType::Char(str.into()))
@@ -160,12 +164,24 @@ impl From<Vec<f32>> for Vector {
     }
 }
 
+impl From<Vec<f64>> for Vector {
+    fn from(vec: Vec<f64>) -> Vector {
+        let image = VectorImage {
+            type_: Symbol::keyword("double"),
+            length: Fixnum::with_usize_or_panic(vec.len()),
+        };
+
+        Vector::Indirect(image, VectorImageType::Double(vec.clone()))
+    }
+}
+
 pub trait VecImage {
     const IMAGE_LEN: usize = 2; // heap words in image
 
     fn image(_: &VectorImage) -> Vec<[u8; 8]>;
-    fn with_heap(&self, _: &Env) -> Tag;
+    fn with_heap(&self, _: &Env) -> exception::Result<Tag>;
     fn ref_(_: &Env, _: Tag, _: usize) -> Option<Tag>;
+    fn to_data(_: &Env, _: Tag) -> VectorImageType;
 }
 
 impl VecImage for VecImageType<'_> {
@@ -173,9 +189,8 @@ impl VecImage for VecImageType<'_> {
         vec![image.type_.as_slice(), image.length.as_slice()]
     }
 
-    fn with_heap(&self, env: &Env) -> Tag {
-        let mut heap_ref = block_on(env.heap.write());
-        let mut fvec = Vec::<u8>::new(); // extend lifetime of float slices
+    fn with_heap(&self, env: &Env) -> exception::Result<Tag> {
+        let mut fvec = Vec::<u8>::new(); // extend lifetime of float and char slices
 
         let (image, vdata) = match self {
             VecImageType::Byte(image, ivec) => match ivec {
@@ -187,7 +202,11 @@ impl VecImage for VecImageType<'_> {
                 _ => panic!(),
             },
             VecImageType::Char(image, ivec) => match ivec {
-                VectorImageType::Char(string) => (&Self::image(image), Some(string.as_bytes())),
+                VectorImageType::Char(string) => {
+                    fvec = Vector::char_image_data(string);
+
+                    (&Self::image(image), Some(fvec.as_slice()))
+                }
                 _ => panic!(),
             },
             VecImageType::T(image, ivec) => {
@@ -214,6 +233,18 @@ impl VecImage for VecImageType<'_> {
                     _ => panic!(),
                 }
             }
+            VecImageType::Double(image, ivec) => {
+                let mut slices = Self::image(image);
+
+                match ivec {
+                    VectorImageType::Double(vec) => {
+                        slices.extend(vec.iter().map(|fl| fl.to_le_bytes()));
+
+                        (&slices.clone(), None)
+                    }
+                    _ => panic!(),
+                }
+            }
             VecImageType::Float(image, ivec) => match ivec {
                 VectorImageType::Float(vec_u4) => {
                     for float in vec_u4 {
@@ -235,17 +266,17 @@ impl VecImage for VecImageType<'_> {
             type_id: Type::Vector as u8,
         };
 
-        let image_id = match heap_ref.alloc(&ha) {
-            Some(id) => id as u64,
-            None => panic!(),
-        };
+        let image_id = block_on(env.heap.write()).alloc(&ha);
 
-        Tag::Indirect(
-            IndirectTag::new()
-                .with_image_id(image_id)
-                .with_heap_id(1)
-                .with_tag(TagType::Vector),
-        )
+        match image_id {
+            Some(image_id) => Ok(Tag::Indirect(
+                IndirectTag::new()
+                    .with_image_id(image_id as u64)
+                    .with_heap_id(Heap::PRIVATE_ID)
+                    .with_tag(TagType::Vector),
+            )),
+            None => Err(Exception::err(env, Tag::nil(), Condition::Heap, "vector")),
+        }
     }
 
     fn ref_(env: &Env, vector: Tag, index: usize) -> Option<Tag> {
@@ -257,11 +288,13 @@ impl VecImage for VecImageType<'_> {
         let len = usize::try_from(vimage.image_id()).unwrap() + Self::IMAGE_LEN;
         let image = Vector::to_image(env, vector);
 
-        if index >= usize::try_from(Fixnum::as_i64(image.length)).unwrap() {
+        let length = Vector::image_length(&image);
+
+        if index >= length {
             None?;
         }
 
-        let heap_ref = block_on(env.heap.read());
+        let heap_ref = Heap::image_heap(env, vimage);
 
         match Vector::to_type(image.type_).unwrap() {
             VectorType::Bit => {
@@ -276,10 +309,7 @@ impl VecImage for VecImageType<'_> {
                 Some(slice[0].into())
             }
             VectorType::Char => {
-                let slice = heap_ref.image_data_slice(len, index, 1)?;
-                let ch: char = slice[0].into();
-
-                Some(ch.into())
+                Vector::char_image_ref(&heap_ref, len, length, index).map(Into::into)
             }
             VectorType::T => Some(Tag::from_slice(heap_ref.image_data_slice(
                 len,
@@ -298,6 +328,64 @@ impl VecImage for VecImageType<'_> {
 
                 Some(f32::from_le_bytes(slice[0..4].try_into().unwrap()).into())
             }
+            VectorType::Double => {
+                let fl = f64::from_le_bytes(
+                    heap_ref.image_data_slice(len, index * 8, 8)?[0..8]
+                        .try_into()
+                        .unwrap(),
+                );
+
+                // release the heap before allocating the double
+                drop(heap_ref);
+
+                Double::with_f64(env, fl).ok()
+            }
+        }
+    }
+
+    // copy out the vector data in one pass, without making element tags
+    fn to_data(env: &Env, vector: Tag) -> VectorImageType {
+        assert!(vector.type_of() == Type::Vector);
+
+        let Tag::Indirect(vimage) = vector else {
+            panic!()
+        };
+        let index = usize::try_from(vimage.image_id()).unwrap() + Self::IMAGE_LEN;
+        let image = Vector::to_image(env, vector);
+        let len = Vector::image_length(&image);
+
+        let heap_ref = Heap::image_heap(env, vimage);
+        let data = |size| heap_ref.image_data_slice(index, 0, size).unwrap();
+
+        match Vector::to_type(image.type_).unwrap() {
+            VectorType::Bit => VectorImageType::Bit(data(len.div_ceil(8)).to_vec()),
+            VectorType::Byte => VectorImageType::Byte(data(len).to_vec()),
+            VectorType::Char => VectorImageType::Char(
+                str::from_utf8(Vector::char_image_bytes(&heap_ref, index).unwrap())
+                    .unwrap()
+                    .into(),
+            ),
+            VectorType::Double => VectorImageType::Double(
+                data(len * 8)
+                    .chunks(8)
+                    .map(|fl| f64::from_le_bytes(fl.try_into().unwrap()))
+                    .collect(),
+            ),
+            VectorType::Fixnum => VectorImageType::Fixnum(
+                data(len * 8)
+                    .chunks(8)
+                    .map(|fx| i64::from_le_bytes(fx.try_into().unwrap()))
+                    .collect(),
+            ),
+            VectorType::Float => VectorImageType::Float(
+                data(len * 4)
+                    .chunks(4)
+                    .map(|fl| f32::from_le_bytes(fl.try_into().unwrap()))
+                    .collect(),
+            ),
+            VectorType::T => {
+                VectorImageType::T(data(len * 8).chunks(8).map(Tag::from_slice).collect())
+            }
         }
     }
 }
@@ -305,7 +393,5 @@ impl VecImage for VecImageType<'_> {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test() {
-        assert!(true);
-    }
+    fn test() {}
 }
@@ -14,6 +14,7 @@ use crate::{
     types::{
         char::Char,
         cons::Cons,
+        double::Double,
         fixnum::Fixnum,
         float::Float,
         vector::{Vector, VectorType, VECTYPEMAP},
@@ -47,7 +48,7 @@ impl Read for Vector {
                     }
                 }
 
-                Ok(Self::from(str).with_heap(env))
+                Ok(Self::from(str).with_heap(env)?)
             }
             '*' => {
                 let mut digits: String = String::new();
@@ -60,19 +61,8 @@ impl Read for Vector {
                                 break;
                             }
                             SyntaxType::Escape => match StreamReader::read_char(env, stream)? {
-                                Some(ch) => {
-                                    if ch == '0' || ch == '1' {
-                                        digits.push(ch);
-                                    } else {
-                                        Err(Exception::err(
-                                            env,
-                                            stream,
-                                            Condition::Eof,
-                                            "mu:read",
-                                        ))?;
-                                    }
-                                }
-                                None => {
+                                Some(ch) if ch == '0' || ch == '1' => digits.push(ch),
+                                Some(_) | None => {
                                     Err(Exception::err(env, stream, Condition::Eof, "mu:read"))?;
                                 }
                             },
@@ -97,7 +87,7 @@ impl Read for Vector {
                     }
                 }
 
-                Ok(Self::from((vec, digits.len())).with_heap(env))
+                Ok(Self::from((vec, digits.len())).with_heap(env)?)
             }
             '(' => {
                 let vec_list = match Cons::read(env, stream) {
@@ -120,7 +110,7 @@ impl Read for Vector {
                         VectorType::T => {
                             let vec = Cons::list_iter(env, vec).collect::<Vec<Tag>>();
 
-                            Ok(Vector::from(vec).with_heap(env))
+                            Ok(Vector::from(vec).with_heap(env)?)
                         }
                         VectorType::Char => {
                             let vec: exception::Result<String> =
@@ -139,7 +129,7 @@ impl Read for Vector {
                                     })
                                     .collect();
 
-                            Ok(Vector::from(vec?).with_heap(env))
+                            Ok(Vector::from(vec?).with_heap(env)?)
                         }
                         VectorType::Byte => {
                             let vec: exception::Result<Vec<u8>> =
@@ -168,7 +158,7 @@ impl Read for Vector {
                                     })
                                     .collect();
 
-                            Ok(Vector::from(vec?).with_heap(env))
+                            Ok(Vector::from(vec?).with_heap(env)?)
                         }
                         VectorType::Fixnum => {
                             let vec: exception::Result<Vec<i64>> =
@@ -187,7 +177,23 @@ impl Read for Vector {
                                     })
                                     .collect();
 
-                            Ok(Vector::from(vec?).with_heap(env))
+                            Ok(Vector::from(vec?).with_heap(env)?)
+                        }
+                        VectorType::Double => {
+                            let vec: exception::Result<Vec<f64>> =
+                                Cons::list_iter(env, Cons::destruct(env, vec_list).1)
+                                    .map(|fl| match fl.type_of() {
+                                        Type::Float | Type::Double => Ok(Double::from_tag(env, fl)),
+                                        _ => Err(Exception::err(
+                                            env,
+                                            fl,
+                                            Condition::Type,
+                                            "mu:read",
+                                        ))?,
+                                    })
+                                    .collect();
+
+                            Ok(Vector::from(vec?).with_heap(env)?)
                         }
                         VectorType::Float => {
                             let vec: exception::Result<Vec<f32>> =
@@ -206,7 +212,7 @@ impl Read for Vector {
                                     })
                                     .collect();
 
-                            Ok(Vector::from(vec?).with_heap(env))
+                            Ok(Vector::from(vec?).with_heap(env)?)
                         }
                     },
                     None => Err(Exception::err(env, vec_type, Condition::Type, "mu:read"))?,
@@ -220,7 +226,5 @@ impl Read for Vector {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn read_test() {
-        assert!(true);
-    }
+    fn read_test() {}
 }
@@ -14,6 +14,7 @@ use crate::{
     types::{
         char::Char,
         cons::Cons,
+        double::Double,
         fixnum::Fixnum,
         float::Float,
         vector::{Vector, VectorType},
@@ -41,7 +42,7 @@ impl CoreFn for Vector {
                 VectorType::T => {
                     let vec = Cons::list_iter(env, list).collect::<Vec<Tag>>();
 
-                    Vector::from(vec).with_heap(env)
+                    Vector::from(vec).with_heap(env)?
                 }
                 VectorType::Char => {
                     let vec: exception::Result<String> = Cons::list_iter(env, list)
@@ -54,7 +55,7 @@ impl CoreFn for Vector {
                         })
                         .collect();
 
-                    Vector::from(vec?).with_heap(env)
+                    Vector::from(vec?).with_heap(env)?
                 }
                 VectorType::Bit => {
                     let mut vec = vec![0; Cons::length(env, list).unwrap().div_ceil(8)];
@@ -73,7 +74,7 @@ impl CoreFn for Vector {
                         }
                     }
 
-                    Vector::from(vec).with_heap(env)
+                    Vector::from(vec).with_heap(env)?
                 }
                 VectorType::Byte => {
                     let vec: exception::Result<Vec<u8>> = Cons::list_iter(env, list)
@@ -96,7 +97,7 @@ impl CoreFn for Vector {
                         })
                         .collect();
 
-                    Vector::from(vec?).with_heap(env)
+                    Vector::from(vec?).with_heap(env)?
                 }
                 VectorType::Fixnum => {
                     let vec: exception::Result<Vec<i64>> = Cons::list_iter(env, list)
@@ -109,7 +110,17 @@ impl CoreFn for Vector {
                         })
                         .collect();
 
-                    Vector::from(vec?).with_heap(env)
+                    Vector::from(vec?).with_heap(env)?
+                }
+                VectorType::Double => {
+                    let vec: exception::Result<Vec<f64>> = Cons::list_iter(env, list)
+                        .map(|fl| match fl.type_of() {
+                            Type::Float | Type::Double => Ok(Double::from_tag(env, fl)),
+                            _ => Err(Exception::err(env, fl, Condition::Type, "mu:make-vector"))?,
+                        })
+                        .collect();
+
+                    Vector::from(vec?).with_heap(env)?
                 }
                 VectorType::Float => {
                     let vec: exception::Result<Vec<f32>> = Cons::list_iter(env, list)
@@ -122,7 +133,7 @@ impl CoreFn for Vector {
                         })
                         .collect();
 
-                    Vector::from(vec?).with_heap(env)
+                    Vector::from(vec?).with_heap(env)?
                 }
             },
             None => Err(Exception::err(
@@ -148,7 +159,11 @@ impl CoreFn for Vector {
             Err(Exception::err(env, index, Condition::Range, "mu:svref"))?;
         }
 
-        fp.value = Self::ref_(env, vector, usize::try_from(nth).unwrap()).unwrap();
+        // in range, a double element that can't be allocated
+        fp.value = match Self::ref_(env, vector, usize::try_from(nth).unwrap()) {
+            Some(el) => el,
+            None => Err(Exception::err(env, vector, Condition::Heap, "mu:svref"))?,
+        };
 
         Ok(())
     }
@@ -177,7 +192,5 @@ impl CoreFn for Vector {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn vector_test() {
-        assert!(true);
-    }
+    fn vector_test() {}
 }
@@ -12,6 +12,7 @@ use {
         },
         streams::writer::StreamWriter,
         types::{
+            double::Double,
             fixnum::Fixnum,
             vector::{Vector, VectorType},
         },
@@ -29,15 +30,13 @@ impl Write for Vector {
             Tag::Direct(direct) => match direct.dtype() {
                 DirectType::String => {
                     let bytes = vector.data(env).to_le_bytes();
-                    let str = str::from_utf8(&bytes).unwrap();
+                    let str = str::from_utf8(&bytes[..DirectTag::length(vector)]).unwrap();
 
                     if escape {
                         StreamWriter::write_str(env, "\"", stream).unwrap();
                     }
 
-                    for nth in 0..DirectTag::length(vector) {
-                        StreamWriter::write_char(env, stream, str.as_bytes()[nth] as char)?;
-                    }
+                    StreamWriter::write_str(env, str, stream)?;
 
                     if escape {
                         StreamWriter::write_str(env, "\"", stream).unwrap();
@@ -63,9 +62,7 @@ impl Write for Vector {
                         StreamWriter::write_str(env, "\"", stream)?;
                     }
 
-                    for ch in Vector::iter(env, vector) {
-                        StreamWriter::write(env, ch, false, stream)?;
-                    }
+                    StreamWriter::write_str(env, &Self::as_string(env, vector), stream)?;
 
                     if escape {
                         StreamWriter::write_str(env, "\"", stream)?;
@@ -84,6 +81,19 @@ impl Write for Vector {
 
                     Ok(())
                 }
+                // written from the image, without allocating the elements
+                VectorType::Double => {
+                    StreamWriter::write_str(env, "#(:double", stream)?;
+
+                    for index in 0..Self::length(env, vector) {
+                        let fl = Self::double_ref(env, vector, index);
+
+                        StreamWriter::write_str(env, " ", stream)?;
+                        StreamWriter::write_str(env, &Double::as_string(fl), stream)?;
+                    }
+
+                    StreamWriter::write_str(env, ")", stream)
+                }
                 _ => {
                     StreamWriter::write_str(env, "#(", stream)?;
                     StreamWriter::write(env, Self::to_image(env, vector).type_, true, stream)?;
@@ -103,7 +113,5 @@ impl Write for Vector {
 #[cfg(test)]
 mod tests {
     #[test]
-    fn test() {
-        assert!(true);
-    }
+    fn test() {}
 }
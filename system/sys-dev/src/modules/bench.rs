@@ -42,10 +42,10 @@ impl Bench {
 
     fn run_perf(&self, script: &str, group: &str, to: &str, ntests: usize) {
         let json_path = Options::add_path(&mut self.report.clone(), to);
-        let mut json_file = File::create(&json_path).expect(&format!("{json_path:?}"));
+        let mut json_file = File::create(&json_path).unwrap_or_else(|_| panic!("{json_path:?}"));
 
         let output = Command::new("python3")
-            .arg(&Options::add_path(&mut self.module.clone(), script))
+            .arg(Options::add_path(&mut self.module.clone(), script))
             .arg(&self.mu_sys)
             .arg(&self.core_sys)
             .arg(&self.module)
@@ -62,10 +62,10 @@ impl Bench {
     fn run_footprint(&self, script: &str, to: &str, ntests: usize) {
         let json_path = Options::add_path(&mut self.report.clone(), to);
 
-        let mut json_file = File::create(&json_path).expect(&format!("{json_path:?}"));
+        let mut json_file = File::create(&json_path).unwrap_or_else(|_| panic!("{json_path:?}"));
 
         let output = Command::new("python3")
-            .arg(&Options::add_path(&mut self.module.clone(), script))
+            .arg(Options::add_path(&mut self.module.clone(), script))
             .arg(&self.mu_sys)
             .arg(&self.core_sys)
             .arg(ntests.to_string())
@@ -76,7 +76,7 @@ impl Bench {
         io::stderr().write_all(&output.stderr).unwrap();
     }
 
-    pub fn bench(&self, argv: &Vec<String>) -> io::Result<()> {
+    pub fn bench(&self, argv: &[String]) -> io::Result<()> {
         match Options::parse_options(
             argv,
             &["base", "current", "report", "clean"],
@@ -156,7 +156,7 @@ impl Bench {
             }
 
             let output = Command::new("python3")
-                .arg(&Options::add_path(
+                .arg(Options::add_path(
                     &mut self.module.clone(),
                     "report-group.py",
                 ))
@@ -209,7 +209,7 @@ impl Bench {
 
         sed_child.wait()?;
 
-        let paste_child = Command::new("paste")
+        let mut paste_child = Command::new("paste")
             .arg(&base_report_path)
             .arg("-")
             .stdin(Stdio::from(sed_child.stdout.unwrap()))
@@ -219,17 +219,19 @@ impl Bench {
 
         let pipe_child = Command::new("sed")
             .args(["-e", "1,$s/^.. //"])
-            .stdin(Stdio::from(paste_child.stdout.unwrap()))
+            .stdin(Stdio::from(paste_child.stdout.take().unwrap()))
             .stdout(Stdio::piped())
             .output()
             .unwrap();
 
+        paste_child.wait()?;
+
         let mut report_tmp_file = NamedTempFile::new().unwrap();
         report_tmp_file.write_all(&pipe_child.stdout).unwrap();
 
         let output = Command::new("python3")
-            .arg(&Options::add_path(&mut self.module.clone(), "report.py"))
-            .arg(&report_tmp_file.path())
+            .arg(Options::add_path(&mut self.module.clone(), "report.py"))
+            .arg(report_tmp_file.path())
             .output()
             .expect("command failed to execute");
 
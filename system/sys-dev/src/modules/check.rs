@@ -12,7 +12,7 @@ use {
 pub struct Check;
 
 impl Check {
-    pub fn check(argv: &Vec<String>, home: &str) {
+    pub fn check(argv: &[String], home: &str) {
         match Options::parse_options(argv, &["debug", "release", "profile"], &["verbose"]) {
             None => (),
             Some(options) => {
@@ -23,9 +23,8 @@ impl Check {
 
                 let mode = &options.modes[0];
 
-                match Options::find_opt(&options, &Opt::Verbose) {
-                    Some(_) => println!("sys-dev build: {:?} --verbose", mode),
-                    None => (),
+                if Options::find_opt(&options, &Opt::Verbose).is_some() {
+                    println!("sys-dev build: {:?} --verbose", mode)
                 };
 
                 let dist = &format!("{home}/dist");
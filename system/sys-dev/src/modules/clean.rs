@@ -12,13 +12,12 @@ use {
 pub struct Clean {}
 
 impl Clean {
-    pub fn clean(argv: &Vec<String>, home: &str) {
+    pub fn clean(argv: &[String], home: &str) {
         match Options::parse_options(argv, &[], &["verbose"]) {
             None => (),
             Some(options) => {
-                match Options::find_opt(&options, &Opt::Verbose) {
-                    Some(_) => println!("sys-dev clean: --verbose"),
-                    None => (),
+                if Options::find_opt(&options, &Opt::Verbose).is_some() {
+                    println!("sys-dev clean: --verbose")
                 };
 
                 let _dist = &format!("{home}/dist");
@@ -44,7 +43,7 @@ impl Clean {
                     .arg(mu.clone() + "/target")
                     .arg(mu.clone() + "/Cargo.lock")
                     .arg(mu.clone() + "/TAGS")
-                    .spawn()
+                    .status()
                     .expect("command failed to execute");
 
                 for dir in dirs {
@@ -53,7 +52,7 @@ impl Clean {
                         .args(["-C", &(mu.clone() + "/" + dir)])
                         .arg("clean")
                         .arg("--no-print-directory")
-                        .spawn()
+                        .status()
                         .expect("command faled to execute");
                 }
             }
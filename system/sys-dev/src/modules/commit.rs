@@ -11,16 +11,15 @@ use {
 pub struct Commit {}
 
 impl Commit {
-    pub fn commit(argv: &Vec<String>) {
+    pub fn commit(argv: &[String]) {
         match Options::parse_options(argv, &[], &["verbose"]) {
             None => (),
             Some(options) => {
-                match Options::find_opt(&options, &Opt::Verbose) {
-                    Some(_) => println!("sys-dev commit: --verbose"),
-                    None => (),
+                if Options::find_opt(&options, &Opt::Verbose).is_some() {
+                    println!("sys-dev commit: --verbose")
                 };
 
-                for cmd in vec!["fmt", "clippy", "test"] {
+                for cmd in ["fmt", "clippy", "test"] {
                     let output = Command::new("cargo")
                         .arg(cmd)
                         .output()
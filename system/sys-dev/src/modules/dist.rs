@@ -11,7 +11,7 @@ use {
 pub struct Check;
 
 impl Check {
-    pub fn check(argv: &Vec<String>, home: &str) {
+    pub fn check(argv: &[String], home: &str) {
         match Options::parse_options(argv, &["debug", "release", "profile"], &["verbose"]) {
             None => (),
             Some(options) => {
@@ -11,7 +11,7 @@ use {
 pub struct Install {}
 
 impl Install {
-    pub fn install(argv: &Vec<String>, home: &str) {
+    pub fn install(argv: &[String], home: &str) {
         match Options::parse_options(argv, &[], &["verbose"]) {
             None => (),
             Some(options) => {
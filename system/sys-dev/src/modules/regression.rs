@@ -38,7 +38,7 @@ impl Regression {
 
     fn test_ns(&self, ns: &str) {
         let output = Command::new("python3")
-            .arg(&Options::add_path(&mut self.module.clone(), "test-ns.py"))
+            .arg(Options::add_path(&mut self.module.clone(), "test-ns.py"))
             .arg(&self.mu_sys)
             .arg(&self.tests)
             .arg(ns)
@@ -50,11 +50,11 @@ impl Regression {
         io::stderr().write_all(&output.stderr).unwrap();
 
         let output = Command::new("python3")
-            .arg(&Options::add_path(
+            .arg(Options::add_path(
                 &mut self.module.clone(),
                 "summarize-ns.py",
             ))
-            .arg(&report_tmp_file.path())
+            .arg(report_tmp_file.path())
             .output()
             .expect("command failed to execute");
 
@@ -64,7 +64,7 @@ impl Regression {
 
     fn test_module(&self, module: &str) {
         let output = Command::new("python3")
-            .arg(&Options::add_path(
+            .arg(Options::add_path(
                 &mut self.module.clone(),
                 "test-module.py",
             ))
@@ -80,11 +80,11 @@ impl Regression {
         io::stderr().write_all(&output.stderr).unwrap();
 
         let output = Command::new("python3")
-            .arg(&Options::add_path(
+            .arg(Options::add_path(
                 &mut self.module.clone(),
                 "summarize-module.py",
             ))
-            .arg(&report_tmp_file.path())
+            .arg(report_tmp_file.path())
             .output()
             .expect("command failed to execute");
 
@@ -92,24 +92,20 @@ impl Regression {
         io::stderr().write_all(&output.stderr).unwrap();
     }
 
-    pub fn regression(&self, argv: &Vec<String>) -> io::Result<()> {
+    pub fn regression(&self, argv: &[String]) -> io::Result<()> {
         match Options::parse_options(argv, &[], &["verbose", "recipe"]) {
             None => Ok(()),
             Some(options) => {
-                if options.modes.len() != 0 {
+                if !options.modes.is_empty() {
                     panic!()
                 }
 
-                match Options::find_opt(&options, &Opt::Verbose) {
-                    Some(_) => {
-                        println!("[test] --verbose")
-                    }
-                    None => (),
+                if Options::find_opt(&options, &Opt::Verbose).is_some() {
+                    println!("[test] --verbose")
                 }
 
-                match Options::find_opt(&options, &Opt::Recipe) {
-                    Some(_) => println!("[tests] --recipe"),
-                    None => (),
+                if Options::find_opt(&options, &Opt::Recipe).is_some() {
+                    println!("[tests] --recipe")
                 };
 
                 self.test_ns("mu");
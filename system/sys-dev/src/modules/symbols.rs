@@ -33,7 +33,7 @@ impl Symbols {
         }
     }
 
-    pub fn symbols(&self, argv: &Vec<String>) -> io::Result<()> {
+    pub fn symbols(&self, argv: &[String]) -> io::Result<()> {
         match Options::parse_options(
             argv,
             &["crossref", "metrics", "reference", "clean"],
@@ -65,10 +65,10 @@ impl Symbols {
                         let tmp_file = NamedTempFile::new().unwrap();
 
                         let output = Command::new(&self.mu_sys)
-                            .args(["-l", &self.core_sys.to_str().unwrap()])
+                            .args(["-l", self.core_sys.to_str().unwrap()])
                             .args([
                                 "-l",
-                                &Options::add_path(&mut self.module.clone(), "crossref.l")
+                                Options::add_path(&mut self.module.clone(), "crossref.l")
                                     .to_str()
                                     .unwrap(),
                             ])
@@ -80,8 +80,8 @@ impl Symbols {
                         io::stderr().write_all(&output.stderr).unwrap();
 
                         let output = Command::new("python3")
-                            .arg(&Options::add_path(&mut self.module.clone(), "crossref.py"))
-                            .arg(&tmp_file.path())
+                            .arg(Options::add_path(&mut self.module.clone(), "crossref.py"))
+                            .arg(tmp_file.path())
                             .output()
                             .expect("command failed to execute");
 
@@ -92,10 +92,10 @@ impl Symbols {
                         let tmp_file = NamedTempFile::new().unwrap();
 
                         let output = Command::new(&self.mu_sys)
-                            .args(["-l", &self.core_sys.to_str().unwrap()])
+                            .args(["-l", self.core_sys.to_str().unwrap()])
                             .args([
                                 "-l",
-                                &Options::add_path(&mut self.module.clone(), "metrics.l")
+                                Options::add_path(&mut self.module.clone(), "metrics.l")
                                     .to_str()
                                     .unwrap(),
                             ])
@@ -110,9 +110,9 @@ impl Symbols {
                         io::stderr().write_all(&output.stderr).unwrap();
 
                         let output = Command::new("python3")
-                            .arg(&Options::add_path(&mut self.module.clone(), "metrics.py"))
+                            .arg(Options::add_path(&mut self.module.clone(), "metrics.py"))
                             .arg(&self.core_sys)
-                            .arg(&tmp_file.path())
+                            .arg(tmp_file.path())
                             .output()
                             .expect("command failed to execute");
 
@@ -123,10 +123,10 @@ impl Symbols {
                         let tmp_file = NamedTempFile::new().unwrap();
 
                         let output = Command::new(&self.mu_sys)
-                            .args(["-l", &self.core_sys.to_str().unwrap()])
+                            .args(["-l", self.core_sys.to_str().unwrap()])
                             .args([
                                 "-l",
-                                &Options::add_path(&mut self.module.clone(), "reference.l")
+                                Options::add_path(&mut self.module.clone(), "reference.l")
                                     .to_str()
                                     .unwrap(),
                             ])
@@ -141,8 +141,8 @@ impl Symbols {
                         io::stderr().write_all(&output.stderr).unwrap();
 
                         let output = Command::new("python3")
-                            .arg(&Options::add_path(&mut self.module.clone(), "reference.py"))
-                            .arg(&tmp_file.path())
+                            .arg(Options::add_path(&mut self.module.clone(), "reference.py"))
+                            .arg(tmp_file.path())
                             .output()
                             .expect("command failed to execute");
 
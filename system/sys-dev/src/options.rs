@@ -82,18 +82,15 @@ impl Options {
     }
 
     pub fn find_opt(options: &Options, opt: &Opt) -> Option<Opt> {
-        match options
+        options
             .options
             .iter()
             .find(|next| std::mem::discriminant(*next) == std::mem::discriminant(opt))
-        {
-            None => None,
-            Some(opt) => Some(opt.clone()),
-        }
+            .cloned()
     }
 
     pub fn opt_value(options: &Options, opt: &Opt) -> Option<String> {
-        match Self::find_opt(&options, opt) {
+        match Self::find_opt(options, opt) {
             Some(opt) => match opt {
                 Opt::Namespace(str) | Opt::Prof(str) | Opt::Ref(str) | Opt::Ntests(str) => {
                     Some(str.to_string())
@@ -117,7 +114,7 @@ impl Options {
         .to_string()
     }
 
-    pub fn parse_options(argv: &Vec<String>, modes: &[&str], opt_list: &[&str]) -> Option<Options> {
+    pub fn parse_options(argv: &[String], modes: &[&str], opt_list: &[&str]) -> Option<Options> {
         let mut opts = getopts::Options::new();
 
         opts.optflag("", "all", "");
@@ -131,7 +128,7 @@ impl Options {
 
         let mode_args = argv[2..]
             .iter()
-            .filter(|mode| mode.chars().next().unwrap() != '-')
+            .filter(|mode| !mode.starts_with('-'))
             .map(|string| string.as_str())
             .collect::<Vec<&str>>();
 
@@ -169,7 +166,7 @@ impl Options {
 
         let mut opt_args = argv[2..]
             .iter()
-            .filter(|opt| opt.chars().next().unwrap() == '-')
+            .filter(|opt| opt.starts_with('-'))
             .collect::<Vec<&String>>();
 
         for opt in &mut opt_args {
@@ -38,13 +38,14 @@ impl Workspace {
         let mut cwd: PathBuf = std::env::current_dir().unwrap();
         loop {
             match Path::read_dir(&cwd) {
-                Ok(mut dir) => match dir.find(|entry| match entry {
-                    Ok(entry) => entry.file_name() == ".sys-dev",
-                    _ => false,
-                }) {
-                    Some(_) => return Some(cwd.to_str().unwrap().to_string()),
-                    None => (),
-                },
+                Ok(mut dir) => {
+                    if dir.any(|entry| match entry {
+                        Ok(entry) => entry.file_name() == ".sys-dev",
+                        _ => false,
+                    }) {
+                        return Some(cwd.to_str().unwrap().to_string());
+                    }
+                }
                 _ => return None,
             }
 
@@ -77,7 +78,7 @@ impl Workspace {
         Ok(())
     }
 
-    pub fn workspace(argv: &Vec<String>) {
+    pub fn workspace(argv: &[String]) {
         match Options::parse_options(argv, &["init", "env"], &["verbose", "recipe"]) {
             None => (),
             Some(options) => {
@@ -87,35 +88,26 @@ impl Workspace {
 
                 let mode = &options.modes[0];
 
-                match Options::find_opt(&options, &Opt::Verbose) {
-                    Some(_) => {
-                        println!(
-                            "verbose [workspace {}]:{}",
-                            mode.name(),
-                            options
-                                .options
-                                .iter()
-                                .map(|option| format!(
-                                    " --{}",
-                                    Options::opt_name((*option).clone())
-                                ))
-                                .collect::<String>()
-                        )
-                    }
-                    None => (),
+                if Options::find_opt(&options, &Opt::Verbose).is_some() {
+                    println!(
+                        "verbose [workspace {}]:{}",
+                        mode.name(),
+                        options
+                            .options
+                            .iter()
+                            .map(|option| format!(" --{}", Options::opt_name((*option).clone())))
+                            .collect::<String>()
+                    )
                 };
 
                 match mode {
                     Mode::Init => {
-                        match Options::find_opt(&options, &Opt::Recipe) {
-                            Some(_) => {
-                                println!(
-                                    "recipe [workspace init]: mkdir {}/.forge",
-                                    env::current_dir().unwrap().to_str().unwrap()
-                                );
-                                return ();
-                            }
-                            None => (),
+                        if Options::find_opt(&options, &Opt::Recipe).is_some() {
+                            println!(
+                                "recipe [workspace init]: mkdir {}/.forge",
+                                env::current_dir().unwrap().to_str().unwrap()
+                            );
+                            return;
                         };
 
                         if Self::sysdev_dir_exists("./") {
@@ -7,7 +7,7 @@ use {
     std::str,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Rc {
     pub config: Config,
     pub load: Option<Vec<String>>,
@@ -18,20 +18,6 @@ pub struct Rc {
     pub lib: Option<Vec<String>>,
 }
 
-impl Default for Rc {
-    fn default() -> Rc {
-        Rc {
-            config: Config::default(),
-            load: None,
-            loader: None,
-            options: None,
-            reader: None,
-            require: None,
-            lib: None,
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct RcBuilder {
     pub json: JsonValue,
@@ -200,10 +186,7 @@ impl Rc {
 
     pub fn option(&self, opt: &str) -> bool {
         match &self.options {
-            Some(vec) => match vec.iter().find(|string| *string == opt) {
-                Some(_) => true,
-                None => false,
-            },
+            Some(vec) => vec.iter().find(|string| *string == opt).is_some(),
             None => false,
         }
     }
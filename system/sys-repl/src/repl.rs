@@ -7,27 +7,22 @@ use {
 
 pub fn repl(env: &Env, ns: String) -> Result<Tag> {
     let eof_value = Mu::eval_str(env, ":%eof%")?;
-    let flush_form = Mu::compile(env, Mu::read_str(env, "(mu:flush mu:*standard-output*)")?)?;
 
+    // forms are read on each trip around the loop, a top-level
+    // evaluation may compact the heap and move anything we hold
     let read_form = match ns.as_str() {
-        "mu" => Mu::read_str(env, "(mu:compile (mu:read mu:*standard-input* () :%eof%))")?,
-        "core" => Mu::read_str(
-            env,
-            "(mu:compile (core:read mu:*standard-input* () :%eof%))",
-        )?,
-        _ => Mu::read_str(
-            env,
-            "(core:compile (core:read mu:*standard-input* () :%eof%))",
-        )?,
+        "mu" => "(mu:compile (mu:read mu:*standard-input* () :%eof%))",
+        "core" => "(mu:compile (core:read mu:*standard-input* () :%eof%))",
+        _ => "(core:compile (core:read mu:*standard-input* () :%eof%))",
     };
 
     let prompt = format!("{ns}> ");
 
     loop {
         Mu::write_str(env, prompt.as_str(), Mu::std_out())?;
-        Mu::eval(env, flush_form)?;
+        Mu::eval_str(env, "(mu:flush mu:*standard-output*)")?;
 
-        match Mu::eval(env, read_form) {
+        match Mu::eval_str(env, read_form) {
             Ok(expr) => {
                 if Mu::eq(expr, eof_value) {
                     break Ok(eof_value);
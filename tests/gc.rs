@@ -0,0 +1,114 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// garbage collection
+use mu::{Env, Mu};
+
+fn env(config: &str) -> Env {
+    Mu::env(&Mu::config(Some(config.to_string())))
+}
+
+fn eval(env: &Env, expr: &str) -> String {
+    let tag = Mu::eval_str(env, expr).unwrap();
+
+    Mu::write_to_string(env, tag, true)
+}
+
+fn define(env: &Env, name: &str, lambda: &str) {
+    eval(
+        env,
+        &format!(r#"(mu:intern (mu:find-namespace "gc") "{name}" (mu:compile '{lambda}))"#),
+    );
+}
+
+#[test]
+fn deep_car_nest() {
+    let env = env(r#"{"gc-mode":"auto"}"#);
+
+    eval(&env, r#"(mu:make-namespace "gc")"#);
+    define(
+        &env,
+        "nest",
+        "(:lambda (n l) (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (mu:cdr p) ())))) (mu:cons n l))))",
+    );
+    define(
+        &env,
+        "depth",
+        "(:lambda (n l) (mu:car (mu:fix (:lambda (p) (:if (mu:eq (mu:cdr p) ()) p (mu:cons (mu:add (mu:car p) 1) (mu:car (mu:cdr p))))) (mu:cons n l))))",
+    );
+
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "gc") "nest-20k" (gc:nest 20000 ()))"#,
+    );
+    eval(&env, "(mu:gc)");
+
+    assert_eq!(eval(&env, "(gc:depth 0 gc:nest-20k)"), "20000");
+    assert!(Mu::heap_verify(&env).is_empty());
+}
+
+#[test]
+fn pinned_across_evaluations() {
+    let env = env(r#"{"gc-mode":"none"}"#);
+
+    eval(&env, r#"(mu:make-namespace "gc")"#);
+    define(
+        &env,
+        "list",
+        "(:lambda (n l) (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (mu:car p) (mu:cdr p))))) (mu:cons n l))))",
+    );
+
+    // garbage below a symbol held on the rust side
+    eval(&env, "(mu:length (gc:list 1000 ()))");
+
+    let symbol = Mu::eval_str(&env, r#"(mu:make-symbol "pinned")"#).unwrap();
+    let pins = Mu::pin(&env, &[symbol]);
+
+    eval(&env, "(mu:gc)");
+    eval(&env, "(mu:length (gc:list 1000 ()))");
+
+    assert_eq!(Mu::write_to_string(&env, symbol, true), "#:pinned");
+    assert!(Mu::heap_verify(&env).is_empty());
+
+    Mu::unpin(&env, pins);
+}
+
+#[test]
+fn garbage_under_ceiling() {
+    let env = env(r#"{"pages":64,"max-pages":64,"gc-mode":"auto","gc-threshold":65536}"#);
+
+    eval(&env, r#"(mu:make-namespace "gc")"#);
+    define(
+        &env,
+        "garbage",
+        "(:lambda (n) (:if (mu:eq (mu:fix (:lambda (n) (:if (mu:eq n 0) 0 (mu:sub (mu:car (mu:cons n (mu:cons n ()))) 1))) n) 0) :done ()))",
+    );
+
+    // collected whole before the heap would have to grow
+    assert_eq!(eval(&env, "(gc:garbage 50000)"), ":done");
+}
+
+#[test]
+fn future_across_compaction() {
+    let env = env(r#"{"gc-mode":"auto"}"#);
+
+    eval(&env, r#"(mu:make-namespace "gc")"#);
+    define(
+        &env,
+        "list",
+        "(:lambda (n l) (:if (mu:eq n 0) l (gc:list (mu:sub n 1) (mu:cons n l))))",
+    );
+
+    // garbage below the handle, the collection leaves the heap
+    // fragmented and the next evaluation compacts it
+    eval(&env, "(mu:length (gc:list 1000 ()))");
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "gc") "future" ((:alambda (n) (mu:length (gc:list n ()))) 1000))"#,
+    );
+    eval(&env, "(mu:gc)");
+    eval(&env, "(mu:length (gc:list 1000 ()))");
+
+    assert_eq!(eval(&env, "(mu:await gc:future)"), "1000");
+    assert!(Mu::heap_verify(&env).is_empty());
+}
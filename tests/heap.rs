@@ -0,0 +1,305 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// heap growth and persistent heap files
+use mu::{Env, Mu};
+
+fn heap_file(name: &str) -> String {
+    let path = format!("/var/tmp/mu.{}.{name}.heap", std::process::id());
+
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn env(path: &str) -> Env {
+    Mu::env(&heap_config(path, 64))
+}
+
+fn heap_config(path: &str, pages: usize) -> mu::Config {
+    Mu::config(Some(format!(
+        "{{\"pages\":{pages},\"heap-file\":\"{path}\"}}"
+    )))
+}
+
+// heap:loop conses a list of n fixnums onto l, iterating with mu:fix
+fn with_loop(env: &Env) {
+    eval(env, r#"(mu:make-namespace "heap")"#);
+    eval(
+        env,
+        r#"(mu:intern (mu:find-namespace "heap") "loop" (mu:compile '(:lambda (n l) (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (mu:car p) (mu:cdr p))))) (mu:cons n l))))))"#,
+    );
+}
+
+// heap:bytes makes a :byte vector of n elements, (i + 1) mod 256
+fn with_bytes(env: &Env) {
+    eval(env, r#"(mu:make-namespace "heap")"#);
+    eval(
+        env,
+        r#"(mu:intern (mu:find-namespace "heap") "bytes" (mu:compile '(:lambda (n) (mu:make-vector :byte (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (mu:logand (mu:car p) 255) (mu:cdr p))))) (mu:cons n ())))))))"#,
+    );
+}
+
+// heap:chars makes a :char vector of n characters, #\λ at even indices
+fn with_chars(env: &Env) {
+    eval(env, r#"(mu:make-namespace "heap")"#);
+    eval(
+        env,
+        r#"(mu:intern (mu:find-namespace "heap") "chars" (mu:compile '(:lambda (n) (mu:make-vector :char (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (:if (mu:eq (mu:logand (mu:car p) 1) 0) #\a #\λ) (mu:cdr p))))) (mu:cons n ())))))))"#,
+    );
+}
+
+fn eval(env: &Env, expr: &str) -> String {
+    let tag = Mu::eval_str(env, expr).unwrap();
+
+    Mu::write_to_string(env, tag, true)
+}
+
+#[test]
+fn save_and_reopen() {
+    let path = heap_file("reopen");
+
+    {
+        let env = env(&path);
+
+        eval(
+            &env,
+            r#"(mu:intern (mu:make-namespace "heap") "test" "saved")"#,
+        );
+    }
+
+    let env = env(&path);
+
+    assert_eq!(eval(&env, "heap:test"), "\"saved\"");
+    assert!(Mu::heap_verify(&env).is_empty());
+
+    drop(env);
+    std::fs::remove_file(&path).unwrap();
+}
+
+// syncs with feature/env:heap-sync
+#[test]
+#[cfg(feature = "env")]
+fn reopen_after_crash_between_syncs() {
+    let path = heap_file("crash");
+
+    {
+        let env = env(&path);
+
+        eval(
+            &env,
+            r#"(mu:intern (mu:make-namespace "heap") "test" "synced")"#,
+        );
+        eval(&env, "(feature/env:heap-sync)");
+
+        // change and collect after the sync, then exit without dropping
+        eval(
+            &env,
+            r#"(mu:intern (mu:find-namespace "heap") "test" "unsynced")"#,
+        );
+        eval(
+            &env,
+            r#"(mu:intern (mu:find-namespace "heap") "garbage" (mu:cons 1 2))"#,
+        );
+        eval(
+            &env,
+            r#"(mu:intern (mu:find-namespace "heap") "garbage" ())"#,
+        );
+        eval(&env, "(mu:gc)");
+
+        std::mem::forget(env);
+    }
+
+    let env = env(&path);
+
+    assert_eq!(eval(&env, "heap:test"), "\"synced\"");
+    assert!(Mu::heap_verify(&env).is_empty());
+
+    drop(env);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn grow_and_reopen() {
+    let path = heap_file("grow");
+
+    {
+        let env = env(&path);
+
+        with_loop(&env);
+        eval(
+            &env,
+            r#"(mu:intern (mu:find-namespace "heap") "list" (heap:loop 100000 ()))"#,
+        );
+    }
+
+    // the file keeps the size the heap grew to
+    assert!(std::fs::metadata(&path).unwrap().len() > 64 * page_size::get() as u64);
+
+    let env = env(&path);
+
+    assert_eq!(eval(&env, "(mu:length heap:list)"), "100000");
+    assert!(Mu::heap_verify(&env).is_empty());
+
+    drop(env);
+    std::fs::remove_file(&path).unwrap();
+}
+
+// a restart is a new process, the test binary runs restart_child in one
+#[test]
+fn streams_across_restart() {
+    let path = heap_file("streams");
+
+    for phase in ["save", "reopen"] {
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["restart_child", "--exact", "--ignored"])
+            .env("MU_RESTART", format!("{phase}:{path}"))
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stdout)
+        );
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[ignore = "run by streams_across_restart"]
+fn restart_child() {
+    let phase = std::env::var("MU_RESTART").unwrap();
+    let (phase, path) = phase.split_once(':').unwrap();
+    let env = env(path);
+
+    if phase == "save" {
+        eval(
+            &env,
+            r#"(mu:intern (mu:make-namespace "heap") "stream" (mu:open :string :input "saved" :t))"#,
+        );
+    } else {
+        // the saved stream reads as released, and a stream opened after the
+        // restart doesn't take its id
+        let stream = eval(&env, r#"(mu:open :string :input "fresh" :t)"#);
+
+        assert_ne!(eval(&env, "heap:stream"), stream);
+        assert_eq!(eval(&env, "(mu:openp heap:stream)"), ":nil");
+        assert_eq!(
+            eval(
+                &env,
+                "(mu:with-exception (:lambda (obj cond src) cond) (:lambda () (mu:read-char heap:stream () ())))"
+            ),
+            ":open"
+        );
+    }
+}
+
+#[test]
+fn max_pages() {
+    let env = Mu::env(&Mu::config(Some(
+        r#"{"pages":64,"max-pages":64}"#.to_string(),
+    )));
+
+    with_loop(&env);
+
+    assert_eq!(eval(&env, "(mu:length (heap:loop 1000 ()))"), "1000");
+    assert_eq!(
+        eval(
+            &env,
+            "(mu:with-exception (:lambda (obj cond src) cond) (:lambda () (heap:loop 200000 ())))"
+        ),
+        ":heap"
+    );
+}
+
+#[test]
+fn oversized_image() {
+    let env = Mu::env(&Mu::config(Some(
+        r#"{"pages":64,"max-pages":128}"#.to_string(),
+    )));
+
+    // an image larger than the heap ceiling raises instead of panicking
+    assert_eq!(
+        eval(
+            &env,
+            "(mu:with-exception (:lambda (obj cond src) cond) (:lambda () (mu:ash 1 40000000)))"
+        ),
+        ":heap"
+    );
+    assert_eq!(eval(&env, "(mu:ash 1 64)"), "18446744073709551616");
+    assert!(Mu::heap_verify(&env).is_empty());
+}
+
+#[test]
+fn fresh_heap_file() {
+    let path = heap_file("fresh");
+
+    // an empty file, or one without a trailer, opens a fresh heap
+    for len in [0, 4096] {
+        std::fs::write(&path, vec![0u8; len]).unwrap();
+
+        let env = Mu::try_env(&heap_config(&path, 64)).unwrap();
+
+        assert_eq!(eval(&env, "(mu:add 1 2)"), "3");
+        std::mem::forget(env);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn not_a_heap_file() {
+    let path = heap_file("invalid");
+    let size = 64 * page_size::get();
+
+    // a garbage trailer, and a heap image with garbage state
+    let mut image = vec![0u8; size + 16];
+
+    image.extend((size as u64).to_le_bytes());
+
+    for bytes in [(0..5000).map(|n| (n % 251) as u8).collect(), image] {
+        std::fs::write(&path, &bytes).unwrap();
+        assert!(Mu::try_env(&heap_config(&path, 64)).is_err());
+
+        // the file is left as it was
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn large_image() {
+    let env = Mu::env(&Mu::config(None));
+
+    with_bytes(&env);
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "heap") "vector" (heap:bytes 100000))"#,
+    );
+    eval(&env, "(mu:gc)");
+
+    assert_eq!(eval(&env, "(mu:vector-length heap:vector)"), "100000");
+    assert_eq!(eval(&env, "(mu:svref heap:vector 0)"), "1");
+    assert_eq!(eval(&env, "(mu:svref heap:vector 99999)"), "160");
+    assert!(Mu::heap_verify(&env).is_empty());
+}
+
+#[test]
+fn large_string() {
+    let env = Mu::env(&Mu::config(None));
+
+    with_chars(&env);
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "heap") "string" (heap:chars 100001))"#,
+    );
+    eval(&env, "(mu:gc)");
+
+    assert_eq!(eval(&env, "(mu:vector-length heap:string)"), "100001");
+    assert_eq!(eval(&env, "(mu:svref heap:string 0)"), "#\\λ");
+    assert_eq!(eval(&env, "(mu:svref heap:string 99999)"), "#\\a");
+    assert_eq!(eval(&env, "(mu:svref heap:string 100000)"), "#\\λ");
+    assert!(Mu::heap_verify(&env).is_empty());
+}
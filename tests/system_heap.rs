@@ -0,0 +1,137 @@
+//  SPDX-FileCopyrightText: Copyright 2025 James M. Putnam (putnamjm.design@gmail.com)
+//  SPDX-License-Identifier: MIT
+
+// environments sharing a system heap
+use {
+    mu::{Env, Mu, SystemHeap},
+    std::sync::Arc,
+};
+
+fn eval(env: &Env, expr: &str) -> String {
+    let tag = Mu::eval_str(env, expr).unwrap();
+
+    Mu::write_to_string(env, tag, true)
+}
+
+fn condition(env: &Env, expr: &str) -> String {
+    eval(
+        env,
+        &format!("(mu:with-exception (:lambda (obj cond src) cond) (:lambda () {expr}))"),
+    )
+}
+
+// a system heap with a bound symbol, a hash, and a function that
+// refers to a symbol it leaves unbound
+fn system() -> Arc<SystemHeap> {
+    let env = Mu::env(&Mu::config(None));
+
+    eval(&env, r#"(mu:make-namespace "sys")"#);
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "sys") "value" "system value")"#,
+    );
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "sys") "table" (mu:make-hash :equal ()))"#,
+    );
+    eval(&env, r#"(mu:hash-put sys:table "key" "system entry")"#);
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "sys") "get" (mu:compile '(:lambda () sys:unbound)))"#,
+    );
+
+    Mu::system_heap(&env)
+}
+
+fn system_env(system: &Arc<SystemHeap>) -> Env {
+    Mu::env_with_system(
+        &Mu::config(Some(r#"{"pages":64,"gc-mode":"auto"}"#.to_string())),
+        system,
+    )
+}
+
+#[test]
+fn shared_system_heap() {
+    let system = system();
+    let env = system_env(&system);
+    let env1 = system_env(&system);
+
+    for env in [&env, &env1] {
+        assert_eq!(eval(env, "sys:value"), "\"system value\"");
+        assert_eq!(
+            eval(env, r#"(mu:hash-get sys:table "key")"#),
+            r#"("key" . "system entry")"#
+        );
+        assert!(Mu::heap_verify(env).is_empty());
+    }
+}
+
+#[test]
+fn system_writes() {
+    let system = system();
+    let env = system_env(&system);
+    let env1 = system_env(&system);
+
+    // system hashes are read-only
+    assert_eq!(
+        condition(&env, r#"(mu:hash-put sys:table "key" "private entry")"#),
+        ":write"
+    );
+    assert_eq!(
+        condition(&env, r#"(mu:hash-rem sys:table "key")"#),
+        ":write"
+    );
+
+    // a bound system symbol keeps its value
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "sys") "value" "private value")"#,
+    );
+    assert_eq!(eval(&env, "sys:value"), "\"system value\"");
+
+    // an unbound one is shadowed in that environment only
+    eval(&env, r#"(mu:intern (mu:find-namespace "sys") "unbound" 1)"#);
+    assert_eq!(eval(&env, "(sys:get)"), "1");
+    assert_eq!(eval(&env1, "(mu:boundp 'sys:unbound)"), ":nil");
+
+    for env in [&env, &env1] {
+        assert_eq!(
+            eval(env, r#"(mu:hash-get sys:table "key")"#),
+            r#"("key" . "system entry")"#
+        );
+        assert!(Mu::heap_verify(env).is_empty());
+    }
+}
+
+#[test]
+fn private_collection() {
+    let system = system();
+    let env = system_env(&system);
+    let env1 = system_env(&system);
+
+    eval(&env1, r#"(mu:make-namespace "private")"#);
+    eval(
+        &env1,
+        r#"(mu:intern (mu:find-namespace "private") "list" (mu:cons "private" (mu:cons sys:value ())))"#,
+    );
+
+    // garbage, a collection, and the compaction that follows it
+    eval(&env, r#"(mu:make-namespace "garbage")"#);
+    eval(
+        &env,
+        r#"(mu:intern (mu:find-namespace "garbage") "loop" (mu:compile '(:lambda (n l) (mu:cdr (mu:fix (:lambda (p) (:if (mu:eq (mu:car p) 0) p (mu:cons (mu:sub (mu:car p) 1) (mu:cons (mu:car p) (mu:cdr p))))) (mu:cons n l))))))"#,
+    );
+    eval(&env, "(mu:length (garbage:loop 10000 ()))");
+    eval(&env, "(mu:gc)");
+    eval(&env, "(mu:length (garbage:loop 1000 ()))");
+
+    assert_eq!(eval(&env1, "private:list"), r#"("private" "system value")"#);
+    assert_eq!(
+        eval(&env1, r#"(mu:hash-get sys:table "key")"#),
+        r#"("key" . "system entry")"#
+    );
+
+    for env in [&env, &env1] {
+        assert!(Mu::heap_verify(env).is_empty());
+    }
+}